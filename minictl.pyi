@@ -11,7 +11,7 @@
 # and not all (ahum, vscode) support the proper method of adding
 # directly to `help()`
 
-from typing import Callable, Dict, List, Optional, Set
+from typing import Callable, Dict, List, Optional, Set, Tuple, Union
 
 def hello_world() -> str:
     """A method to be used exclusively for checking if everything is installed
@@ -39,7 +39,7 @@ class LTLFormula:
             - Variables, that must start with a lowercase letter, and be followed by:
                 - More lowercase letters
                 - Numbers
-                - The special characters "=" and "_"
+                - The special characters "=", "_", and "."
             - "TOP" and "BOT" to represent "⊤" and "⊥" respectively.
             - "!", which means "not" / "¬" and binds tightly
             - Any of the modal operators "X", "F", and "G", which bind tightly
@@ -48,6 +48,28 @@ class LTLFormula:
             - "->", "<-", and "<->" to represent right, left, and bi-implication, which bind loosely
             - Any amount of brackets "(.)" surrounding formulas to change variable binding.
         """
+    def to_nnf(self) -> LTLFormula:
+        """The negation normal form of this formula, in which "Neg" only occurs directly around variables,
+        and no implications are left. Negations are pushed inwards through the dual operators,
+        like "!F p" becoming "G !p", and "!(p U q)" becoming "!p R !q". "X" is its own dual.
+        """
+    def to_until_form(self) -> LTLFormula:
+        """An equivalent formula that only uses "TOP", variables, "Neg", "And", "X", and "U",
+        like "G p" becoming "!(TOP U !p)".
+        """
+    def simplify(self) -> Tuple[LTLFormula, List[str]]:
+        """Simplify this formula, returning the simplified formula and the rules that were applied, in order.
+        The rules are double negation, absorption of "TOP" and "BOT", idempotence ("p and p" and "p U p" are "p"),
+        nested operators ("F F p" is "F p", "F G F p" is "G F p", and the same with "F" and "G" swapped),
+        and merging ("G p and G q" is "G (p and q)", "F p or F q" is "F (p or q)").
+        """
+    def pretty(self, style: str = "unicode") -> str:
+        """Print this formula with as few brackets as possible, in one of three styles:
+            - "ascii", like "G (p -> F !q)", which LTLFormula.parse reads back into the same formula.
+            - "unicode", like "G (p → F ¬q)".
+            - "latex", like "\\mathrm{G}\\, (p \\rightarrow \\mathrm{F}\\, \\neg q)", for LaTeX math mode.
+        Unlike str(), which puts brackets around every subformula.
+        """
 
     def __eq__(self, value: object, /) -> bool: ...
     def __str__(self) -> str: ...
@@ -74,16 +96,162 @@ class CTLFormula:
             - Variables, that must start with a lowercase letter, and be followed by:
                 - More lowercase letters
                 - Numbers
-                - The special characters "=" and "_"
+                - The special characters "=", "_", and "."
             - "TOP" and "BOT" to represent "⊤" and "⊥" respectively.
             - "!", which means "not" / "¬" and binds tightly
             - Any of the modal operators "EX", "AX", "EF", "AF", "EG", and "AG", which bind tightly
             - The modal operators "E[. U .]" and "A[. U .]", with something valid in place of the dots.
+            - Their duals, the release operators "E[. R .]" and "A[. R .]".
                 - The square brackets instead of round ones are mandatory, and make it unambiguous.
             - "or" and "and" (surrounded by spaces) to represent "∨" and "∧"
             - "->", "<-", and "<->" to represent right, left, and bi-implication, which bind loosely
             - Any amount of brackets "(.)" surrounding formulas to change variable binding.
         """
+    def to_enf(self) -> CTLFormula:
+        """The existential normal form of this formula, which only uses "TOP", variables,
+        "Neg", "And", "EX", "EU", and "EG", the operators the CTLChecker computes directly.
+        Like the CTLChecker, it rewrites "AF p" into "!EG !p", and "AG p" into "!E[TOP U !p]".
+        """
+    def to_nnf(self) -> CTLFormula:
+        """The negation normal form of this formula, in which "Neg" only occurs directly around variables,
+        and no implications are left. Negations are pushed inwards through the dual operators,
+        like "!EF p" becoming "AG !p", and "!E[p U q]" becoming "A[!p R !q]".
+        """
+    def simplify(self) -> Tuple[CTLFormula, List[str]]:
+        """Simplify this formula, returning the simplified formula and the rules that were applied, in order.
+        The rules are double negation, absorption of "TOP" and "BOT", idempotence ("p and p" is "p"),
+        nested operators ("AG AG p" is "AG p", and the same for "EF", "AF", and "EG"),
+        and merging ("AG p and AG q" is "AG (p and q)", "EF p or EF q" is "EF (p or q)").
+        The simplified formula holds in exactly the same states, also with sinks or fairness constraints.
+        """
+    def pretty(self, style: str = "unicode") -> str:
+        """Print this formula with as few brackets as possible, in one of three styles:
+            - "ascii", like "AG (p -> EF !q)", which CTLFormula.parse reads back into the same formula.
+            - "unicode", like "AG (p → EF ¬q)".
+            - "latex", like "\\mathrm{AG}\\, (p \\rightarrow \\mathrm{EF}\\, \\neg q)", for LaTeX math mode.
+        Unlike str(), which puts brackets around every subformula.
+        """
+
+    def __eq__(self, value: object, /) -> bool: ...
+    def __str__(self) -> str: ...
+    def __hash__(self) -> int: ...
+
+class CTLStarFormula:
+    """The python view into the CTLStarFormula, a formula of CTL*.
+    This class is frozen. Objects, once created, cannot be modified.
+
+    The path quantifiers "E" and "A" and the temporal operators "X", "F", "G", "U", "W", and "R"
+    are separate formulas, so that quantifiers can be put in front of any path formula, like
+    `CTLStarFormula("E", CTLStarFormula("G", CTLStarFormula("p")))`.
+    The same formula can be created through the .parse method like: CTLStarFormula.parse("E G p")
+
+    Implements `__str__`, `__eq__`, and `__hash__`.
+    """
+
+    name: str
+    arguments: List[CTLStarFormula]
+    def __init__(self, name: str, *args: CTLStarFormula) -> None: ...
+    @staticmethod
+    def parse(formula: str) -> CTLStarFormula:
+        """Parse a string representing a CTLStarFormula into a CTLStarFormula
+        The parser allows formulas with the following elements:
+            - Variables, that must start with a lowercase letter, and be followed by:
+                - More lowercase letters
+                - Numbers
+                - The special characters "=", "_", and "."
+            - "TOP" and "BOT" to represent "⊤" and "⊥" respectively.
+            - "!", which means "not" / "¬" and binds tightly
+            - The path quantifiers "E" and "A", and the modal operators "X", "F", and "G", which bind tightly.
+              These are single letters, so they can be stacked without spaces, like "AG EF p" or "E GF p".
+            - The modal operators "U", "W", and "R".
+            - "or" and "and" (surrounded by spaces) to represent "∨" and "∧"
+            - "->", "<-", and "<->" to represent right, left, and bi-implication, which bind loosely
+            - Any amount of brackets "(.)" surrounding formulas to change variable binding.
+              Square brackets "[.]" can be used the same way, so CTL formulas like "E[p U q]" parse as-is.
+        """
+
+    def __eq__(self, value: object, /) -> bool: ...
+    def __str__(self) -> str: ...
+    def __hash__(self) -> int: ...
+
+class MuFormula:
+    """The python view into the MuFormula, a formula of the modal mu-calculus.
+    This class is frozen. Objects, once created, cannot be modified.
+
+    Next to the lowercase propositions of the model, formulas can contain fixpoint variables,
+    which have an uppercase name, like `MuFormula("X")`. These are bound by the fixpoint operators,
+    which take such a variable as their first argument, like
+    `MuFormula("Mu", MuFormula("X"), MuFormula("Or", MuFormula("p"), MuFormula("Diamond", MuFormula("X"))))`.
+    The same formula can be created through the .parse method like: MuFormula.parse("mu X. p or <>X")
+
+    Implements `__str__`, `__eq__`, and `__hash__`.
+    """
+
+    name: str
+    arguments: List[MuFormula]
+    def __init__(self, name: str, *args: MuFormula) -> None: ...
+    @staticmethod
+    def parse(formula: str) -> MuFormula:
+        """Parse a string representing a MuFormula into a MuFormula
+        The parser allows formulas with the following elements:
+            - Propositions, that must start with a lowercase letter, and be followed by:
+                - More lowercase letters
+                - Numbers
+                - The special characters "=", "_", and "."
+            - Fixpoint variables, that are written in uppercase letters, numbers, and "_", like "X" or "Z1".
+            - "TOP" and "BOT" to represent "⊤" and "⊥" respectively.
+            - "!", which means "not" / "¬" and binds tightly
+            - The modalities "<>" and "[]", meaning "some successor" and "all successors", which bind tightly
+            - "mu X." and "nu X." for the least and greatest fixpoint, whose body extends as far right as possible.
+            - "or" and "and" (surrounded by spaces) to represent "∨" and "∧"
+            - "->", "<-", and "<->" to represent right, left, and bi-implication, which bind loosely
+            - Any amount of brackets "(.)" surrounding formulas to change variable binding.
+        """
+    @staticmethod
+    def from_ctl(formula: CTLFormula) -> MuFormula:
+        """Translate a CTLFormula into an equivalent MuFormula.
+        Every CTL operator becomes a fixpoint over the modalities, like "EF p" becoming "mu Z0. p or <>Z0",
+        which is exactly the loop the CTLChecker runs to compute it.
+        The fixpoint variables are named "Z0", "Z1", ... after how deeply they are nested.
+        """
+
+    def __eq__(self, value: object, /) -> bool: ...
+    def __str__(self) -> str: ...
+    def __hash__(self) -> int: ...
+
+class HMLFormula:
+    """The python view into the HMLFormula, a formula of Hennessy–Milner logic.
+    This class is frozen. Objects, once created, cannot be modified.
+
+    The modalities "Diamond" and "Box" take the action of the transitions they look at
+    as the `action` keyword argument, like
+    `HMLFormula("Diamond", HMLFormula("Box", HMLFormula("BOT"), action="coffee"), action="coin")`.
+    For all other formulas, `action` is None.
+    The same formula can be created through the .parse method like: HMLFormula.parse("<coin>[coffee]BOT")
+
+    Implements `__str__`, `__eq__`, and `__hash__`.
+    """
+
+    name: str
+    arguments: List[HMLFormula]
+    action: Optional[str]
+    def __init__(self, name: str, *args: HMLFormula, action: Optional[str] = None) -> None: ...
+    @staticmethod
+    def parse(formula: str) -> HMLFormula:
+        """Parse a string representing an HMLFormula into an HMLFormula
+        The parser allows formulas with the following elements:
+            - Propositions, that must start with a lowercase letter, and be followed by:
+                - More lowercase letters
+                - Numbers
+                - The special characters "=", "_", and "."
+            - "TOP" and "BOT" to represent "⊤" and "⊥" respectively.
+            - "!", which means "not" / "¬" and binds tightly
+            - The modalities "<a>" and "[a]", meaning "some a-transition" and "all a-transitions", which bind tightly.
+              The action can be any name without whitespace or brackets.
+            - "or" and "and" (surrounded by spaces) to represent "∨" and "∧"
+            - "->", "<-", and "<->" to represent right, left, and bi-implication, which bind loosely
+            - Any amount of brackets "(.)" surrounding formulas to change variable binding.
+        """
 
     def __eq__(self, value: object, /) -> bool: ...
     def __str__(self) -> str: ...
@@ -113,6 +281,10 @@ class Model:
     This constructor throws a value error when the arguments do not lead to a valid frame,
     e.g. if edges point to unknown states.
 
+    Transitions can also be labelled with an action, by giving them as a tuple of the action
+    and the target state, like {"s1": [("a", "s2"), "s1"]}. This makes the model a labelled
+    transition system. Checkers for state-based logics like CTL simply ignore the actions.
+
     As a third optional argument, you can pass a list of initial states (e.g. ["s1"]),
     if this is not passed, the first state in the list is marked as initial.
 
@@ -123,7 +295,7 @@ class Model:
     def __init__(
         self,
         states: List[State],
-        edges: Dict[str, list[str]],
+        edges: Dict[str, List[str | Tuple[str, str]]],
         initial_states: Optional[List[str]] = None,
         *,
         allow_sink: bool = False,
@@ -143,18 +315,33 @@ class Model:
     def all_except(self, names: Set[str]) -> set[str]:
         """Get all states except those with the input names"""
 
-    def pre_e(self, names: Set[str]) -> set[str]:
+    def pre_e(self, names: Set[str], actions: Optional[Set[str]] = None) -> set[str]:
         """Get all states that have at least one outgoing edge to a state which name is
         in the input set of names
         pre_e(Y) = {s ∈ S | exists s', (s -> s' and s' ∈ Y)}
+
+        If `actions` is given, only the edges labelled with one of those actions count
+        pre_e(Y, A) = {s ∈ S | exists s', a ∈ A, (s -a-> s' and s' ∈ Y)}
         """
 
-    def pre_a(self, names: Set[str]) -> set[str]:
+    def pre_a(self, names: Set[str], actions: Optional[Set[str]] = None) -> set[str]:
         """Get all states of which all outgoing edges are to a state which name is
         in the input set of names
         pre_a(Y) = {s ∈ S | for all s', (s -> s' implies s' ∈ Y)}
+
+        If `actions` is given, only the edges labelled with one of those actions count,
+        so this includes the states without any such edges
+        pre_a(Y, A) = {s ∈ S | for all s', a ∈ A, (s -a-> s' implies s' ∈ Y)}
+        """
+
+    def get_transitions(self, name: str) -> List[Tuple[Optional[str], str]]:
+        """Get the outgoing transitions of the input state, as tuples of their action and target state,
+        in the order they were given. Transitions without an action have `None` as their action.
         """
 
+    def all_actions(self) -> Set[str]:
+        """Get the set of all actions that label some transition of the model"""
+
     def get_next(self, name: str) -> Set[str]:
         """Get the set of names of all the states that the input state
         has outgoing connections to.
@@ -163,6 +350,151 @@ class Model:
     def all_initial(self) -> Set[str]:
         """Get the set of names of all the states that were marked as initial"""
 
+    @staticmethod
+    def parse(text: str, *, allow_sink: bool = False) -> Model:
+        """Create a model from its plain-text description. This looks like:
+        ```
+        # Comments start with a '#' and run until the end of the line
+        state s1 : p q      # a state, with the propositions that are true in it
+        state s2            # a state in which nothing is true
+        s1 -> s1 s2         # transitions, which can be spread over multiple lines
+        s2 -> s1 : a        # transitions labelled with the action a
+        initial s1          # the initial states. If this is left out, the first state is initial
+        ```
+        Names with whitespace or any of '#', ':', '"', or '->' in them must be written in double quotes.
+        The order of the lines does not matter.
+
+        This throws a value error mentioning the line number if the text cannot be parsed,
+        and, like the constructor, if some state has no outgoing transitions and `allow_sink` is not set.
+        """
+
+    @staticmethod
+    def parse_program(
+        text: str, *, allow_sink: bool = False
+    ) -> Tuple[Model, List[Union[CTLFormula, LTLFormula]]]:
+        """Compile a program in a small guarded-command language, like that of SMV, into a model.
+        Returns the model together with the specs of the program, in the order they were written.
+        A program looks like:
+        ```
+        VAR                                     # finite-domain variables
+          step : {acquire, access, release, done};
+          lock : {free, held};
+          tries : 0..3;                         # a range of numbers
+        INIT                                    # the initial states
+          step=acquire and lock=free and tries=0;
+        TRANS                                   # guarded commands, as guard : updates
+          step=acquire and lock=free : step := access, lock := held;
+          step=acquire and lock=held : tries := tries + 1;
+          step=access : step := {access, release};   # a non-deterministic choice
+          step=release : step := done, lock := free;
+          step=done : ;                         # no updates: stay in the same state
+        CTLSPEC AG(step=access -> lock=held);
+        LTLSPEC G(lock=held -> F lock=free);
+        ```
+        Statements end with a ';'. Guards and INIT conditions are propositional formulas.
+        Variables that a command does not update keep their value.
+
+        The states are the assignments reachable from those satisfying the INIT conditions.
+        They are named like "step=acquire,lock=free,tries=0", and satisfy the propositions
+        "step=acquire", "lock=free", and "tries=0". States in which no guard holds have no outgoing edges.
+        This throws a ValueError mentioning the line number if the program cannot be compiled,
+        and if some state has no outgoing transitions and `allow_sink` is not set.
+        """
+
+    @staticmethod
+    def synchronous_product(
+        components: List[Tuple[str, Model]], *, synchronise: bool = False
+    ) -> Model:
+        """The synchronous product of the components, where all of them take a step at the same time.
+        Every component comes with a name, which prefixes its variables in the product,
+        so the variable "x" of the component named "p1" becomes "p1.x".
+        States are named after the states of the components, joined by commas, like "s0,t1",
+        and only the states reachable from the initial states are part of the product.
+
+        If `synchronise` is set, the components can only step together on transitions with the same action.
+        Otherwise, any combination of transitions can be taken, and the product transition only keeps
+        the action if all of them share it.
+        As the product can only step if all components can, it can have states without outgoing edges.
+        This throws a ValueError if there are no components, or two of them have the same name.
+        """
+    @staticmethod
+    def interleaving_product(
+        components: List[Tuple[str, Model]], *, synchronise: bool = False
+    ) -> Model:
+        """The interleaving (or asynchronous) product of the components, where one of them takes a step at a time,
+        while the others stay where they are. Every transition keeps its action.
+        Variables and states are named as in `synchronous_product`.
+
+        If `synchronise` is set, actions that occur in more than one component can only be taken
+        by all components that have them at the same time, and not by any of them alone.
+        Unlabelled transitions and actions of only one component are still interleaved.
+        This throws a ValueError if there are no components, or two of them have the same name.
+        """
+    def minimise_bisimulation(self) -> Tuple[Model, Dict[str, str]]:
+        """Returns the smallest model bisimilar to this one, in which all bisimilar states are merged,
+        together with a dict mapping every state of this model to the state it was merged into.
+        Merged states are named after the first of their states, in the order they were given in,
+        and keep their variables. Transitions are only matched with transitions with the same action.
+
+        As none of the logics can tell bisimilar states apart, every formula holds in a state
+        of this model exactly when it holds in the state it was merged into.
+        """
+    def bisimulation(
+        self, other: Model
+    ) -> Tuple[Optional[Set[Tuple[str, str]]], Optional[CTLFormula]]:
+        """Decide whether the initial states of this model and the other are bisimilar:
+        whether every initial state of either is bisimilar to some initial state of the other.
+        Action labels are ignored, as they are in CTL.
+
+        Returns a pair of which exactly one is set:
+            - The largest bisimulation, as pairs of a state of this model and one of the other.
+            - A CTL formula that holds in all initial states of exactly one of the two models.
+              It only uses variables, negation, conjunction, disjunction, EX, and AX.
+        """
+    def simulated_by(
+        self, other: Model
+    ) -> Tuple[Optional[Set[Tuple[str, str]]], Optional[CTLFormula]]:
+        """Decide whether the other model simulates this one:
+        whether every initial state of this model is simulated by some initial state of the other.
+        Action labels are ignored, as they are in CTL.
+
+        Returns a pair of which exactly one is set:
+            - The largest simulation, as pairs of a state of this model and a state of the other that simulates it.
+            - An ACTL formula that holds in all initial states of the other model, but not in those of this one.
+              As ACTL formulas that hold in a model hold in all models it simulates, this shows why it is not simulated.
+              It only uses variables, their negations, disjunction, and AX.
+        """
+    def to_text(self) -> str:
+        """Print this model in the format read by `Model.parse`, such that parsing it gives back the same model"""
+
+    def to_dot(self, highlight: Optional[Set[str]] = None) -> str:
+        """Export this model to Graphviz DOT, with states labelled with their variables,
+        transitions labelled with their actions, and initial states marked with an incoming arrow.
+        If `highlight` is given, e.g. the result of `CTLChecker.check`, the states in it are coloured.
+        This throws a key error if `highlight` contains states that are not in the model.
+
+        In a notebook, the result can be rendered with `graphviz.Source(model.to_dot())`.
+        """
+
+class TraceStep:
+    """
+    One step of the computation of a CTLChecker created with `trace=True`.
+    This class is frozen. Objects, once created, cannot be modified.
+
+    `kind` is either "rewrite", for a formula that was computed through the equivalent `rewritten` one,
+    or "iteration", for the set of `states` after iteration number `iteration` of the fixpoint
+    computation of an EU or AF `formula`. Iteration 0 is the starting set, and the last iteration
+    is the same as the one before it, as that is when the fixpoint is reached.
+    The fields that do not apply to the kind of step are None.
+
+    Implements `__str__`, like "E[(⊤)U(p)] [1]: {s1, s2}".
+    """
+    kind: str
+    formula: CTLFormula
+    rewritten: Optional[CTLFormula]
+    iteration: Optional[int]
+    states: Optional[Set[str]]
+
 # fmt: off
 class CTLChecker:
     """
@@ -173,25 +505,78 @@ class CTLChecker:
 
     In Python, you can create this class from a model with the
     CTLChecker(model) constructor.
+
+    Optionally, a list of fairness constraints can be passed with the `fairness` keyword argument,
+    like CTLChecker(model, fairness=[CTLFormula.parse("p")]). The path quantifiers then only range
+    over fair paths: paths on which every constraint holds infinitely often. The constraints
+    themselves are evaluated as normal CTL formulas. States from which no fair path starts
+    vacuously satisfy all universal formulas.
+
+    With `reachable_only=True`, all states that cannot be reached from an initial state are removed
+    from the model before checking, which does not change the results in the remaining states.
+    `get_model` then returns the restricted model, and `nr_pruned` the number of states removed.
+
+    With `trace=True`, the checker records every rewrite it does (like AG φ into ¬EF¬φ),
+    and the set of states after every iteration of its EU and AF fixpoint computations.
+    These can be taken out with `take_trace`.
     """
-    def __init__(self, model: Model) -> None: ...
+    def __init__(
+        self,
+        model: Model,
+        *,
+        fairness: Optional[List[CTLFormula]] = None,
+        reachable_only: bool = False,
+        trace: bool = False,
+    ) -> None: ...
     def check(self, formula: CTLFormula, debug: bool = False) -> Set[str]:
         """Returns the names of the set of states in which the provided formula is true,
         if the parameter "debug" is passed as `True`, this will fail early in case a custom
         algorithm is applied and its output is not the expected output.
         """
+    def holds_initially(self, formula: CTLFormula) -> Dict[str, bool]:
+        """Returns, for every initial state of the model, whether the provided formula is true in it.
+        This saves intersecting the result of `check` with `model.all_initial()` by hand.
+        """
+    def nr_pruned(self) -> int:
+        """Returns the number of unreachable states removed from the model with `reachable_only`"""
+    def take_trace(self) -> List[TraceStep]:
+        """Returns the steps recorded since the checker was created with `trace=True`, or since the last call,
+        in the order in which they happened. Results are cached, so every subformula is only computed once,
+        and only shows up in the trace of the first formula that needs it. Formulas computed with a custom
+        algorithm from `set_custom` do not show up either. Without `trace=True`, this is always empty.
+        """
     def is_modified(self) -> bool:
         """Returns whether this checker has a custom algorithm applied"""
 
+    def is_fair(self) -> bool:
+        """Returns whether this checker was created with fairness constraints"""
+
     def get_model(self) -> Model:
-        """Returns the model with which the checker was created"""
+        """Returns the model with which the checker was created, restricted to its reachable states
+        if it was created with `reachable_only=True`"""
+    def witness(self, state: str, formula: CTLFormula) -> Optional[Tuple[List[str], List[str]]]:
+        """Returns a path starting in the input state showing that the existential formula holds there.
+        The path is returned as a tuple of a finite prefix of state names, and a loop of state names
+        that is repeated forever after it. For "EX", "EF" and "E[.U.]" the path is finite,
+        and the loop is empty, while for "EG", the loop is what shows the formula holds.
+        Without fairness constraints, an "EG" path can also be finite, if it ends in a sink state.
+        If the formula is not of one of these shapes, or it does not hold in the state, this returns `None`.
+        """
+    def counterexample(self, state: str, formula: CTLFormula) -> Optional[Tuple[List[str], List[str]]]:
+        """Returns a path starting in the input state showing that the universal formula does not hold there,
+        in the same shape as `witness`. For "AX" and "AG", the path is finite, for "AF", it contains a loop
+        (unless it ends in a sink state),
+        and for "A[.U.]" it can be either.
+        If the formula is not of one of these shapes, or it does hold in the state, this returns `None`.
+        """
     def set_custom(
         self,
         target: str,
         func: Callable[[Set[str], Model], set[str]] | Callable[[set[str], set[str], Model], set[str]],
     ) -> None:
         """ Set a new custom algorithm for the checker. This is only possible if it
-        has not checked any formulas jet for cache invalidation reasons.
+        has not checked any formulas jet for cache invalidation reasons,
+        and if it was not created with fairness constraints.
         These functions must:
            - As their first argument(s) take the set of states in which the dependent formulas are true
            - As the last argument, take an argument "model", of type "Model".
@@ -199,5 +584,140 @@ class CTLChecker:
         For example, for the algorithm for EFϕ, the first argument is the set of states in which ϕ is true,
         and the second argument is `model` and for the algorithm for E[ϕUψ] must take as its first argument
         the set of states in which ϕ is true,as the second argument the set of states in which ψ is true,
-        and as a third argument `model`
+        and as a third argument `model`. The targets are "EX", "AX", "EF", "AF", "EG", "AG",
+        and the binary "EU", "AU", "ER", and "AR".
+        """
+
+class SymbolicCTLChecker:
+    """
+    The Python view into the symbolic CTL Checker
+    Though this class is not frozen, you cannot modify it directly.
+    The object will update itself on calls of `check` by updating the cache.
+
+    This checker represents sets of states and the transition relation as binary decision diagrams (BDDs),
+    and computes the fixpoints for EX, EU, and EG on those.
+    It does not support fairness, witnesses, or custom algorithms.
+
+    Created from a model with the SymbolicCTLChecker(model) constructor, it encodes every state by its index,
+    so it still goes over all states and edges of the model once, and is not faster than `CTLChecker`.
+    Created from a program with `SymbolicCTLChecker.from_program(text)`, it encodes the values of the variables
+    instead, and never lists the states, so it can check programs with far more states than `Model.parse_program`
+    can compile. In both cases, the results are the same as those of `CTLChecker`.
+    """
+    def __init__(self, model: Model) -> None: ...
+    @staticmethod
+    def from_program(
+        text: str, *, allow_sink: bool = False
+    ) -> Tuple[SymbolicCTLChecker, List[Union[CTLFormula, LTLFormula]]]:
+        """Encode a program in the language of `Model.parse_program`, without listing its states.
+        Returns the checker together with the specs of the program, in the order they were written.
+        The states, and so the results of `check`, are named like those of `Model.parse_program`.
+        This throws a ValueError on the same programs as `Model.parse_program`.
+        """
+    def check(self, formula: CTLFormula) -> Set[str]:
+        """Returns the names of the set of states in which the provided formula holds.
+        For large programs, prefer `holds`, as this has to list every state in the set.
+        """
+    def holds(self, formula: CTLFormula) -> bool:
+        """Returns whether the provided formula holds in all initial states"""
+    def get_model(self) -> Optional[Model]:
+        """Returns the model with which the checker was created, or None if it was created from a program"""
+
+class LTLChecker:
+    """
+    The Python view into the LTL Checker
+    Though this class is not frozen, you cannot modify it directly.
+    The object will update itself on calls of `check` by updating the cache.
+
+    In Python, you can create this class from a model with the
+    LTLChecker(model) constructor.
+    """
+    def __init__(self, model: Model) -> None: ...
+    def check(self, formula: LTLFormula) -> Set[str]:
+        """Returns the names of the set of states from which all paths satisfy the provided formula.
+        As LTL formulas talk about infinite paths, states from which no infinite path exists
+        (because every path ends in a sink) vacuously satisfy every formula.
         """
+    def holds(self, formula: LTLFormula) -> bool:
+        """Returns whether all paths starting from any of the initial states of the model
+        satisfy the provided formula.
+        """
+    def counterexample(self, formula: LTLFormula) -> Optional[Tuple[List[str], List[str]]]:
+        """Searches for a path starting in one of the initial states that violates the provided formula.
+        If there is one, it is returned as a "lasso": a tuple of a finite prefix of state names,
+        and a non-empty loop of state names that is repeated forever after the prefix.
+        If there is none (meaning the formula holds), this returns `None`.
+        """
+    def get_model(self) -> Model:
+        """Returns the model with which the checker was created"""
+
+class CTLStarChecker:
+    """
+    The Python view into the CTL* Checker
+    Though this class is not frozen, you cannot modify it directly.
+    The object will update itself on calls of `check` by updating the cache.
+
+    Path formulas under "E" and "A" can be nested arbitrarily, like in "E(GF p and FG q)".
+    These are checked by labelling the states with their state subformulas, like in CTL,
+    and then checking the remaining path formula with the LTL checker.
+
+    In Python, you can create this class from a model with the
+    CTLStarChecker(model) constructor.
+    """
+    def __init__(self, model: Model) -> None: ...
+    def check(self, formula: CTLStarFormula) -> Set[str]:
+        """Returns the names of the set of states in which the provided formula holds.
+        A formula that is not a state formula, like "GF p", is checked as if it was written "A(GF p)".
+        Like in the LTL checker, path formulas are about infinite paths only, so in states
+        from which every path ends in a sink, "A ψ" vacuously holds and "E ψ" does not.
+        """
+    def holds(self, formula: CTLStarFormula) -> bool:
+        """Returns whether the provided formula holds in all initial states of the model."""
+    def get_model(self) -> Model:
+        """Returns the model with which the checker was created"""
+
+class MuChecker:
+    """
+    The Python view into the modal mu-calculus Checker
+    Though this class is not frozen, you cannot modify it directly.
+    The object will update itself on calls of `check` by updating the cache.
+
+    Fixpoints are computed from the empty set for "mu" and all states for "nu", by only following
+    the states that change, so formulas in which no fixpoint uses the variable of an enclosing one
+    (like all translations of CTL) take time linear in the model. When a fixpoint variable is used in a nested fixpoint, the outer one is iterated
+    instead, and like Emerson and Lei, fixpoints that are needed again are restarted from their previous
+    value where that is safe, so nested fixpoints of the same kind are not recomputed from scratch.
+
+    In Python, you can create this class from a model with the
+    MuChecker(model) constructor.
+    """
+    def __init__(self, model: Model) -> None: ...
+    def check(self, formula: MuFormula) -> Set[str]:
+        """Returns the names of the set of states in which the provided formula holds.
+        Raises a ValueError if the formula contains fixpoint variables that are not bound,
+        or that occur under an odd number of negations, as then the fixpoints might not exist.
+        """
+    def holds(self, formula: MuFormula) -> bool:
+        """Returns whether the provided formula holds in all initial states of the model."""
+    def get_model(self) -> Model:
+        """Returns the model with which the checker was created"""
+
+class HMLChecker:
+    """
+    The Python view into the Hennessy–Milner logic Checker
+    Though this class is not frozen, you cannot modify it directly.
+    The object will update itself on calls of `check` by updating the cache.
+
+    The modalities only look at transitions labelled with their action,
+    so unlabelled transitions are never taken, and "[a]" vacuously holds in states without a-transitions.
+
+    In Python, you can create this class from a model with the
+    HMLChecker(model) constructor.
+    """
+    def __init__(self, model: Model) -> None: ...
+    def check(self, formula: HMLFormula) -> Set[str]:
+        """Returns the names of the set of states in which the provided formula holds."""
+    def holds(self, formula: HMLFormula) -> bool:
+        """Returns whether the provided formula holds in all initial states of the model."""
+    def get_model(self) -> Model:
+        """Returns the model with which the checker was created"""
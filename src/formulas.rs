@@ -6,24 +6,70 @@
 //
 // This allows CTL and LTL to live completely seperate lives, which makes sense,
 // as they are completely distinct logics.
+// CTL* gets the same treatment, even though it contains both,
+// and so do the mu-calculus and Hennessy–Milner logic.
 use std::hash::Hash;
 
+pub mod ctl_builders;
+mod ctl_normal_forms;
 mod ctl_parse;
+mod ctl_print;
+mod ctl_simplify;
 mod ctl_types;
 pub use ctl_parse::{parse_ctl, CTLParseError};
+pub use ctl_simplify::CTLSimplification;
 pub(crate) use ctl_types::ctl_formula_macros;
 pub use ctl_types::{CTLFormula, CTLVariable};
 
+pub mod ctlstar_builders;
+mod ctlstar_parse;
+mod ctlstar_types;
+pub use ctlstar_parse::{parse_ctlstar, CTLStarParseError};
+pub(crate) use ctlstar_types::ctlstar_formula_macros;
+pub use ctlstar_types::{CTLStarFormula, CTLStarVariable};
+
+pub mod mu_builders;
+mod mu_from_ctl;
+mod mu_parse;
+mod mu_types;
+pub use mu_parse::{parse_mu, MuParseError};
+pub(crate) use mu_types::mu_formula_macros;
+pub use mu_types::{MuFormula, MuVariable};
+
+pub mod hml_builders;
+mod hml_parse;
+mod hml_types;
+pub use hml_parse::{parse_hml, HMLParseError};
+pub(crate) use hml_types::hml_formula_macros;
+pub use hml_types::{HMLFormula, HMLVariable};
+
+pub mod ltl_builders;
+mod ltl_normal_forms;
 mod ltl_parse;
+mod ltl_print;
+mod ltl_simplify;
 mod ltl_types;
 pub use ltl_parse::{parse_ltl, LTLParseError};
+pub use ltl_simplify::LTLSimplification;
 pub(crate) use ltl_types::ltl_formula_macros;
 pub use ltl_types::{LTLFormula, LTLVariable};
 
 pub trait MLVariable: Eq + Hash {}
 
+mod print_style;
+pub use print_style::{PrintStyle, UnknownPrintStyle};
+
 #[cfg(feature = "python")]
 pub mod ctl_python;
 
 #[cfg(feature = "python")]
 pub mod ltl_python;
+
+#[cfg(feature = "python")]
+pub mod ctlstar_python;
+
+#[cfg(feature = "python")]
+pub mod mu_python;
+
+#[cfg(feature = "python")]
+pub mod hml_python;
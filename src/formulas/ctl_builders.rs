@@ -0,0 +1,85 @@
+//! Public functions to build CTL formulas in code, without going through the parser.
+//! Every function returns a boxed formula, the same as what the parser returns.
+//!
+//! ```
+//! use minictl::formulas::ctl_builders::*;
+//! // AG(request -> AF response)
+//! let formula = ag(implies_r(atom("request"), af(atom("response"))));
+//! ```
+use super::{CTLFormula, CTLVariable};
+
+pub fn top() -> Box<CTLFormula> {
+    Box::new(CTLFormula::Top)
+}
+
+pub fn bot() -> Box<CTLFormula> {
+    Box::new(CTLFormula::Bot)
+}
+
+pub fn atom(name: impl Into<String>) -> Box<CTLFormula> {
+    Box::new(CTLFormula::Atomic(CTLVariable::new(name.into())))
+}
+
+pub fn neg(inner: Box<CTLFormula>) -> Box<CTLFormula> {
+    Box::new(CTLFormula::Neg(inner))
+}
+
+pub fn and(lhs: Box<CTLFormula>, rhs: Box<CTLFormula>) -> Box<CTLFormula> {
+    Box::new(CTLFormula::And(lhs, rhs))
+}
+
+pub fn or(lhs: Box<CTLFormula>, rhs: Box<CTLFormula>) -> Box<CTLFormula> {
+    Box::new(CTLFormula::Or(lhs, rhs))
+}
+
+pub fn implies_r(lhs: Box<CTLFormula>, rhs: Box<CTLFormula>) -> Box<CTLFormula> {
+    Box::new(CTLFormula::ImpliesR(lhs, rhs))
+}
+
+pub fn implies_l(lhs: Box<CTLFormula>, rhs: Box<CTLFormula>) -> Box<CTLFormula> {
+    Box::new(CTLFormula::ImpliesL(lhs, rhs))
+}
+
+pub fn implies_bi(lhs: Box<CTLFormula>, rhs: Box<CTLFormula>) -> Box<CTLFormula> {
+    Box::new(CTLFormula::BiImplies(lhs, rhs))
+}
+
+pub fn eu(lhs: Box<CTLFormula>, rhs: Box<CTLFormula>) -> Box<CTLFormula> {
+    Box::new(CTLFormula::EU(lhs, rhs))
+}
+
+pub fn au(lhs: Box<CTLFormula>, rhs: Box<CTLFormula>) -> Box<CTLFormula> {
+    Box::new(CTLFormula::AU(lhs, rhs))
+}
+
+pub fn er(lhs: Box<CTLFormula>, rhs: Box<CTLFormula>) -> Box<CTLFormula> {
+    Box::new(CTLFormula::ER(lhs, rhs))
+}
+
+pub fn ar(lhs: Box<CTLFormula>, rhs: Box<CTLFormula>) -> Box<CTLFormula> {
+    Box::new(CTLFormula::AR(lhs, rhs))
+}
+
+pub fn ex(inner: Box<CTLFormula>) -> Box<CTLFormula> {
+    Box::new(CTLFormula::EX(inner))
+}
+
+pub fn ax(inner: Box<CTLFormula>) -> Box<CTLFormula> {
+    Box::new(CTLFormula::AX(inner))
+}
+
+pub fn ef(inner: Box<CTLFormula>) -> Box<CTLFormula> {
+    Box::new(CTLFormula::EF(inner))
+}
+
+pub fn af(inner: Box<CTLFormula>) -> Box<CTLFormula> {
+    Box::new(CTLFormula::AF(inner))
+}
+
+pub fn eg(inner: Box<CTLFormula>) -> Box<CTLFormula> {
+    Box::new(CTLFormula::EG(inner))
+}
+
+pub fn ag(inner: Box<CTLFormula>) -> Box<CTLFormula> {
+    Box::new(CTLFormula::AG(inner))
+}
@@ -0,0 +1,138 @@
+// Normal forms of CTL formulas.
+//
+// The existential normal form only uses ⊤, variables, ¬, ∧, EX, EU, and EG.
+// These are the operators the CTL checker computes directly,
+// and the rewrites are the same ones it applies on the fly:
+//     AX φ     = ¬EX¬φ
+//     EF φ     = E[⊤ U φ]
+//     AF φ     = ¬EG¬φ
+//     AG φ     = ¬E[⊤ U ¬φ]
+//     A[φ U ψ] = ¬E[¬ψ U (¬φ ∧ ¬ψ)] ∧ ¬EG¬ψ
+//     E[φ R ψ] = E[ψ U (φ ∧ ψ)] ∨ EGψ
+//     A[φ R ψ] = ¬E[¬φ U ¬ψ]
+//
+// The negation normal form only has negations directly in front of variables.
+// It pushes negations inwards through the dual of every operator:
+//     ¬EX φ     = AX ¬φ          ¬AX φ     = EX ¬φ
+//     ¬EF φ     = AG ¬φ          ¬AG φ     = EF ¬φ
+//     ¬AF φ     = EG ¬φ          ¬EG φ     = AF ¬φ
+//     ¬E[φ U ψ] = A[¬φ R ¬ψ]     ¬A[φ R ψ] = E[¬φ U ¬ψ]
+//     ¬A[φ U ψ] = E[¬φ R ¬ψ]     ¬E[φ R ψ] = A[¬φ U ¬ψ]
+//
+// These dualities also hold on models with sinks, the way the CTL checker treats them.
+use super::ctl_formula_macros as f;
+use super::{CTLFormula, CTLVariable};
+
+impl CTLFormula {
+    /// The existential normal form of this formula,
+    /// which only uses ⊤, variables, ¬, ∧, EX, EU, and EG.
+    /// Double negations introduced by the rewrites are removed.
+    pub fn to_enf(&self) -> Box<CTLFormula> {
+        enf(self)
+    }
+    /// The negation normal form of this formula, in which negations only occur
+    /// directly in front of variables, and no implications are left.
+    /// This might introduce the release operators ER and AR.
+    pub fn to_nnf(&self) -> Box<CTLFormula> {
+        nnf(self, false)
+    }
+}
+
+fn not(formula: Box<CTLFormula>) -> Box<CTLFormula> {
+    if let CTLFormula::Neg(inner) = *formula {
+        inner
+    } else {
+        f::neg!(formula)
+    }
+}
+
+fn or(lhs: Box<CTLFormula>, rhs: Box<CTLFormula>) -> Box<CTLFormula> {
+    not(f::and!(not(lhs), not(rhs)))
+}
+
+fn implies(lhs: Box<CTLFormula>, rhs: Box<CTLFormula>) -> Box<CTLFormula> {
+    not(f::and!(lhs, not(rhs)))
+}
+
+fn enf(formula: &CTLFormula) -> Box<CTLFormula> {
+    use CTLFormula as F;
+    match formula {
+        F::Top => f::top!(),
+        F::Bot => not(f::top!()),
+        F::Atomic(var) => f::atom!(var.inner.clone()),
+        F::Neg(inner) => not(enf(inner)),
+        F::And(lhs, rhs) => f::and!(enf(lhs), enf(rhs)),
+        F::Or(lhs, rhs) => or(enf(lhs), enf(rhs)),
+        F::ImpliesR(lhs, rhs) => implies(enf(lhs), enf(rhs)),
+        F::ImpliesL(lhs, rhs) => implies(enf(rhs), enf(lhs)),
+        F::BiImplies(lhs, rhs) => {
+            let (lhs, rhs) = (enf(lhs), enf(rhs));
+            f::and!(implies(lhs.clone(), rhs.clone()), implies(rhs, lhs))
+        }
+        F::EX(inner) => f::ex!(enf(inner)),
+        F::AX(inner) => not(f::ex!(not(enf(inner)))),
+        F::EF(inner) => f::eu!(f::top!(), enf(inner)),
+        F::AF(inner) => not(f::eg!(not(enf(inner)))),
+        F::EG(inner) => f::eg!(enf(inner)),
+        F::AG(inner) => not(f::eu!(f::top!(), not(enf(inner)))),
+        F::EU(lhs, rhs) => f::eu!(enf(lhs), enf(rhs)),
+        F::AU(lhs, rhs) => {
+            let (lhs, rhs) = (enf(lhs), enf(rhs));
+            let neither = f::and!(not(lhs), not(rhs.clone()));
+            f::and!(
+                not(f::eu!(not(rhs.clone()), neither)),
+                not(f::eg!(not(rhs)))
+            )
+        }
+        F::ER(lhs, rhs) => {
+            let (lhs, rhs) = (enf(lhs), enf(rhs));
+            or(f::eu!(rhs.clone(), f::and!(lhs, rhs.clone())), f::eg!(rhs))
+        }
+        F::AR(lhs, rhs) => not(f::eu!(not(enf(lhs)), not(enf(rhs)))),
+    }
+}
+
+fn nnf(formula: &CTLFormula, negated: bool) -> Box<CTLFormula> {
+    use CTLFormula as F;
+    match (formula, negated) {
+        (F::Top, false) | (F::Bot, true) => f::top!(),
+        (F::Top, true) | (F::Bot, false) => f::bot!(),
+        (F::Atomic(var), false) => f::atom!(var.inner.clone()),
+        (F::Atomic(var), true) => f::neg!(f::atom!(var.inner.clone())),
+        (F::Neg(inner), _) => nnf(inner, !negated),
+        (F::And(lhs, rhs), false) => f::and!(nnf(lhs, false), nnf(rhs, false)),
+        (F::And(lhs, rhs), true) => f::or!(nnf(lhs, true), nnf(rhs, true)),
+        (F::Or(lhs, rhs), false) => f::or!(nnf(lhs, false), nnf(rhs, false)),
+        (F::Or(lhs, rhs), true) => f::and!(nnf(lhs, true), nnf(rhs, true)),
+        (F::ImpliesR(lhs, rhs), false) => f::or!(nnf(lhs, true), nnf(rhs, false)),
+        (F::ImpliesR(lhs, rhs), true) => f::and!(nnf(lhs, false), nnf(rhs, true)),
+        (F::ImpliesL(lhs, rhs), false) => f::or!(nnf(lhs, false), nnf(rhs, true)),
+        (F::ImpliesL(lhs, rhs), true) => f::and!(nnf(lhs, true), nnf(rhs, false)),
+        (F::BiImplies(lhs, rhs), false) => f::and!(
+            f::or!(nnf(lhs, true), nnf(rhs, false)),
+            f::or!(nnf(rhs, true), nnf(lhs, false))
+        ),
+        (F::BiImplies(lhs, rhs), true) => f::or!(
+            f::and!(nnf(lhs, false), nnf(rhs, true)),
+            f::and!(nnf(rhs, false), nnf(lhs, true))
+        ),
+        (F::EX(inner), false) | (F::AX(inner), true) => f::ex!(nnf(inner, negated)),
+        (F::AX(inner), false) | (F::EX(inner), true) => f::ax!(nnf(inner, negated)),
+        (F::EF(inner), false) | (F::AG(inner), true) => f::ef!(nnf(inner, negated)),
+        (F::AG(inner), false) | (F::EF(inner), true) => f::ag!(nnf(inner, negated)),
+        (F::AF(inner), false) | (F::EG(inner), true) => f::af!(nnf(inner, negated)),
+        (F::EG(inner), false) | (F::AF(inner), true) => f::eg!(nnf(inner, negated)),
+        (F::EU(lhs, rhs), false) | (F::AR(lhs, rhs), true) => {
+            f::eu!(nnf(lhs, negated), nnf(rhs, negated))
+        }
+        (F::AR(lhs, rhs), false) | (F::EU(lhs, rhs), true) => {
+            f::ar!(nnf(lhs, negated), nnf(rhs, negated))
+        }
+        (F::AU(lhs, rhs), false) | (F::ER(lhs, rhs), true) => {
+            f::au!(nnf(lhs, negated), nnf(rhs, negated))
+        }
+        (F::ER(lhs, rhs), false) | (F::AU(lhs, rhs), true) => {
+            f::er!(nnf(lhs, negated), nnf(rhs, negated))
+        }
+    }
+}
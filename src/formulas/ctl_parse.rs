@@ -25,6 +25,7 @@ enum CTLToken {
     E,
     A,
     U,
+    R,
     LParen,
     RParen,
     LSquare,
@@ -121,7 +122,7 @@ impl<'a> CTLLexer<'a> {
     fn consume_lowercase_variable_or_keyword(&mut self) -> Option<Result<CTLToken, CTLParseError>> {
         let mut name = String::new();
         while let Some(&ch) = self.chars.peek() {
-            if ch.is_lowercase() || ch.is_numeric() || matches!(ch, '=' | '_') {
+            if ch.is_lowercase() || ch.is_numeric() || matches!(ch, '=' | '_' | '.') {
                 name.push(ch);
                 self.chars.next();
             } else {
@@ -158,6 +159,7 @@ impl<'a> CTLLexer<'a> {
             "E" => Some(Ok(T::E)),
             "A" => Some(Ok(T::A)),
             "U" => Some(Ok(T::U)),
+            "R" => Some(Ok(T::R)),
             _ => Some(Err(CTLParseError::UnknownKeyword(name))),
         }
     }
@@ -192,6 +194,19 @@ impl<'a> CTLParser<'a> {
             }
         }
     }
+    /// Whether the E[ or A[ construction is an until (true) or a release (false)
+    fn expect_until_or_release(&mut self, quantifier: &str) -> Result<bool, CTLParseError> {
+        match self.tokens.next() {
+            Some(Ok(CTLToken::U)) => Ok(true),
+            Some(Ok(CTLToken::R)) => Ok(false),
+            Some(other) => Err(CTLParseError::UnexpectedToken(format!(
+                "Expected U or R after {quantifier}[ for {quantifier}[pUq] construction, got {other:?}"
+            ))),
+            None => Err(CTLParseError::UnexpectedEndOfInput(format!(
+                "Expected U or R after {quantifier}[ for {quantifier}[pUq] construction, got end of input"
+            ))),
+        }
+    }
     fn parse(&mut self) -> Result<Box<CTLFormula>, CTLParseError> {
         self.parse_expression(1)
     }
@@ -248,18 +263,26 @@ impl<'a> CTLParser<'a> {
             Some(Ok(T::E)) => {
                 self.expect_token(T::LSquare, "Expected [ after E for E[pUq] construction")?;
                 let left = self.parse_primary()?;
-                self.expect_token(T::U, "Expected U after E[ for E[pUq] construction")?;
+                let until = self.expect_until_or_release("E")?;
                 let right = self.parse_primary()?;
                 self.expect_token(T::RSquare, "Expected ] after E[.U. for E[pUq] construction")?;
-                Ok(f::eu!(left, right))
+                Ok(if until {
+                    f::eu!(left, right)
+                } else {
+                    f::er!(left, right)
+                })
             }
             Some(Ok(T::A)) => {
                 self.expect_token(T::LSquare, "Expected [ after A for A[pUq] construction")?;
                 let left = self.parse_primary()?;
-                self.expect_token(T::U, "Expected U after A[ for A[pUq] construction")?;
+                let until = self.expect_until_or_release("A")?;
                 let right = self.parse_primary()?;
                 self.expect_token(T::RSquare, "Expected ] after A[.U. for A[pUq] construction")?;
-                Ok(f::au!(left, right))
+                Ok(if until {
+                    f::au!(left, right)
+                } else {
+                    f::ar!(left, right)
+                })
             }
             Some(Ok(other)) => Err(CTLParseError::UnexpectedToken(format!("{:?}", other))),
             Some(Err(error)) => Err(error),
@@ -0,0 +1,126 @@
+// Printing CTL formulas with as few parentheses as the parser allows.
+//
+// A binary operator only needs parentheses around an argument that binds looser than itself,
+// or, on the right, around one that binds equally loose, as the parser associates to the left.
+// Everything else takes its arguments with parse_primary, so those need parentheses
+// around every argument that is not a primary formula itself.
+use std::fmt;
+
+use super::{CTLFormula, PrintStyle};
+
+const PRIMARY: u8 = 4;
+
+fn precedence(formula: &CTLFormula) -> u8 {
+    use CTLFormula as F;
+    match formula {
+        F::ImpliesR(..) | F::ImpliesL(..) | F::BiImplies(..) => 1,
+        F::Or(..) => 2,
+        F::And(..) => 3,
+        _ => PRIMARY,
+    }
+}
+
+impl CTLFormula {
+    /// Print this formula in the given style.
+    /// With `PrintStyle::Ascii`, `parse_ctl` parses the output back into this same formula,
+    /// as long as all variables are valid variable names for the parser.
+    pub fn print(&self, style: PrintStyle) -> String {
+        let mut out = String::new();
+        // Writing to a String never fails
+        let _ = write_formula(&mut out, self, style);
+        out
+    }
+}
+
+impl fmt::Display for CTLFormula {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_formula(f, self, PrintStyle::Unicode)
+    }
+}
+
+fn write_formula(
+    out: &mut impl fmt::Write,
+    formula: &CTLFormula,
+    style: PrintStyle,
+) -> fmt::Result {
+    use CTLFormula as F;
+    let own = precedence(formula);
+    match formula {
+        F::Top => write!(out, "{}", style.top()),
+        F::Bot => write!(out, "{}", style.bot()),
+        F::Atomic(var) => write!(out, "{}", style.variable(&var.inner)),
+        F::Neg(inner) => {
+            write!(out, "{}", style.not())?;
+            write_argument(out, inner, PRIMARY, style)
+        }
+        F::And(lhs, rhs) => write_binary(out, own, lhs, style.and(), rhs, style),
+        F::Or(lhs, rhs) => write_binary(out, own, lhs, style.or(), rhs, style),
+        F::ImpliesR(lhs, rhs) => write_binary(out, own, lhs, style.implies_r(), rhs, style),
+        F::ImpliesL(lhs, rhs) => write_binary(out, own, lhs, style.implies_l(), rhs, style),
+        F::BiImplies(lhs, rhs) => write_binary(out, own, lhs, style.bi_implies(), rhs, style),
+        F::EX(inner) => write_prefix(out, "EX", inner, style),
+        F::EF(inner) => write_prefix(out, "EF", inner, style),
+        F::EG(inner) => write_prefix(out, "EG", inner, style),
+        F::AX(inner) => write_prefix(out, "AX", inner, style),
+        F::AF(inner) => write_prefix(out, "AF", inner, style),
+        F::AG(inner) => write_prefix(out, "AG", inner, style),
+        F::EU(lhs, rhs) => write_path(out, "E", lhs, "U", rhs, style),
+        F::AU(lhs, rhs) => write_path(out, "A", lhs, "U", rhs, style),
+        F::ER(lhs, rhs) => write_path(out, "E", lhs, "R", rhs, style),
+        F::AR(lhs, rhs) => write_path(out, "A", lhs, "R", rhs, style),
+    }
+}
+
+/// Write an argument, in parentheses if it binds looser than `min_precedence`.
+fn write_argument(
+    out: &mut impl fmt::Write,
+    formula: &CTLFormula,
+    min_precedence: u8,
+    style: PrintStyle,
+) -> fmt::Result {
+    if precedence(formula) < min_precedence {
+        write!(out, "(")?;
+        write_formula(out, formula, style)?;
+        write!(out, ")")
+    } else {
+        write_formula(out, formula, style)
+    }
+}
+
+fn write_binary(
+    out: &mut impl fmt::Write,
+    own_precedence: u8,
+    lhs: &CTLFormula,
+    operator: &str,
+    rhs: &CTLFormula,
+    style: PrintStyle,
+) -> fmt::Result {
+    write_argument(out, lhs, own_precedence, style)?;
+    write!(out, "{operator}")?;
+    write_argument(out, rhs, own_precedence + 1, style)
+}
+
+fn write_prefix(
+    out: &mut impl fmt::Write,
+    keyword: &str,
+    inner: &CTLFormula,
+    style: PrintStyle,
+) -> fmt::Result {
+    write!(out, "{}", style.prefix(keyword))?;
+    write_argument(out, inner, PRIMARY, style)
+}
+
+fn write_path(
+    out: &mut impl fmt::Write,
+    quantifier: &str,
+    lhs: &CTLFormula,
+    keyword: &str,
+    rhs: &CTLFormula,
+    style: PrintStyle,
+) -> fmt::Result {
+    write!(out, "{}[", style.quantifier(quantifier))?;
+    write_argument(out, lhs, PRIMARY, style)?;
+    write!(out, "{}", style.infix(keyword))?;
+    write_argument(out, rhs, PRIMARY, style)?;
+    write!(out, "]")
+}
@@ -8,9 +8,10 @@ use pyo3::prelude::*;
 use pyo3::types::PyTuple;
 
 use std::fmt;
+use std::str::FromStr;
 
 use super::ctl_formula_macros as f;
-use super::{parse_ctl, CTLFormula, CTLVariable};
+use super::{parse_ctl, CTLFormula, CTLVariable, PrintStyle};
 
 /// The python view into the CTLFormula.
 /// This class is frozen. Objects, once created, cannot be modified.
@@ -88,6 +89,8 @@ impl PyCTLFormula {
             }
             F::EU(f1, f2) => Self::new_bare("EU", vec![PF::from_rust(f1), PF::from_rust(f2)]),
             F::AU(f1, f2) => Self::new_bare("AU", vec![PF::from_rust(f1), PF::from_rust(f2)]),
+            F::ER(f1, f2) => Self::new_bare("ER", vec![PF::from_rust(f1), PF::from_rust(f2)]),
+            F::AR(f1, f2) => Self::new_bare("AR", vec![PF::from_rust(f1), PF::from_rust(f2)]),
             F::Atomic(variable) => Self {
                 name: variable.inner.clone(),
                 arguments: Vec::default(),
@@ -117,6 +120,8 @@ impl PyCTLFormula {
             "BiImplies" => f::implies_bi!(self.arg_to_rust(0)?, self.arg_to_rust(1)?),
             "EU" => f::eu!(self.arg_to_rust(0)?, self.arg_to_rust(1)?),
             "AU" => f::au!(self.arg_to_rust(0)?, self.arg_to_rust(1)?),
+            "ER" => f::er!(self.arg_to_rust(0)?, self.arg_to_rust(1)?),
+            "AR" => f::ar!(self.arg_to_rust(0)?, self.arg_to_rust(1)?),
             other => f::atom!(other.to_string()),
         };
         Some(ret)
@@ -151,6 +156,8 @@ impl fmt::Display for PyCTLFormula {
             "BiImplies" => write!(f, "({})↔({})", get_argstr(0), get_argstr(1)),
             "EU" => write!(f, "E[({})U({})]", get_argstr(0), get_argstr(1)),
             "AU" => write!(f, "A[({})U({})]", get_argstr(0), get_argstr(1)),
+            "ER" => write!(f, "E[({})R({})]", get_argstr(0), get_argstr(1)),
+            "AR" => write!(f, "A[({})R({})]", get_argstr(0), get_argstr(1)),
             _ => write!(f, "{}", self.name),
         }
     }
@@ -166,7 +173,7 @@ impl PyCTLFormula {
             "Neg" | "EX" | "AX" | "EF" | "AF" | "EG" | "AG" => {
                 Self::new_with_pyargs(name, py_args, 1)
             }
-            "And" | "Or" | "ImpliesR" | "ImpliesL" | "BiImplies" | "EU" | "AU" => {
+            "And" | "Or" | "ImpliesR" | "ImpliesL" | "BiImplies" | "EU" | "AU" | "ER" | "AR" => {
                 Self::new_with_pyargs(name, py_args, 2)
             }
             _ if py_args.is_empty() => {
@@ -193,4 +200,33 @@ impl PyCTLFormula {
         })?;
         Ok(Self::from_rust(&res))
     }
+    fn to_enf(&self) -> PyResult<Self> {
+        let rsformula = self
+            .to_rust()
+            .ok_or(PyValueError::new_err("formula is not a valid CTL formula"))?;
+        Ok(Self::from_rust(&rsformula.to_enf()))
+    }
+    fn to_nnf(&self) -> PyResult<Self> {
+        let rsformula = self
+            .to_rust()
+            .ok_or(PyValueError::new_err("formula is not a valid CTL formula"))?;
+        Ok(Self::from_rust(&rsformula.to_nnf()))
+    }
+    fn simplify(&self) -> PyResult<(Self, Vec<String>)> {
+        let rsformula = self
+            .to_rust()
+            .ok_or(PyValueError::new_err("formula is not a valid CTL formula"))?;
+        let (simplified, applied) = rsformula.simplify();
+        let applied = applied.iter().map(|rule| rule.to_string()).collect();
+        Ok((Self::from_rust(&simplified), applied))
+    }
+    #[pyo3(signature = (style = "unicode"))]
+    fn pretty(&self, style: &str) -> PyResult<String> {
+        let style =
+            PrintStyle::from_str(style).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let rsformula = self
+            .to_rust()
+            .ok_or(PyValueError::new_err("formula is not a valid CTL formula"))?;
+        Ok(rsformula.print(style))
+    }
 }
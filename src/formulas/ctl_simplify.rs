@@ -0,0 +1,161 @@
+// Simplification of CTL formulas.
+//
+// Formulas are simplified bottom-up: first the children, then the formula itself,
+// for as long as one of the rules applies to it. Only rules that make a formula smaller are used,
+// so this always terminates.
+//
+// All rules also hold on models with sinks, and under fairness constraints,
+// so a simplified formula gives the same results in every checker.
+use std::fmt;
+
+use super::ctl_formula_macros as f;
+use super::CTLFormula;
+
+/// A rule that was used to simplify a CTL formula.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CTLSimplification {
+    /// ¬¬φ ≡ φ
+    DoubleNegation,
+    /// Removing ⊤ and ⊥ from connectives, like φ ∧ ⊤ ≡ φ, φ ∨ ⊤ ≡ ⊤, and ⊥ → φ ≡ ⊤
+    Absorption,
+    /// φ ∧ φ ≡ φ and φ ∨ φ ≡ φ
+    Idempotence,
+    /// AG AG φ ≡ AG φ
+    NestedAG,
+    /// EF EF φ ≡ EF φ
+    NestedEF,
+    /// AF AF φ ≡ AF φ
+    NestedAF,
+    /// EG EG φ ≡ EG φ
+    NestedEG,
+    /// AG φ ∧ AG ψ ≡ AG (φ ∧ ψ)
+    MergeAG,
+    /// EF φ ∨ EF ψ ≡ EF (φ ∨ ψ)
+    MergeEF,
+}
+
+impl fmt::Display for CTLSimplification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use CTLSimplification as S;
+        let rule = match self {
+            S::DoubleNegation => "¬¬φ ≡ φ",
+            S::Absorption => "⊤/⊥ absorption",
+            S::Idempotence => "idempotence",
+            S::NestedAG => "AG AG φ ≡ AG φ",
+            S::NestedEF => "EF EF φ ≡ EF φ",
+            S::NestedAF => "AF AF φ ≡ AF φ",
+            S::NestedEG => "EG EG φ ≡ EG φ",
+            S::MergeAG => "AG φ ∧ AG ψ ≡ AG (φ ∧ ψ)",
+            S::MergeEF => "EF φ ∨ EF ψ ≡ EF (φ ∨ ψ)",
+        };
+        write!(f, "{rule}")
+    }
+}
+
+impl CTLFormula {
+    /// Simplify this formula with propositional laws and temporal identities.
+    /// Returns the simplified formula, and the rules that were applied, in order.
+    /// A rule is listed once for every time it was applied.
+    pub fn simplify(&self) -> (Box<CTLFormula>, Vec<CTLSimplification>) {
+        let mut applied = Vec::new();
+        let simplified = simplify(self, &mut applied);
+        (simplified, applied)
+    }
+}
+
+fn simplify(formula: &CTLFormula, applied: &mut Vec<CTLSimplification>) -> Box<CTLFormula> {
+    let formula = map_children(formula, |child| simplify(child, applied));
+    match rewrite(&formula) {
+        Some((rewritten, rule)) => {
+            applied.push(rule);
+            simplify(&rewritten, applied)
+        }
+        None => formula,
+    }
+}
+
+fn map_children(
+    formula: &CTLFormula,
+    mut f: impl FnMut(&CTLFormula) -> Box<CTLFormula>,
+) -> Box<CTLFormula> {
+    use CTLFormula as F;
+    Box::new(match formula {
+        F::Top => F::Top,
+        F::Bot => F::Bot,
+        F::Atomic(var) => F::Atomic(var.clone()),
+        F::Neg(inner) => F::Neg(f(inner)),
+        F::And(lhs, rhs) => F::And(f(lhs), f(rhs)),
+        F::Or(lhs, rhs) => F::Or(f(lhs), f(rhs)),
+        F::ImpliesR(lhs, rhs) => F::ImpliesR(f(lhs), f(rhs)),
+        F::ImpliesL(lhs, rhs) => F::ImpliesL(f(lhs), f(rhs)),
+        F::BiImplies(lhs, rhs) => F::BiImplies(f(lhs), f(rhs)),
+        F::EX(inner) => F::EX(f(inner)),
+        F::EF(inner) => F::EF(f(inner)),
+        F::EG(inner) => F::EG(f(inner)),
+        F::EU(lhs, rhs) => F::EU(f(lhs), f(rhs)),
+        F::AX(inner) => F::AX(f(inner)),
+        F::AF(inner) => F::AF(f(inner)),
+        F::AG(inner) => F::AG(f(inner)),
+        F::AU(lhs, rhs) => F::AU(f(lhs), f(rhs)),
+        F::ER(lhs, rhs) => F::ER(f(lhs), f(rhs)),
+        F::AR(lhs, rhs) => F::AR(f(lhs), f(rhs)),
+    })
+}
+
+/// A single rewrite at the top of the formula, if any rule applies to it.
+fn rewrite(formula: &CTLFormula) -> Option<(Box<CTLFormula>, CTLSimplification)> {
+    use CTLFormula as F;
+    use CTLSimplification as S;
+    let rewritten = match formula {
+        F::Neg(inner) => match inner.as_ref() {
+            F::Neg(inner) => (inner.clone(), S::DoubleNegation),
+            F::Top => (f::bot!(), S::Absorption),
+            F::Bot => (f::top!(), S::Absorption),
+            _ => return None,
+        },
+        F::And(lhs, rhs) => match (lhs.as_ref(), rhs.as_ref()) {
+            (F::Bot, _) | (_, F::Bot) => (f::bot!(), S::Absorption),
+            (F::Top, _) => (rhs.clone(), S::Absorption),
+            (_, F::Top) => (lhs.clone(), S::Absorption),
+            _ if lhs == rhs => (lhs.clone(), S::Idempotence),
+            (F::AG(lhs), F::AG(rhs)) => (f::ag!(f::and!(lhs.clone(), rhs.clone())), S::MergeAG),
+            _ => return None,
+        },
+        F::Or(lhs, rhs) => match (lhs.as_ref(), rhs.as_ref()) {
+            (F::Top, _) | (_, F::Top) => (f::top!(), S::Absorption),
+            (F::Bot, _) => (rhs.clone(), S::Absorption),
+            (_, F::Bot) => (lhs.clone(), S::Absorption),
+            _ if lhs == rhs => (lhs.clone(), S::Idempotence),
+            (F::EF(lhs), F::EF(rhs)) => (f::ef!(f::or!(lhs.clone(), rhs.clone())), S::MergeEF),
+            _ => return None,
+        },
+        F::ImpliesR(lhs, rhs) => implication(lhs, rhs)?,
+        F::ImpliesL(lhs, rhs) => implication(rhs, lhs)?,
+        F::BiImplies(lhs, rhs) => match (lhs.as_ref(), rhs.as_ref()) {
+            (F::Top, _) => (rhs.clone(), S::Absorption),
+            (_, F::Top) => (lhs.clone(), S::Absorption),
+            (F::Bot, _) => (f::neg!(rhs.clone()), S::Absorption),
+            (_, F::Bot) => (f::neg!(lhs.clone()), S::Absorption),
+            _ => return None,
+        },
+        F::AG(inner) if matches!(inner.as_ref(), F::AG(_)) => (inner.clone(), S::NestedAG),
+        F::EF(inner) if matches!(inner.as_ref(), F::EF(_)) => (inner.clone(), S::NestedEF),
+        F::AF(inner) if matches!(inner.as_ref(), F::AF(_)) => (inner.clone(), S::NestedAF),
+        F::EG(inner) if matches!(inner.as_ref(), F::EG(_)) => (inner.clone(), S::NestedEG),
+        _ => return None,
+    };
+    Some(rewritten)
+}
+
+fn implication(lhs: &CTLFormula, rhs: &CTLFormula) -> Option<(Box<CTLFormula>, CTLSimplification)> {
+    use CTLFormula as F;
+    match (lhs, rhs) {
+        (F::Bot, _) | (_, F::Top) => Some((f::top!(), CTLSimplification::Absorption)),
+        (F::Top, _) => Some((Box::new(rhs.clone()), CTLSimplification::Absorption)),
+        (_, F::Bot) => Some((
+            f::neg!(Box::new(lhs.clone())),
+            CTLSimplification::Absorption,
+        )),
+        _ => None,
+    }
+}
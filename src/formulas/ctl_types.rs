@@ -3,17 +3,20 @@ use std::collections::HashSet;
 use super::MLVariable;
 
 #[derive(Debug, Hash, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct CTLVariable {
     pub inner: String,
 }
 impl CTLVariable {
-    pub(crate) fn new(inner: String) -> Self {
+    pub fn new(inner: String) -> Self {
         Self { inner }
     }
 }
 impl MLVariable for CTLVariable {}
 
 #[derive(Debug, Hash, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CTLFormula {
     Top,
     Bot,
@@ -32,6 +35,10 @@ pub enum CTLFormula {
     AF(Box<CTLFormula>),
     AG(Box<CTLFormula>),
     AU(Box<CTLFormula>, Box<CTLFormula>),
+    // The duals of EU and AU, which negation normal form needs:
+    // E[φRψ] is ¬A[¬φU¬ψ], and A[φRψ] is ¬E[¬φU¬ψ].
+    ER(Box<CTLFormula>, Box<CTLFormula>),
+    AR(Box<CTLFormula>, Box<CTLFormula>),
 }
 
 impl CTLFormula {
@@ -45,7 +52,9 @@ impl CTLFormula {
             | F::ImpliesL(l, r)
             | F::BiImplies(l, r)
             | F::EU(l, r)
-            | F::AU(l, r) => {
+            | F::AU(l, r)
+            | F::ER(l, r)
+            | F::AR(l, r) => {
                 f(l);
                 f(r);
             }
@@ -195,4 +204,18 @@ pub(crate) mod ctl_formula_macros {
         };
     }
     pub(crate) use au;
+
+    macro_rules! er {
+        ($lhs:expr, $rhs:expr) => {
+            Box::new(CTLFormula::ER($lhs, $rhs))
+        };
+    }
+    pub(crate) use er;
+
+    macro_rules! ar {
+        ($lhs:expr, $rhs:expr) => {
+            Box::new(CTLFormula::AR($lhs, $rhs))
+        };
+    }
+    pub(crate) use ar;
 }
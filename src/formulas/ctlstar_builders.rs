@@ -0,0 +1,77 @@
+//! Public functions to build CTL* formulas in code, without going through the parser.
+//! Every function returns a boxed formula, the same as what the parser returns.
+//!
+//! ```
+//! use minictl::formulas::ctlstar_builders::*;
+//! // E(G F p and F G q)
+//! let formula = e(and(g(f(atom("p"))), f(g(atom("q")))));
+//! ```
+use super::{CTLStarFormula, CTLStarVariable};
+
+pub fn top() -> Box<CTLStarFormula> {
+    Box::new(CTLStarFormula::Top)
+}
+
+pub fn bot() -> Box<CTLStarFormula> {
+    Box::new(CTLStarFormula::Bot)
+}
+
+pub fn atom(name: impl Into<String>) -> Box<CTLStarFormula> {
+    Box::new(CTLStarFormula::Atomic(CTLStarVariable::new(name.into())))
+}
+
+pub fn neg(inner: Box<CTLStarFormula>) -> Box<CTLStarFormula> {
+    Box::new(CTLStarFormula::Neg(inner))
+}
+
+pub fn and(lhs: Box<CTLStarFormula>, rhs: Box<CTLStarFormula>) -> Box<CTLStarFormula> {
+    Box::new(CTLStarFormula::And(lhs, rhs))
+}
+
+pub fn or(lhs: Box<CTLStarFormula>, rhs: Box<CTLStarFormula>) -> Box<CTLStarFormula> {
+    Box::new(CTLStarFormula::Or(lhs, rhs))
+}
+
+pub fn implies_r(lhs: Box<CTLStarFormula>, rhs: Box<CTLStarFormula>) -> Box<CTLStarFormula> {
+    Box::new(CTLStarFormula::ImpliesR(lhs, rhs))
+}
+
+pub fn implies_l(lhs: Box<CTLStarFormula>, rhs: Box<CTLStarFormula>) -> Box<CTLStarFormula> {
+    Box::new(CTLStarFormula::ImpliesL(lhs, rhs))
+}
+
+pub fn implies_bi(lhs: Box<CTLStarFormula>, rhs: Box<CTLStarFormula>) -> Box<CTLStarFormula> {
+    Box::new(CTLStarFormula::BiImplies(lhs, rhs))
+}
+
+pub fn e(inner: Box<CTLStarFormula>) -> Box<CTLStarFormula> {
+    Box::new(CTLStarFormula::E(inner))
+}
+
+pub fn a(inner: Box<CTLStarFormula>) -> Box<CTLStarFormula> {
+    Box::new(CTLStarFormula::A(inner))
+}
+
+pub fn u(lhs: Box<CTLStarFormula>, rhs: Box<CTLStarFormula>) -> Box<CTLStarFormula> {
+    Box::new(CTLStarFormula::U(lhs, rhs))
+}
+
+pub fn w(lhs: Box<CTLStarFormula>, rhs: Box<CTLStarFormula>) -> Box<CTLStarFormula> {
+    Box::new(CTLStarFormula::W(lhs, rhs))
+}
+
+pub fn r(lhs: Box<CTLStarFormula>, rhs: Box<CTLStarFormula>) -> Box<CTLStarFormula> {
+    Box::new(CTLStarFormula::R(lhs, rhs))
+}
+
+pub fn x(inner: Box<CTLStarFormula>) -> Box<CTLStarFormula> {
+    Box::new(CTLStarFormula::X(inner))
+}
+
+pub fn f(inner: Box<CTLStarFormula>) -> Box<CTLStarFormula> {
+    Box::new(CTLStarFormula::F(inner))
+}
+
+pub fn g(inner: Box<CTLStarFormula>) -> Box<CTLStarFormula> {
+    Box::new(CTLStarFormula::G(inner))
+}
@@ -0,0 +1,288 @@
+use std::iter::Peekable;
+use std::str::Chars;
+use thiserror::Error;
+
+use super::ctlstar_types::{CTLStarFormula, CTLStarVariable};
+
+#[derive(Debug, Clone, PartialEq)]
+enum CTLStarToken {
+    Top,
+    Bot,
+    Variable(String),
+    Not,
+    And,
+    Or,
+    ImpliesR,
+    ImpliesL,
+    BiImplies,
+    E,
+    A,
+    X,
+    F,
+    G,
+    U,
+    W,
+    R,
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+}
+
+#[derive(Debug, PartialEq, Error)]
+pub enum CTLStarParseError {
+    #[error("Unexpected character: {0}")]
+    UnexpectedCharacter(char),
+    #[error("Unknown keyword: {0}")]
+    UnknownKeyword(String),
+    #[error("Unexpected Token: {0}")]
+    UnexpectedToken(String),
+    #[error("Unexpected end of input: {0}")]
+    UnexpectedEndOfInput(String),
+}
+
+struct CTLStarLexer<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl<'a> CTLStarLexer<'a> {
+    fn new(input: &'a str) -> Self {
+        Self {
+            chars: input.chars().peekable(),
+        }
+    }
+
+    fn expect_char(&mut self, expected: char, err_str: &str) -> Result<(), CTLStarParseError> {
+        match self.chars.next() {
+            Some(a) if a == expected => Ok(()),
+            Some(other) => Err(CTLStarParseError::UnexpectedCharacter(other)),
+            None => {
+                let err_message = format!("{}, got end of input", err_str);
+                Err(CTLStarParseError::UnexpectedEndOfInput(err_message))
+            }
+        }
+    }
+
+    fn next_token(&mut self) -> Option<Result<CTLStarToken, CTLStarParseError>> {
+        while let Some(&ch) = self.chars.peek() {
+            match ch {
+                ch if ch.is_whitespace() => {
+                    self.chars.next();
+                }
+                '(' => {
+                    self.chars.next();
+                    return Some(Ok(CTLStarToken::LParen));
+                }
+                ')' => {
+                    self.chars.next();
+                    return Some(Ok(CTLStarToken::RParen));
+                }
+                '[' => {
+                    self.chars.next();
+                    return Some(Ok(CTLStarToken::LBracket));
+                }
+                ']' => {
+                    self.chars.next();
+                    return Some(Ok(CTLStarToken::RBracket));
+                }
+                '!' => {
+                    self.chars.next();
+                    return Some(Ok(CTLStarToken::Not));
+                }
+                '-' => {
+                    self.chars.next();
+                    return Some(
+                        self.expect_char('>', "Expected > after - that didn't start with <")
+                            .map(|_| CTLStarToken::ImpliesR),
+                    );
+                }
+                '<' => {
+                    self.chars.next();
+                    // Both `<-` and `<->` start with `<-`, only `<->` has a `>` after it.
+                    if let Err(error) = self.expect_char('-', "Expected - after <") {
+                        return Some(Err(error));
+                    }
+                    if self.chars.peek() == Some(&'>') {
+                        self.chars.next();
+                        return Some(Ok(CTLStarToken::BiImplies));
+                    }
+                    return Some(Ok(CTLStarToken::ImpliesL));
+                }
+                ch if ch.is_lowercase() => return self.consume_lowercase_variable_or_keyword(),
+                ch if ch.is_uppercase() => return self.consume_keyword(),
+                _ => return Some(Err(CTLStarParseError::UnexpectedCharacter(ch))),
+            }
+        }
+        None
+    }
+
+    fn consume_lowercase_variable_or_keyword(
+        &mut self,
+    ) -> Option<Result<CTLStarToken, CTLStarParseError>> {
+        let mut name = String::new();
+        while let Some(&ch) = self.chars.peek() {
+            if ch.is_lowercase() || ch.is_numeric() || matches!(ch, '=' | '_' | '.') {
+                name.push(ch);
+                self.chars.next();
+            } else {
+                break;
+            }
+        }
+        match name.as_str() {
+            "and" => Some(Ok(CTLStarToken::And)),
+            "or" => Some(Ok(CTLStarToken::Or)),
+            _ => Some(Ok(CTLStarToken::Variable(name))),
+        }
+    }
+
+    // Path quantifiers and temporal operators are single letters, and are split up here,
+    // so the CTL-style `AG EF p` and the stacked `E GF p` lex the same as `A G E F p` and `E G F p`.
+    fn consume_keyword(&mut self) -> Option<Result<CTLStarToken, CTLStarParseError>> {
+        use CTLStarToken as T;
+        let operator = match self.chars.peek() {
+            Some('E') => Some(T::E),
+            Some('A') => Some(T::A),
+            Some('X') => Some(T::X),
+            Some('F') => Some(T::F),
+            Some('G') => Some(T::G),
+            Some('U') => Some(T::U),
+            Some('W') => Some(T::W),
+            Some('R') => Some(T::R),
+            _ => None,
+        };
+        if let Some(operator) = operator {
+            self.chars.next();
+            return Some(Ok(operator));
+        }
+        let mut name = String::new();
+        while let Some(&ch) = self.chars.peek() {
+            if ch.is_uppercase() {
+                name.push(ch);
+                self.chars.next();
+            } else {
+                break;
+            }
+        }
+        match name.as_str() {
+            "TOP" => Some(Ok(T::Top)),
+            "BOT" => Some(Ok(T::Bot)),
+            _ => Some(Err(CTLStarParseError::UnknownKeyword(name))),
+        }
+    }
+}
+
+impl Iterator for CTLStarLexer<'_> {
+    type Item = Result<CTLStarToken, CTLStarParseError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_token()
+    }
+}
+
+struct CTLStarParser<'a> {
+    tokens: Peekable<CTLStarLexer<'a>>,
+}
+
+impl<'a> CTLStarParser<'a> {
+    fn new(lexer: CTLStarLexer<'a>) -> Self {
+        Self {
+            tokens: lexer.peekable(),
+        }
+    }
+    fn expect_token(
+        &mut self,
+        expected: CTLStarToken,
+        err_str: &str,
+    ) -> Result<(), CTLStarParseError> {
+        match self.tokens.next() {
+            Some(Ok(a)) if a == expected => Ok(()),
+            Some(other) => {
+                let err_message = format!("{}, got {:?}", err_str, other);
+                Err(CTLStarParseError::UnexpectedToken(err_message))
+            }
+            None => {
+                let err_message = format!("{}, got end of input", err_str);
+                Err(CTLStarParseError::UnexpectedEndOfInput(err_message))
+            }
+        }
+    }
+    fn parse(&mut self) -> Result<Box<CTLStarFormula>, CTLStarParseError> {
+        self.parse_expression(1)
+    }
+    fn parse_expression(
+        &mut self,
+        min_precedence: u8,
+    ) -> Result<Box<CTLStarFormula>, CTLStarParseError> {
+        use CTLStarFormula as F;
+        use CTLStarToken as T;
+        let mut left = self.parse_primary()?;
+
+        while let Some(Ok(token)) = self.tokens.peek() {
+            let token_precedence = match token {
+                T::ImpliesR | T::ImpliesL | T::BiImplies => 1,
+                T::Or => 2,
+                T::And => 3,
+                T::U | T::W | T::R => 4,
+                _ => break,
+            };
+
+            if token_precedence < min_precedence {
+                break;
+            }
+
+            let token = self.tokens.next().unwrap()?;
+            let right = self.parse_expression(token_precedence + 1)?;
+
+            left = Box::new(match token {
+                T::ImpliesR => F::ImpliesR(left, right),
+                T::ImpliesL => F::ImpliesL(left, right),
+                T::BiImplies => F::BiImplies(left, right),
+                T::Or => F::Or(left, right),
+                T::And => F::And(left, right),
+                T::U => F::U(left, right),
+                T::W => F::W(left, right),
+                T::R => F::R(left, right),
+                _ => unreachable!(),
+            });
+        }
+
+        Ok(left)
+    }
+    fn parse_primary(&mut self) -> Result<Box<CTLStarFormula>, CTLStarParseError> {
+        use super::ctlstar_formula_macros as f;
+        use CTLStarToken as T;
+        match self.tokens.next() {
+            Some(Ok(T::Top)) => Ok(f::top!()),
+            Some(Ok(T::Bot)) => Ok(f::bot!()),
+            Some(Ok(T::Variable(var))) => Ok(f::atom!(var)),
+            Some(Ok(T::Not)) => Ok(f::neg!(self.parse_primary()?)),
+            Some(Ok(T::E)) => Ok(f::e!(self.parse_primary()?)),
+            Some(Ok(T::A)) => Ok(f::a!(self.parse_primary()?)),
+            Some(Ok(T::X)) => Ok(f::x!(self.parse_primary()?)),
+            Some(Ok(T::F)) => Ok(f::f!(self.parse_primary()?)),
+            Some(Ok(T::G)) => Ok(f::g!(self.parse_primary()?)),
+            Some(Ok(T::LParen)) => {
+                let expr = self.parse_expression(1)?;
+                self.expect_token(T::RParen, "Expected closing parentheses")?;
+                Ok(expr)
+            }
+            // Brackets are only there so the CTL notation `E[p U q]` can be used as-is.
+            Some(Ok(T::LBracket)) => {
+                let expr = self.parse_expression(1)?;
+                self.expect_token(T::RBracket, "Expected closing bracket")?;
+                Ok(expr)
+            }
+            Some(Ok(other)) => Err(CTLStarParseError::UnexpectedToken(format!("{:?}", other))),
+            Some(Err(error)) => Err(error),
+            None => Err(CTLStarParseError::UnexpectedEndOfInput(
+                "Expected primary expression".to_owned(),
+            )),
+        }
+    }
+}
+
+#[inline(always)]
+pub fn parse_ctlstar(input: &str) -> Result<Box<CTLStarFormula>, CTLStarParseError> {
+    let lexer = CTLStarLexer::new(input);
+    let mut parser = CTLStarParser::new(lexer);
+    parser.parse()
+}
@@ -0,0 +1,205 @@
+// Unlike in CTL, the path quantifiers and temporal operators are separate formulas here,
+// so `CTLStarFormula("E", CTLStarFormula("G", CTLStarFormula("p")))` is what CTL calls EG p.
+// Otherwise this is the same switchboard over the formula types as for the other logics.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyTuple;
+
+use std::fmt;
+
+use crate::formulas::CTLStarVariable;
+
+use super::{parse_ctlstar, CTLStarFormula};
+
+/// The Python view of a `CTLStarFormula`: the name of its operator and its arguments.
+/// The path quantifiers `E` and `A` are operators of their own, which take any path formula.
+/// Objects are frozen once created.
+///
+/// Formulas can be built from their parts, like
+/// `CTLStarFormula("E", CTLStarFormula("G", CTLStarFormula("p")))`,
+/// or parsed, like `CTLStarFormula.parse("E(GF p and FG q)")`.
+/// They can be printed, compared, and hashed.
+#[pyclass(
+    module = "minictl",
+    name = "CTLStarFormula",
+    get_all,
+    frozen,
+    eq,
+    hash,
+    str,
+    from_py_object
+)]
+#[derive(Debug, Hash, PartialEq, Eq, Clone)]
+pub struct PyCTLStarFormula {
+    pub name: String,
+    pub arguments: Vec<PyCTLStarFormula>,
+}
+
+impl PyCTLStarFormula {
+    #[inline(always)]
+    fn new_bare(name: &str, arguments: Vec<PyCTLStarFormula>) -> Self {
+        Self {
+            name: name.to_owned(),
+            arguments,
+        }
+    }
+    fn new_with_pyargs(
+        name: String,
+        py_arguments: &Bound<'_, PyTuple>,
+        nr_args: usize,
+    ) -> PyResult<Self> {
+        let nr_found = py_arguments.len();
+        let mut arguments = Vec::with_capacity(nr_found);
+        if nr_found == nr_args {
+            for item in py_arguments.iter() {
+                arguments.push(item.extract::<PyCTLStarFormula>()?);
+            }
+            Ok(Self { name, arguments })
+        } else {
+            Err(PyValueError::new_err(
+                "Expected {nr_args} arguments for {name}, found {nr_found}",
+            ))
+        }
+    }
+    pub(crate) fn from_rust(formula: &CTLStarFormula) -> Self {
+        use CTLStarFormula as F;
+        use PyCTLStarFormula as PF;
+        match formula {
+            F::Top => Self::new_bare("TOP", Vec::default()),
+            F::Bot => Self::new_bare("BOT", Vec::default()),
+            F::Neg(f) => Self::new_bare("Neg", vec![PF::from_rust(f)]),
+            F::E(f) => Self::new_bare("E", vec![PF::from_rust(f)]),
+            F::A(f) => Self::new_bare("A", vec![PF::from_rust(f)]),
+            F::X(f) => Self::new_bare("X", vec![PF::from_rust(f)]),
+            F::F(f) => Self::new_bare("F", vec![PF::from_rust(f)]),
+            F::G(f) => Self::new_bare("G", vec![PF::from_rust(f)]),
+            F::And(f1, f2) => Self::new_bare("And", vec![PF::from_rust(f1), PF::from_rust(f2)]),
+            F::Or(f1, f2) => Self::new_bare("Or", vec![PF::from_rust(f1), PF::from_rust(f2)]),
+            F::ImpliesR(f1, f2) => {
+                Self::new_bare("ImpliesR", vec![PF::from_rust(f1), PF::from_rust(f2)])
+            }
+            F::ImpliesL(f1, f2) => {
+                Self::new_bare("ImpliesL", vec![PF::from_rust(f1), PF::from_rust(f2)])
+            }
+            F::BiImplies(f1, f2) => {
+                Self::new_bare("BiImplies", vec![PF::from_rust(f1), PF::from_rust(f2)])
+            }
+            F::U(f1, f2) => Self::new_bare("U", vec![PF::from_rust(f1), PF::from_rust(f2)]),
+            F::W(f1, f2) => Self::new_bare("W", vec![PF::from_rust(f1), PF::from_rust(f2)]),
+            F::R(f1, f2) => Self::new_bare("R", vec![PF::from_rust(f1), PF::from_rust(f2)]),
+            F::Atomic(variable) => Self {
+                name: variable.inner.clone(),
+                arguments: Vec::default(),
+            },
+        }
+    }
+    #[inline(always)]
+    fn arg_to_rust(&self, index: usize) -> Option<Box<CTLStarFormula>> {
+        self.arguments.get(index)?.to_rust()
+    }
+
+    pub(crate) fn to_rust(&self) -> Option<Box<CTLStarFormula>> {
+        use super::ctlstar_formula_macros as f;
+        let ret = match self.name.as_str() {
+            "TOP" => f::top!(),
+            "BOT" => f::bot!(),
+            "Neg" => f::neg!(self.arg_to_rust(0)?),
+            "E" => f::e!(self.arg_to_rust(0)?),
+            "A" => f::a!(self.arg_to_rust(0)?),
+            "X" => f::x!(self.arg_to_rust(0)?),
+            "F" => f::f!(self.arg_to_rust(0)?),
+            "G" => f::g!(self.arg_to_rust(0)?),
+            "And" => f::and!(self.arg_to_rust(0)?, self.arg_to_rust(1)?),
+            "Or" => f::or!(self.arg_to_rust(0)?, self.arg_to_rust(1)?),
+            "ImpliesR" => f::impies_r!(self.arg_to_rust(0)?, self.arg_to_rust(1)?),
+            "ImpliesL" => f::impies_l!(self.arg_to_rust(0)?, self.arg_to_rust(1)?),
+            "BiImplies" => f::implies_bi!(self.arg_to_rust(0)?, self.arg_to_rust(1)?),
+            "U" => f::u!(self.arg_to_rust(0)?, self.arg_to_rust(1)?),
+            "W" => f::w!(self.arg_to_rust(0)?, self.arg_to_rust(1)?),
+            "R" => f::r!(self.arg_to_rust(0)?, self.arg_to_rust(1)?),
+            other => f::atom!(other.to_string()),
+        };
+        Some(ret)
+    }
+}
+
+impl fmt::Display for PyCTLStarFormula {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The constructor checks the number of arguments, so the fallback should not be
+        // needed, but printing a malformed formula should not panic either.
+        let get_argstr = |index| {
+            self.arguments
+                .get(index)
+                .map(|f| format!("{}", f))
+                .unwrap_or("ERR: missing".to_string())
+        };
+        match self.name.as_str() {
+            "TOP" => write!(f, "⊤"),
+            "BOT" => write!(f, "⊥"),
+            "Neg" => write!(f, "¬({})", get_argstr(0)),
+            "E" => write!(f, "E({})", get_argstr(0)),
+            "A" => write!(f, "A({})", get_argstr(0)),
+            "X" => write!(f, "X({})", get_argstr(0)),
+            "F" => write!(f, "F({})", get_argstr(0)),
+            "G" => write!(f, "G({})", get_argstr(0)),
+            "And" => write!(f, "({})∧({})", get_argstr(0), get_argstr(1)),
+            "Or" => write!(f, "({})∨({})", get_argstr(0), get_argstr(1)),
+            "ImpliesR" => write!(f, "({})→({})", get_argstr(0), get_argstr(1)),
+            "ImpliesL" => write!(f, "({})←({})", get_argstr(0), get_argstr(1)),
+            "BiImplies" => write!(f, "({})↔({})", get_argstr(0), get_argstr(1)),
+            "U" => write!(f, "({})U({})", get_argstr(0), get_argstr(1)),
+            "W" => write!(f, "({})W({})", get_argstr(0), get_argstr(1)),
+            "R" => write!(f, "({})R({})", get_argstr(0), get_argstr(1)),
+            _ => write!(f, "{}", self.name),
+        }
+    }
+}
+
+#[pymethods]
+impl PyCTLStarFormula {
+    #[new]
+    #[pyo3(signature=(name, *py_args))]
+    fn new(name: String, py_args: &Bound<'_, PyTuple>) -> PyResult<Self> {
+        match name.as_str() {
+            "TOP" => Self::new_with_pyargs(name, py_args, 0),
+            "BOT" => Self::new_with_pyargs(name, py_args, 0),
+            "Neg" => Self::new_with_pyargs(name, py_args, 1),
+            "E" => Self::new_with_pyargs(name, py_args, 1),
+            "A" => Self::new_with_pyargs(name, py_args, 1),
+            "X" => Self::new_with_pyargs(name, py_args, 1),
+            "F" => Self::new_with_pyargs(name, py_args, 1),
+            "G" => Self::new_with_pyargs(name, py_args, 1),
+            "And" => Self::new_with_pyargs(name, py_args, 2),
+            "Or" => Self::new_with_pyargs(name, py_args, 2),
+            "ImpliesR" => Self::new_with_pyargs(name, py_args, 2),
+            "ImpliesL" => Self::new_with_pyargs(name, py_args, 2),
+            "BiImplies" => Self::new_with_pyargs(name, py_args, 2),
+            "U" => Self::new_with_pyargs(name, py_args, 2),
+            "W" => Self::new_with_pyargs(name, py_args, 2),
+            "R" => Self::new_with_pyargs(name, py_args, 2),
+            _ if py_args.is_empty() => {
+                if name
+                    .chars()
+                    .all(|c| c.is_alphanumeric() && !c.is_uppercase())
+                {
+                    Self::new_with_pyargs(name, py_args, 0)
+                } else {
+                    Err(PyValueError::new_err(
+                        "{name} is not a valid formula name: not all letters are lowercase",
+                    ))
+                }
+            }
+            _ => Err(PyValueError::new_err(
+                "{name} is not a valid formula name, or, if variable, arguments are nonempty",
+            )),
+        }
+    }
+    #[staticmethod]
+    fn parse(formula: String) -> PyResult<Self> {
+        let res = parse_ctlstar(&formula).map_err(|err| {
+            PyValueError::new_err(format!("Cannot parse {} into formula: {}", formula, err))
+        })?;
+        Ok(Self::from_rust(&res))
+    }
+}
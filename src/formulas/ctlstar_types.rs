@@ -0,0 +1,201 @@
+use super::MLVariable;
+
+#[derive(Debug, Hash, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct CTLStarVariable {
+    pub inner: String,
+}
+impl CTLStarVariable {
+    pub fn new(inner: String) -> Self {
+        Self { inner }
+    }
+}
+impl MLVariable for CTLStarVariable {}
+
+#[derive(Debug, Hash, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CTLStarFormula {
+    Top,
+    Bot,
+    Atomic(CTLStarVariable),
+    Neg(Box<CTLStarFormula>),
+    And(Box<CTLStarFormula>, Box<CTLStarFormula>),
+    Or(Box<CTLStarFormula>, Box<CTLStarFormula>),
+    ImpliesR(Box<CTLStarFormula>, Box<CTLStarFormula>),
+    ImpliesL(Box<CTLStarFormula>, Box<CTLStarFormula>),
+    BiImplies(Box<CTLStarFormula>, Box<CTLStarFormula>),
+    E(Box<CTLStarFormula>),
+    A(Box<CTLStarFormula>),
+    X(Box<CTLStarFormula>),
+    F(Box<CTLStarFormula>),
+    G(Box<CTLStarFormula>),
+    U(Box<CTLStarFormula>, Box<CTLStarFormula>),
+    W(Box<CTLStarFormula>, Box<CTLStarFormula>),
+    R(Box<CTLStarFormula>, Box<CTLStarFormula>),
+}
+
+impl CTLStarFormula {
+    pub fn for_each_child(&self, mut f: impl FnMut(&CTLStarFormula)) {
+        use CTLStarFormula as F;
+        match self {
+            F::E(x) | F::A(x) | F::X(x) | F::F(x) | F::G(x) | F::Neg(x) => f(x),
+            F::And(l, r)
+            | F::Or(l, r)
+            | F::ImpliesR(l, r)
+            | F::ImpliesL(l, r)
+            | F::BiImplies(l, r)
+            | F::U(l, r)
+            | F::W(l, r)
+            | F::R(l, r) => {
+                f(l);
+                f(r);
+            }
+            F::Top | F::Bot | F::Atomic(_) => {}
+        }
+    }
+    /// Whether this is a state formula, meaning every temporal operator is under an E or A,
+    /// so its truth only depends on the current state and not on the path taken from it.
+    pub fn is_state_formula(&self) -> bool {
+        use CTLStarFormula as F;
+        match self {
+            F::E(_) | F::A(_) | F::Top | F::Bot | F::Atomic(_) => true,
+            F::X(_) | F::F(_) | F::G(_) | F::U(..) | F::W(..) | F::R(..) => false,
+            _ => {
+                let mut ret = true;
+                self.for_each_child(|child| ret &= child.is_state_formula());
+                ret
+            }
+        }
+    }
+    pub fn total_size(&self) -> usize {
+        let mut sum = 1;
+        self.for_each_child(|child| {
+            sum += child.total_size();
+        });
+        sum
+    }
+}
+
+pub(crate) mod ctlstar_formula_macros {
+    #![allow(unused)]
+
+    macro_rules! top {
+        () => {
+            Box::new(CTLStarFormula::Top)
+        };
+    }
+    pub(crate) use top;
+
+    macro_rules! bot {
+        () => {
+            Box::new(CTLStarFormula::Bot)
+        };
+    }
+    pub(crate) use bot;
+
+    macro_rules! atom {
+        ($inner:expr) => {
+            Box::new(CTLStarFormula::Atomic(CTLStarVariable::new($inner)))
+        };
+    }
+    pub(crate) use atom;
+
+    macro_rules! neg {
+        ($inner:expr) => {
+            Box::new(CTLStarFormula::Neg($inner))
+        };
+    }
+    pub(crate) use neg;
+
+    macro_rules! and {
+        ($lhs:expr, $rhs:expr) => {
+            Box::new(CTLStarFormula::And($lhs, $rhs))
+        };
+    }
+    pub(crate) use and;
+
+    macro_rules! or {
+        ($lhs:expr, $rhs:expr) => {
+            Box::new(CTLStarFormula::Or($lhs, $rhs))
+        };
+    }
+    pub(crate) use or;
+
+    macro_rules! impies_r {
+        ($lhs:expr, $rhs:expr) => {
+            Box::new(CTLStarFormula::ImpliesR($lhs, $rhs))
+        };
+    }
+    pub(crate) use impies_r;
+
+    macro_rules! impies_l {
+        ($lhs:expr, $rhs:expr) => {
+            Box::new(CTLStarFormula::ImpliesL($lhs, $rhs))
+        };
+    }
+    pub(crate) use impies_l;
+
+    macro_rules! implies_bi {
+        ($lhs:expr, $rhs:expr) => {
+            Box::new(CTLStarFormula::BiImplies($lhs, $rhs))
+        };
+    }
+    pub(crate) use implies_bi;
+
+    macro_rules! e {
+        ($inner:expr) => {
+            Box::new(CTLStarFormula::E($inner))
+        };
+    }
+    pub(crate) use e;
+
+    macro_rules! a {
+        ($inner:expr) => {
+            Box::new(CTLStarFormula::A($inner))
+        };
+    }
+    pub(crate) use a;
+
+    macro_rules! x {
+        ($inner:expr) => {
+            Box::new(CTLStarFormula::X($inner))
+        };
+    }
+    pub(crate) use x;
+
+    macro_rules! f {
+        ($inner:expr) => {
+            Box::new(CTLStarFormula::F($inner))
+        };
+    }
+    pub(crate) use f;
+
+    macro_rules! g {
+        ($inner:expr) => {
+            Box::new(CTLStarFormula::G($inner))
+        };
+    }
+    pub(crate) use g;
+
+    macro_rules! u {
+        ($lhs:expr, $rhs:expr) => {
+            Box::new(CTLStarFormula::U($lhs, $rhs))
+        };
+    }
+    pub(crate) use u;
+
+    macro_rules! w {
+        ($lhs:expr, $rhs:expr) => {
+            Box::new(CTLStarFormula::W($lhs, $rhs))
+        };
+    }
+    pub(crate) use w;
+
+    macro_rules! r {
+        ($lhs:expr, $rhs:expr) => {
+            Box::new(CTLStarFormula::R($lhs, $rhs))
+        };
+    }
+    pub(crate) use r;
+}
@@ -0,0 +1,54 @@
+//! Public functions to build Hennessy–Milner logic formulas in code, without going through the parser.
+//! Every function returns a boxed formula, the same as what the parser returns.
+//! As `box` is a reserved word in rust, the box modality is built with `square`.
+//!
+//! ```
+//! use minictl::formulas::hml_builders::*;
+//! // <coin>([coffee]BOT and <tea>TOP)
+//! let formula = diamond("coin", and(square("coffee", bot()), diamond("tea", top())));
+//! ```
+use super::{HMLFormula, HMLVariable};
+
+pub fn top() -> Box<HMLFormula> {
+    Box::new(HMLFormula::Top)
+}
+
+pub fn bot() -> Box<HMLFormula> {
+    Box::new(HMLFormula::Bot)
+}
+
+pub fn atom(name: impl Into<String>) -> Box<HMLFormula> {
+    Box::new(HMLFormula::Atomic(HMLVariable::new(name.into())))
+}
+
+pub fn neg(inner: Box<HMLFormula>) -> Box<HMLFormula> {
+    Box::new(HMLFormula::Neg(inner))
+}
+
+pub fn and(lhs: Box<HMLFormula>, rhs: Box<HMLFormula>) -> Box<HMLFormula> {
+    Box::new(HMLFormula::And(lhs, rhs))
+}
+
+pub fn or(lhs: Box<HMLFormula>, rhs: Box<HMLFormula>) -> Box<HMLFormula> {
+    Box::new(HMLFormula::Or(lhs, rhs))
+}
+
+pub fn implies_r(lhs: Box<HMLFormula>, rhs: Box<HMLFormula>) -> Box<HMLFormula> {
+    Box::new(HMLFormula::ImpliesR(lhs, rhs))
+}
+
+pub fn implies_l(lhs: Box<HMLFormula>, rhs: Box<HMLFormula>) -> Box<HMLFormula> {
+    Box::new(HMLFormula::ImpliesL(lhs, rhs))
+}
+
+pub fn implies_bi(lhs: Box<HMLFormula>, rhs: Box<HMLFormula>) -> Box<HMLFormula> {
+    Box::new(HMLFormula::BiImplies(lhs, rhs))
+}
+
+pub fn diamond(action: impl Into<String>, inner: Box<HMLFormula>) -> Box<HMLFormula> {
+    Box::new(HMLFormula::Diamond(action.into(), inner))
+}
+
+pub fn square(action: impl Into<String>, inner: Box<HMLFormula>) -> Box<HMLFormula> {
+    Box::new(HMLFormula::Box(action.into(), inner))
+}
@@ -0,0 +1,259 @@
+use std::iter::Peekable;
+use std::str::Chars;
+use thiserror::Error;
+
+use super::hml_types::{HMLFormula, HMLVariable};
+
+#[derive(Debug, Clone, PartialEq)]
+enum HMLToken {
+    Top,
+    Bot,
+    Variable(String),
+    Not,
+    And,
+    Or,
+    ImpliesR,
+    ImpliesL,
+    BiImplies,
+    Diamond(String),
+    Box(String),
+    LParen,
+    RParen,
+}
+
+#[derive(Debug, PartialEq, Error)]
+pub enum HMLParseError {
+    #[error("Unexpected character: {0}")]
+    UnexpectedCharacter(char),
+    #[error("Unknown keyword: {0}")]
+    UnknownKeyword(String),
+    #[error("Unexpected Token: {0}")]
+    UnexpectedToken(String),
+    #[error("Unexpected end of input: {0}")]
+    UnexpectedEndOfInput(String),
+}
+
+struct HMLLexer<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl<'a> HMLLexer<'a> {
+    fn new(input: &'a str) -> Self {
+        Self {
+            chars: input.chars().peekable(),
+        }
+    }
+
+    fn expect_char(&mut self, expected: char, err_str: &str) -> Result<(), HMLParseError> {
+        match self.chars.next() {
+            Some(a) if a == expected => Ok(()),
+            Some(other) => Err(HMLParseError::UnexpectedCharacter(other)),
+            None => {
+                let err_message = format!("{}, got end of input", err_str);
+                Err(HMLParseError::UnexpectedEndOfInput(err_message))
+            }
+        }
+    }
+
+    fn next_token(&mut self) -> Option<Result<HMLToken, HMLParseError>> {
+        while let Some(&ch) = self.chars.peek() {
+            match ch {
+                ch if ch.is_whitespace() => {
+                    self.chars.next();
+                }
+                '(' => {
+                    self.chars.next();
+                    return Some(Ok(HMLToken::LParen));
+                }
+                ')' => {
+                    self.chars.next();
+                    return Some(Ok(HMLToken::RParen));
+                }
+                '[' => {
+                    self.chars.next();
+                    return Some(self.consume_action(']').map(HMLToken::Box));
+                }
+                '!' => {
+                    self.chars.next();
+                    return Some(Ok(HMLToken::Not));
+                }
+                '-' => {
+                    self.chars.next();
+                    return Some(
+                        self.expect_char('>', "Expected > after - that didn't start with <")
+                            .map(|_| HMLToken::ImpliesR),
+                    );
+                }
+                '<' => {
+                    self.chars.next();
+                    // Anything but a - after < is the action of a diamond.
+                    if self.chars.peek() != Some(&'-') {
+                        return Some(self.consume_action('>').map(HMLToken::Diamond));
+                    }
+                    self.chars.next();
+                    if self.chars.peek() == Some(&'>') {
+                        self.chars.next();
+                        return Some(Ok(HMLToken::BiImplies));
+                    }
+                    return Some(Ok(HMLToken::ImpliesL));
+                }
+                ch if ch.is_lowercase() => return self.consume_lowercase_variable_or_keyword(),
+                ch if ch.is_uppercase() => return self.consume_keyword(),
+                _ => return Some(Err(HMLParseError::UnexpectedCharacter(ch))),
+            }
+        }
+        None
+    }
+
+    /// The action of a modality, up to and including the closing bracket.
+    fn consume_action(&mut self, close: char) -> Result<String, HMLParseError> {
+        let mut name = String::new();
+        while let Some(&ch) = self.chars.peek() {
+            if ch.is_whitespace() || matches!(ch, '<' | '>' | '[' | ']' | '(' | ')') {
+                break;
+            }
+            name.push(ch);
+            self.chars.next();
+        }
+        if name.is_empty() {
+            return Err(match self.chars.peek() {
+                Some(&ch) => HMLParseError::UnexpectedCharacter(ch),
+                None => HMLParseError::UnexpectedEndOfInput("Expected action".to_owned()),
+            });
+        }
+        let err_str = format!("Expected {} after action", close);
+        self.expect_char(close, &err_str)?;
+        Ok(name)
+    }
+
+    fn consume_lowercase_variable_or_keyword(&mut self) -> Option<Result<HMLToken, HMLParseError>> {
+        let mut name = String::new();
+        while let Some(&ch) = self.chars.peek() {
+            if ch.is_lowercase() || ch.is_numeric() || matches!(ch, '=' | '_' | '.') {
+                name.push(ch);
+                self.chars.next();
+            } else {
+                break;
+            }
+        }
+        match name.as_str() {
+            "and" => Some(Ok(HMLToken::And)),
+            "or" => Some(Ok(HMLToken::Or)),
+            _ => Some(Ok(HMLToken::Variable(name))),
+        }
+    }
+
+    fn consume_keyword(&mut self) -> Option<Result<HMLToken, HMLParseError>> {
+        use HMLToken as T;
+        let mut name = String::new();
+        while let Some(&ch) = self.chars.peek() {
+            if ch.is_uppercase() {
+                name.push(ch);
+                self.chars.next();
+            } else {
+                break;
+            }
+        }
+        match name.as_str() {
+            "TOP" => Some(Ok(T::Top)),
+            "BOT" => Some(Ok(T::Bot)),
+            _ => Some(Err(HMLParseError::UnknownKeyword(name))),
+        }
+    }
+}
+
+impl Iterator for HMLLexer<'_> {
+    type Item = Result<HMLToken, HMLParseError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_token()
+    }
+}
+
+struct HMLParser<'a> {
+    tokens: Peekable<HMLLexer<'a>>,
+}
+
+impl<'a> HMLParser<'a> {
+    fn new(lexer: HMLLexer<'a>) -> Self {
+        Self {
+            tokens: lexer.peekable(),
+        }
+    }
+    fn expect_token(&mut self, expected: HMLToken, err_str: &str) -> Result<(), HMLParseError> {
+        match self.tokens.next() {
+            Some(Ok(a)) if a == expected => Ok(()),
+            Some(other) => {
+                let err_message = format!("{}, got {:?}", err_str, other);
+                Err(HMLParseError::UnexpectedToken(err_message))
+            }
+            None => {
+                let err_message = format!("{}, got end of input", err_str);
+                Err(HMLParseError::UnexpectedEndOfInput(err_message))
+            }
+        }
+    }
+    fn parse(&mut self) -> Result<Box<HMLFormula>, HMLParseError> {
+        self.parse_expression(1)
+    }
+    fn parse_expression(&mut self, min_precedence: u8) -> Result<Box<HMLFormula>, HMLParseError> {
+        use HMLFormula as F;
+        use HMLToken as T;
+        let mut left = self.parse_primary()?;
+
+        while let Some(Ok(token)) = self.tokens.peek() {
+            let token_precedence = match token {
+                T::ImpliesR | T::ImpliesL | T::BiImplies => 1,
+                T::Or => 2,
+                T::And => 3,
+                _ => break,
+            };
+
+            if token_precedence < min_precedence {
+                break;
+            }
+
+            let token = self.tokens.next().unwrap()?;
+            let right = self.parse_expression(token_precedence + 1)?;
+
+            left = Box::new(match token {
+                T::ImpliesR => F::ImpliesR(left, right),
+                T::ImpliesL => F::ImpliesL(left, right),
+                T::BiImplies => F::BiImplies(left, right),
+                T::Or => F::Or(left, right),
+                T::And => F::And(left, right),
+                _ => unreachable!(),
+            });
+        }
+
+        Ok(left)
+    }
+    fn parse_primary(&mut self) -> Result<Box<HMLFormula>, HMLParseError> {
+        use super::hml_formula_macros as f;
+        use HMLToken as T;
+        match self.tokens.next() {
+            Some(Ok(T::Top)) => Ok(f::top!()),
+            Some(Ok(T::Bot)) => Ok(f::bot!()),
+            Some(Ok(T::Variable(var))) => Ok(f::atom!(var)),
+            Some(Ok(T::Not)) => Ok(f::neg!(self.parse_primary()?)),
+            Some(Ok(T::Diamond(action))) => Ok(f::diamond!(action, self.parse_primary()?)),
+            Some(Ok(T::Box(action))) => Ok(f::square!(action, self.parse_primary()?)),
+            Some(Ok(T::LParen)) => {
+                let expr = self.parse_expression(1)?;
+                self.expect_token(T::RParen, "Expected closing parentheses")?;
+                Ok(expr)
+            }
+            Some(Ok(other)) => Err(HMLParseError::UnexpectedToken(format!("{:?}", other))),
+            Some(Err(error)) => Err(error),
+            None => Err(HMLParseError::UnexpectedEndOfInput(
+                "Expected primary expression".to_owned(),
+            )),
+        }
+    }
+}
+
+#[inline(always)]
+pub fn parse_hml(input: &str) -> Result<Box<HMLFormula>, HMLParseError> {
+    let lexer = HMLLexer::new(input);
+    let mut parser = HMLParser::new(lexer);
+    parser.parse()
+}
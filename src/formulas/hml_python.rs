@@ -0,0 +1,207 @@
+// Like the other formula files, this is a big switchboard matching over the formula types.
+// The only new thing is the action of the modalities, which is not a formula, so it gets its
+// own field, like `HMLFormula("Diamond", HMLFormula("TOP"), action="coin")`.
+// It is None for every formula that is not a modality.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyTuple;
+
+use std::fmt;
+
+use crate::formulas::HMLVariable;
+
+use super::{parse_hml, HMLFormula};
+
+/// The Python view of an `HMLFormula`: the name of its operator, its arguments, and for the
+/// `Diamond` and `Box` modalities, the action of the transitions they look at.
+/// Objects are frozen once created.
+///
+/// Formulas can be built from their parts, like
+/// `HMLFormula("Diamond", HMLFormula("Box", HMLFormula("BOT"), action="coffee"), action="coin")`,
+/// or parsed, like `HMLFormula.parse("<coin>[coffee]BOT")`.
+/// They can be printed, compared, and hashed.
+#[pyclass(
+    module = "minictl",
+    name = "HMLFormula",
+    get_all,
+    frozen,
+    eq,
+    hash,
+    str,
+    from_py_object
+)]
+#[derive(Debug, Hash, PartialEq, Eq, Clone)]
+pub struct PyHMLFormula {
+    pub name: String,
+    pub arguments: Vec<PyHMLFormula>,
+    pub action: Option<String>,
+}
+
+impl PyHMLFormula {
+    #[inline(always)]
+    fn new_bare(name: &str, arguments: Vec<PyHMLFormula>) -> Self {
+        Self {
+            name: name.to_owned(),
+            arguments,
+            action: None,
+        }
+    }
+    #[inline(always)]
+    fn new_modal(name: &str, action: &str, inner: PyHMLFormula) -> Self {
+        Self {
+            name: name.to_owned(),
+            arguments: vec![inner],
+            action: Some(action.to_owned()),
+        }
+    }
+    fn new_with_pyargs(
+        name: String,
+        py_arguments: &Bound<'_, PyTuple>,
+        nr_args: usize,
+        action: Option<String>,
+    ) -> PyResult<Self> {
+        let nr_found = py_arguments.len();
+        let mut arguments = Vec::with_capacity(nr_found);
+        if nr_found == nr_args {
+            for item in py_arguments.iter() {
+                arguments.push(item.extract::<PyHMLFormula>()?);
+            }
+            Ok(Self {
+                name,
+                arguments,
+                action,
+            })
+        } else {
+            Err(PyValueError::new_err(
+                "Expected {nr_args} arguments for {name}, found {nr_found}",
+            ))
+        }
+    }
+    pub(crate) fn from_rust(formula: &HMLFormula) -> Self {
+        use HMLFormula as F;
+        use PyHMLFormula as PF;
+        match formula {
+            F::Top => Self::new_bare("TOP", Vec::default()),
+            F::Bot => Self::new_bare("BOT", Vec::default()),
+            F::Neg(f) => Self::new_bare("Neg", vec![PF::from_rust(f)]),
+            F::Diamond(a, f) => Self::new_modal("Diamond", a, PF::from_rust(f)),
+            F::Box(a, f) => Self::new_modal("Box", a, PF::from_rust(f)),
+            F::And(f1, f2) => Self::new_bare("And", vec![PF::from_rust(f1), PF::from_rust(f2)]),
+            F::Or(f1, f2) => Self::new_bare("Or", vec![PF::from_rust(f1), PF::from_rust(f2)]),
+            F::ImpliesR(f1, f2) => {
+                Self::new_bare("ImpliesR", vec![PF::from_rust(f1), PF::from_rust(f2)])
+            }
+            F::ImpliesL(f1, f2) => {
+                Self::new_bare("ImpliesL", vec![PF::from_rust(f1), PF::from_rust(f2)])
+            }
+            F::BiImplies(f1, f2) => {
+                Self::new_bare("BiImplies", vec![PF::from_rust(f1), PF::from_rust(f2)])
+            }
+            F::Atomic(variable) => Self::new_bare(&variable.inner, Vec::default()),
+        }
+    }
+    #[inline(always)]
+    fn arg_to_rust(&self, index: usize) -> Option<Box<HMLFormula>> {
+        self.arguments.get(index)?.to_rust()
+    }
+
+    pub(crate) fn to_rust(&self) -> Option<Box<HMLFormula>> {
+        use super::hml_formula_macros as f;
+        let ret = match self.name.as_str() {
+            "TOP" => f::top!(),
+            "BOT" => f::bot!(),
+            "Neg" => f::neg!(self.arg_to_rust(0)?),
+            "Diamond" => f::diamond!(self.action.clone()?, self.arg_to_rust(0)?),
+            "Box" => f::square!(self.action.clone()?, self.arg_to_rust(0)?),
+            "And" => f::and!(self.arg_to_rust(0)?, self.arg_to_rust(1)?),
+            "Or" => f::or!(self.arg_to_rust(0)?, self.arg_to_rust(1)?),
+            "ImpliesR" => f::impies_r!(self.arg_to_rust(0)?, self.arg_to_rust(1)?),
+            "ImpliesL" => f::impies_l!(self.arg_to_rust(0)?, self.arg_to_rust(1)?),
+            "BiImplies" => f::implies_bi!(self.arg_to_rust(0)?, self.arg_to_rust(1)?),
+            other => f::atom!(other.to_string()),
+        };
+        Some(ret)
+    }
+}
+
+impl fmt::Display for PyHMLFormula {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The constructor checks the number of arguments and the action, so these fallbacks
+        // should not be needed, but printing a malformed formula should not panic either.
+        let get_argstr = |index| {
+            self.arguments
+                .get(index)
+                .map(|f| format!("{}", f))
+                .unwrap_or("ERR: missing".to_string())
+        };
+        let action = self.action.as_deref().unwrap_or("ERR: missing");
+        match self.name.as_str() {
+            "TOP" => write!(f, "⊤"),
+            "BOT" => write!(f, "⊥"),
+            "Neg" => write!(f, "¬({})", get_argstr(0)),
+            "Diamond" => write!(f, "⟨{}⟩({})", action, get_argstr(0)),
+            "Box" => write!(f, "[{}]({})", action, get_argstr(0)),
+            "And" => write!(f, "({})∧({})", get_argstr(0), get_argstr(1)),
+            "Or" => write!(f, "({})∨({})", get_argstr(0), get_argstr(1)),
+            "ImpliesR" => write!(f, "({})→({})", get_argstr(0), get_argstr(1)),
+            "ImpliesL" => write!(f, "({})←({})", get_argstr(0), get_argstr(1)),
+            "BiImplies" => write!(f, "({})↔({})", get_argstr(0), get_argstr(1)),
+            _ => write!(f, "{}", self.name),
+        }
+    }
+}
+
+#[pymethods]
+impl PyHMLFormula {
+    #[new]
+    #[pyo3(signature=(name, *py_args, action=None))]
+    fn new(name: String, py_args: &Bound<'_, PyTuple>, action: Option<String>) -> PyResult<Self> {
+        match (name.as_str(), action) {
+            ("Diamond" | "Box", Some(action)) => {
+                if action.is_empty() || action.chars().any(|c| c.is_whitespace()) {
+                    return Err(PyValueError::new_err(
+                        "The action of a modality has to be a nonempty name without whitespace",
+                    ));
+                }
+                Self::new_with_pyargs(name, py_args, 1, Some(action))
+            }
+            ("Diamond" | "Box", None) => Err(PyValueError::new_err(
+                "{name} needs an action, like action=\"a\"",
+            )),
+            (_, Some(_)) => Err(PyValueError::new_err(
+                "Only the modalities Diamond and Box take an action",
+            )),
+            ("TOP", None) => Self::new_with_pyargs(name, py_args, 0, None),
+            ("BOT", None) => Self::new_with_pyargs(name, py_args, 0, None),
+            ("Neg", None) => Self::new_with_pyargs(name, py_args, 1, None),
+            ("And", None) => Self::new_with_pyargs(name, py_args, 2, None),
+            ("Or", None) => Self::new_with_pyargs(name, py_args, 2, None),
+            ("ImpliesR", None) => Self::new_with_pyargs(name, py_args, 2, None),
+            ("ImpliesL", None) => Self::new_with_pyargs(name, py_args, 2, None),
+            ("BiImplies", None) => Self::new_with_pyargs(name, py_args, 2, None),
+            (_, None) if py_args.is_empty() => {
+                if name
+                    .chars()
+                    .all(|c| c.is_alphanumeric() && !c.is_uppercase())
+                {
+                    Self::new_with_pyargs(name, py_args, 0, None)
+                } else {
+                    Err(PyValueError::new_err(
+                        "{name} is not a valid formula name: variables have to be lowercase",
+                    ))
+                }
+            }
+            _ => Err(PyValueError::new_err(
+                "{name} is not a valid formula name, or, if variable, arguments are nonempty",
+            )),
+        }
+    }
+    #[staticmethod]
+    fn parse(formula: String) -> PyResult<Self> {
+        let res = parse_hml(&formula).map_err(|err| {
+            PyValueError::new_err(format!("Cannot parse {} into formula: {}", formula, err))
+        })?;
+        Ok(Self::from_rust(&res))
+    }
+}
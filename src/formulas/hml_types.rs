@@ -0,0 +1,162 @@
+use std::collections::HashSet;
+
+use super::MLVariable;
+
+#[derive(Debug, Hash, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct HMLVariable {
+    pub inner: String,
+}
+impl HMLVariable {
+    pub fn new(inner: String) -> Self {
+        Self { inner }
+    }
+}
+impl MLVariable for HMLVariable {}
+
+/// A formula of Hennessy–Milner logic, over models with actions on their transitions.
+///
+/// `Diamond(a, φ)` holds if some transition labelled `a` leads to a state satisfying `φ`,
+/// and `Box(a, φ)` if all of them do (which is vacuously true if there are none).
+/// `Atomic` is a proposition of the model, like in the other logics.
+#[derive(Debug, Hash, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HMLFormula {
+    Top,
+    Bot,
+    Atomic(HMLVariable),
+    Neg(Box<HMLFormula>),
+    And(Box<HMLFormula>, Box<HMLFormula>),
+    Or(Box<HMLFormula>, Box<HMLFormula>),
+    ImpliesR(Box<HMLFormula>, Box<HMLFormula>),
+    ImpliesL(Box<HMLFormula>, Box<HMLFormula>),
+    BiImplies(Box<HMLFormula>, Box<HMLFormula>),
+    Diamond(String, Box<HMLFormula>),
+    Box(String, Box<HMLFormula>),
+}
+
+impl HMLFormula {
+    pub fn for_each_child(&self, mut f: impl FnMut(&HMLFormula)) {
+        use HMLFormula as F;
+        match self {
+            F::Neg(x) | F::Diamond(_, x) | F::Box(_, x) => f(x),
+            F::And(l, r)
+            | F::Or(l, r)
+            | F::ImpliesR(l, r)
+            | F::ImpliesL(l, r)
+            | F::BiImplies(l, r) => {
+                f(l);
+                f(r);
+            }
+            F::Top | F::Bot | F::Atomic(_) => {}
+        }
+    }
+    /// All actions mentioned in the modalities of this formula.
+    pub fn actions(&self) -> HashSet<String> {
+        let mut ret = HashSet::new();
+        if let HMLFormula::Diamond(action, _) | HMLFormula::Box(action, _) = self {
+            ret.insert(action.clone());
+        }
+        self.for_each_child(|child| ret.extend(child.actions()));
+        ret
+    }
+    /// The modal depth of the formula: the maximal number of nested modalities.
+    /// A formula of depth n can only tell states apart by what happens in their first n steps.
+    pub fn depth(&self) -> usize {
+        let mut ret = 0;
+        self.for_each_child(|child| ret = ret.max(child.depth()));
+        match self {
+            HMLFormula::Diamond(..) | HMLFormula::Box(..) => ret + 1,
+            _ => ret,
+        }
+    }
+    pub fn total_size(&self) -> usize {
+        let mut sum = 1;
+        self.for_each_child(|child| {
+            sum += child.total_size();
+        });
+        sum
+    }
+}
+
+pub(crate) mod hml_formula_macros {
+    #![allow(unused)]
+
+    macro_rules! top {
+        () => {
+            Box::new(HMLFormula::Top)
+        };
+    }
+    pub(crate) use top;
+
+    macro_rules! bot {
+        () => {
+            Box::new(HMLFormula::Bot)
+        };
+    }
+    pub(crate) use bot;
+
+    macro_rules! atom {
+        ($inner:expr) => {
+            Box::new(HMLFormula::Atomic(HMLVariable::new($inner)))
+        };
+    }
+    pub(crate) use atom;
+
+    macro_rules! neg {
+        ($inner:expr) => {
+            Box::new(HMLFormula::Neg($inner))
+        };
+    }
+    pub(crate) use neg;
+
+    macro_rules! and {
+        ($lhs:expr, $rhs:expr) => {
+            Box::new(HMLFormula::And($lhs, $rhs))
+        };
+    }
+    pub(crate) use and;
+
+    macro_rules! or {
+        ($lhs:expr, $rhs:expr) => {
+            Box::new(HMLFormula::Or($lhs, $rhs))
+        };
+    }
+    pub(crate) use or;
+
+    macro_rules! impies_r {
+        ($lhs:expr, $rhs:expr) => {
+            Box::new(HMLFormula::ImpliesR($lhs, $rhs))
+        };
+    }
+    pub(crate) use impies_r;
+
+    macro_rules! impies_l {
+        ($lhs:expr, $rhs:expr) => {
+            Box::new(HMLFormula::ImpliesL($lhs, $rhs))
+        };
+    }
+    pub(crate) use impies_l;
+
+    macro_rules! implies_bi {
+        ($lhs:expr, $rhs:expr) => {
+            Box::new(HMLFormula::BiImplies($lhs, $rhs))
+        };
+    }
+    pub(crate) use implies_bi;
+
+    macro_rules! diamond {
+        ($action:expr, $inner:expr) => {
+            Box::new(HMLFormula::Diamond($action, $inner))
+        };
+    }
+    pub(crate) use diamond;
+
+    macro_rules! square {
+        ($action:expr, $inner:expr) => {
+            Box::new(HMLFormula::Box($action, $inner))
+        };
+    }
+    pub(crate) use square;
+}
@@ -0,0 +1,69 @@
+//! Public functions to build LTL formulas in code, without going through the parser.
+//! Every function returns a boxed formula, the same as what the parser returns.
+//!
+//! ```
+//! use minictl::formulas::ltl_builders::*;
+//! // G(request -> F response)
+//! let formula = g(implies_r(atom("request"), f(atom("response"))));
+//! ```
+use super::{LTLFormula, LTLVariable};
+
+pub fn top() -> Box<LTLFormula> {
+    Box::new(LTLFormula::Top)
+}
+
+pub fn bot() -> Box<LTLFormula> {
+    Box::new(LTLFormula::Bot)
+}
+
+pub fn atom(name: impl Into<String>) -> Box<LTLFormula> {
+    Box::new(LTLFormula::Atomic(LTLVariable::new(name.into())))
+}
+
+pub fn neg(inner: Box<LTLFormula>) -> Box<LTLFormula> {
+    Box::new(LTLFormula::Neg(inner))
+}
+
+pub fn and(lhs: Box<LTLFormula>, rhs: Box<LTLFormula>) -> Box<LTLFormula> {
+    Box::new(LTLFormula::And(lhs, rhs))
+}
+
+pub fn or(lhs: Box<LTLFormula>, rhs: Box<LTLFormula>) -> Box<LTLFormula> {
+    Box::new(LTLFormula::Or(lhs, rhs))
+}
+
+pub fn implies_r(lhs: Box<LTLFormula>, rhs: Box<LTLFormula>) -> Box<LTLFormula> {
+    Box::new(LTLFormula::ImpliesR(lhs, rhs))
+}
+
+pub fn implies_l(lhs: Box<LTLFormula>, rhs: Box<LTLFormula>) -> Box<LTLFormula> {
+    Box::new(LTLFormula::ImpliesL(lhs, rhs))
+}
+
+pub fn implies_bi(lhs: Box<LTLFormula>, rhs: Box<LTLFormula>) -> Box<LTLFormula> {
+    Box::new(LTLFormula::BiImplies(lhs, rhs))
+}
+
+pub fn u(lhs: Box<LTLFormula>, rhs: Box<LTLFormula>) -> Box<LTLFormula> {
+    Box::new(LTLFormula::U(lhs, rhs))
+}
+
+pub fn w(lhs: Box<LTLFormula>, rhs: Box<LTLFormula>) -> Box<LTLFormula> {
+    Box::new(LTLFormula::W(lhs, rhs))
+}
+
+pub fn r(lhs: Box<LTLFormula>, rhs: Box<LTLFormula>) -> Box<LTLFormula> {
+    Box::new(LTLFormula::R(lhs, rhs))
+}
+
+pub fn x(inner: Box<LTLFormula>) -> Box<LTLFormula> {
+    Box::new(LTLFormula::X(inner))
+}
+
+pub fn f(inner: Box<LTLFormula>) -> Box<LTLFormula> {
+    Box::new(LTLFormula::F(inner))
+}
+
+pub fn g(inner: Box<LTLFormula>) -> Box<LTLFormula> {
+    Box::new(LTLFormula::G(inner))
+}
@@ -0,0 +1,114 @@
+// Normal forms of LTL formulas.
+//
+// The until form only uses ⊤, variables, ¬, ∧, X, and U:
+//     F φ     = ⊤ U φ
+//     G φ     = ¬(⊤ U ¬φ)
+//     φ R ψ   = ¬(¬φ U ¬ψ)
+//     φ W ψ   = ¬(¬ψ U (¬φ ∧ ¬ψ))
+//
+// The negation normal form only has negations directly in front of variables.
+// It pushes negations inwards through the dual of every operator,
+// the same way the LTL checker does before building its tableau:
+//     ¬X φ     = X ¬φ
+//     ¬F φ     = G ¬φ             ¬G φ   = F ¬φ
+//     ¬(φ U ψ) = ¬φ R ¬ψ          ¬(φ R ψ) = ¬φ U ¬ψ
+//     ¬(φ W ψ) = ¬ψ U (¬φ ∧ ¬ψ)
+//
+// X is its own dual, as LTL is only about infinite paths, on which every state has a next one.
+use super::ltl_formula_macros as f;
+use super::{LTLFormula, LTLVariable};
+
+impl LTLFormula {
+    /// The until form of this formula, which only uses ⊤, variables, ¬, ∧, X, and U.
+    /// Double negations introduced by the rewrites are removed.
+    pub fn to_until_form(&self) -> Box<LTLFormula> {
+        until_form(self)
+    }
+    /// The negation normal form of this formula, in which negations only occur
+    /// directly in front of variables, and no implications are left.
+    pub fn to_nnf(&self) -> Box<LTLFormula> {
+        nnf(self, false)
+    }
+}
+
+fn not(formula: Box<LTLFormula>) -> Box<LTLFormula> {
+    if let LTLFormula::Neg(inner) = *formula {
+        inner
+    } else {
+        f::neg!(formula)
+    }
+}
+
+fn or(lhs: Box<LTLFormula>, rhs: Box<LTLFormula>) -> Box<LTLFormula> {
+    not(f::and!(not(lhs), not(rhs)))
+}
+
+fn implies(lhs: Box<LTLFormula>, rhs: Box<LTLFormula>) -> Box<LTLFormula> {
+    not(f::and!(lhs, not(rhs)))
+}
+
+fn until_form(formula: &LTLFormula) -> Box<LTLFormula> {
+    use LTLFormula as F;
+    match formula {
+        F::Top => f::top!(),
+        F::Bot => not(f::top!()),
+        F::Atomic(var) => f::atom!(var.inner.clone()),
+        F::Neg(inner) => not(until_form(inner)),
+        F::And(lhs, rhs) => f::and!(until_form(lhs), until_form(rhs)),
+        F::Or(lhs, rhs) => or(until_form(lhs), until_form(rhs)),
+        F::ImpliesR(lhs, rhs) => implies(until_form(lhs), until_form(rhs)),
+        F::ImpliesL(lhs, rhs) => implies(until_form(rhs), until_form(lhs)),
+        F::BiImplies(lhs, rhs) => {
+            let (lhs, rhs) = (until_form(lhs), until_form(rhs));
+            f::and!(implies(lhs.clone(), rhs.clone()), implies(rhs, lhs))
+        }
+        F::X(inner) => f::x!(until_form(inner)),
+        F::F(inner) => f::u!(f::top!(), until_form(inner)),
+        F::G(inner) => not(f::u!(f::top!(), not(until_form(inner)))),
+        F::U(lhs, rhs) => f::u!(until_form(lhs), until_form(rhs)),
+        F::R(lhs, rhs) => not(f::u!(not(until_form(lhs)), not(until_form(rhs)))),
+        F::W(lhs, rhs) => {
+            let (lhs, rhs) = (until_form(lhs), until_form(rhs));
+            let neither = f::and!(not(lhs), not(rhs.clone()));
+            not(f::u!(not(rhs), neither))
+        }
+    }
+}
+
+fn nnf(formula: &LTLFormula, negated: bool) -> Box<LTLFormula> {
+    use LTLFormula as F;
+    match (formula, negated) {
+        (F::Top, false) | (F::Bot, true) => f::top!(),
+        (F::Top, true) | (F::Bot, false) => f::bot!(),
+        (F::Atomic(var), false) => f::atom!(var.inner.clone()),
+        (F::Atomic(var), true) => f::neg!(f::atom!(var.inner.clone())),
+        (F::Neg(inner), _) => nnf(inner, !negated),
+        (F::And(lhs, rhs), false) => f::and!(nnf(lhs, false), nnf(rhs, false)),
+        (F::And(lhs, rhs), true) => f::or!(nnf(lhs, true), nnf(rhs, true)),
+        (F::Or(lhs, rhs), false) => f::or!(nnf(lhs, false), nnf(rhs, false)),
+        (F::Or(lhs, rhs), true) => f::and!(nnf(lhs, true), nnf(rhs, true)),
+        (F::ImpliesR(lhs, rhs), false) => f::or!(nnf(lhs, true), nnf(rhs, false)),
+        (F::ImpliesR(lhs, rhs), true) => f::and!(nnf(lhs, false), nnf(rhs, true)),
+        (F::ImpliesL(lhs, rhs), false) => f::or!(nnf(lhs, false), nnf(rhs, true)),
+        (F::ImpliesL(lhs, rhs), true) => f::and!(nnf(lhs, true), nnf(rhs, false)),
+        (F::BiImplies(lhs, rhs), false) => f::and!(
+            f::or!(nnf(lhs, true), nnf(rhs, false)),
+            f::or!(nnf(rhs, true), nnf(lhs, false))
+        ),
+        (F::BiImplies(lhs, rhs), true) => f::or!(
+            f::and!(nnf(lhs, false), nnf(rhs, true)),
+            f::and!(nnf(rhs, false), nnf(lhs, true))
+        ),
+        (F::X(inner), _) => f::x!(nnf(inner, negated)),
+        (F::F(inner), false) | (F::G(inner), true) => f::f!(nnf(inner, negated)),
+        (F::G(inner), false) | (F::F(inner), true) => f::g!(nnf(inner, negated)),
+        (F::U(lhs, rhs), false) | (F::R(lhs, rhs), true) => {
+            f::u!(nnf(lhs, negated), nnf(rhs, negated))
+        }
+        (F::R(lhs, rhs), false) | (F::U(lhs, rhs), true) => {
+            f::r!(nnf(lhs, negated), nnf(rhs, negated))
+        }
+        (F::W(lhs, rhs), false) => f::w!(nnf(lhs, false), nnf(rhs, false)),
+        (F::W(lhs, rhs), true) => f::u!(nnf(rhs, true), f::and!(nnf(lhs, true), nnf(rhs, true))),
+    }
+}
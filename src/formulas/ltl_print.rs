@@ -0,0 +1,108 @@
+// Printing LTL formulas with as few parentheses as the parser allows.
+//
+// A binary operator only needs parentheses around an argument that binds looser than itself,
+// or, on the right, around one that binds equally loose, as the parser associates to the left.
+// The unary operators take their argument with parse_primary, so they need parentheses
+// around every argument that is not a primary formula itself.
+use std::fmt;
+
+use super::{LTLFormula, PrintStyle};
+
+const PRIMARY: u8 = 5;
+
+fn precedence(formula: &LTLFormula) -> u8 {
+    use LTLFormula as F;
+    match formula {
+        F::ImpliesR(..) | F::ImpliesL(..) | F::BiImplies(..) => 1,
+        F::Or(..) => 2,
+        F::And(..) => 3,
+        F::U(..) | F::W(..) | F::R(..) => 4,
+        _ => PRIMARY,
+    }
+}
+
+impl LTLFormula {
+    /// Print this formula in the given style.
+    /// With `PrintStyle::Ascii`, `parse_ltl` parses the output back into this same formula,
+    /// as long as all variables are valid variable names for the parser.
+    pub fn print(&self, style: PrintStyle) -> String {
+        let mut out = String::new();
+        // Writing to a String never fails
+        let _ = write_formula(&mut out, self, style);
+        out
+    }
+}
+
+impl fmt::Display for LTLFormula {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_formula(f, self, PrintStyle::Unicode)
+    }
+}
+
+fn write_formula(
+    out: &mut impl fmt::Write,
+    formula: &LTLFormula,
+    style: PrintStyle,
+) -> fmt::Result {
+    use LTLFormula as F;
+    let own = precedence(formula);
+    match formula {
+        F::Top => write!(out, "{}", style.top()),
+        F::Bot => write!(out, "{}", style.bot()),
+        F::Atomic(var) => write!(out, "{}", style.variable(&var.inner)),
+        F::Neg(inner) => {
+            write!(out, "{}", style.not())?;
+            write_argument(out, inner, PRIMARY, style)
+        }
+        F::And(lhs, rhs) => write_binary(out, own, lhs, style.and(), rhs, style),
+        F::Or(lhs, rhs) => write_binary(out, own, lhs, style.or(), rhs, style),
+        F::ImpliesR(lhs, rhs) => write_binary(out, own, lhs, style.implies_r(), rhs, style),
+        F::ImpliesL(lhs, rhs) => write_binary(out, own, lhs, style.implies_l(), rhs, style),
+        F::BiImplies(lhs, rhs) => write_binary(out, own, lhs, style.bi_implies(), rhs, style),
+        F::X(inner) => write_prefix(out, "X", inner, style),
+        F::F(inner) => write_prefix(out, "F", inner, style),
+        F::G(inner) => write_prefix(out, "G", inner, style),
+        F::U(lhs, rhs) => write_binary(out, own, lhs, &style.infix("U"), rhs, style),
+        F::W(lhs, rhs) => write_binary(out, own, lhs, &style.infix("W"), rhs, style),
+        F::R(lhs, rhs) => write_binary(out, own, lhs, &style.infix("R"), rhs, style),
+    }
+}
+
+/// Write an argument, in parentheses if it binds looser than `min_precedence`.
+fn write_argument(
+    out: &mut impl fmt::Write,
+    formula: &LTLFormula,
+    min_precedence: u8,
+    style: PrintStyle,
+) -> fmt::Result {
+    if precedence(formula) < min_precedence {
+        write!(out, "(")?;
+        write_formula(out, formula, style)?;
+        write!(out, ")")
+    } else {
+        write_formula(out, formula, style)
+    }
+}
+
+fn write_binary(
+    out: &mut impl fmt::Write,
+    own_precedence: u8,
+    lhs: &LTLFormula,
+    operator: &str,
+    rhs: &LTLFormula,
+    style: PrintStyle,
+) -> fmt::Result {
+    write_argument(out, lhs, own_precedence, style)?;
+    write!(out, "{operator}")?;
+    write_argument(out, rhs, own_precedence + 1, style)
+}
+
+fn write_prefix(
+    out: &mut impl fmt::Write,
+    keyword: &str,
+    inner: &LTLFormula,
+    style: PrintStyle,
+) -> fmt::Result {
+    write!(out, "{}", style.prefix(keyword))?;
+    write_argument(out, inner, PRIMARY, style)
+}
@@ -1,6 +1,3 @@
-// Allowing unused while LTLChecker isn't implemented.
-#![allow(unused)]
-
 // This file is basically a big switchboard matching over the formula types multiple times.
 // ... in all cases recursively defining some simple formula.
 // It might be possible to define these generically and make the file a lot shorter,
@@ -11,10 +8,11 @@ use pyo3::prelude::*;
 use pyo3::types::PyTuple;
 
 use std::fmt;
+use std::str::FromStr;
 
 use crate::formulas::LTLVariable;
 
-use super::{parse_ltl, LTLFormula};
+use super::{parse_ltl, LTLFormula, PrintStyle};
 
 /// The python view into the LTLFormula.
 /// This class is frozen. Objects, once created, cannot be modified.
@@ -199,4 +197,33 @@ impl PyLTLFormula {
         })?;
         Ok(Self::from_rust(&res))
     }
+    fn to_nnf(&self) -> PyResult<Self> {
+        let rsformula = self
+            .to_rust()
+            .ok_or(PyValueError::new_err("formula is not a valid LTL formula"))?;
+        Ok(Self::from_rust(&rsformula.to_nnf()))
+    }
+    fn to_until_form(&self) -> PyResult<Self> {
+        let rsformula = self
+            .to_rust()
+            .ok_or(PyValueError::new_err("formula is not a valid LTL formula"))?;
+        Ok(Self::from_rust(&rsformula.to_until_form()))
+    }
+    fn simplify(&self) -> PyResult<(Self, Vec<String>)> {
+        let rsformula = self
+            .to_rust()
+            .ok_or(PyValueError::new_err("formula is not a valid LTL formula"))?;
+        let (simplified, applied) = rsformula.simplify();
+        let applied = applied.iter().map(|rule| rule.to_string()).collect();
+        Ok((Self::from_rust(&simplified), applied))
+    }
+    #[pyo3(signature = (style = "unicode"))]
+    fn pretty(&self, style: &str) -> PyResult<String> {
+        let style =
+            PrintStyle::from_str(style).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let rsformula = self
+            .to_rust()
+            .ok_or(PyValueError::new_err("formula is not a valid LTL formula"))?;
+        Ok(rsformula.print(style))
+    }
 }
@@ -0,0 +1,168 @@
+// Simplification of LTL formulas.
+//
+// Formulas are simplified bottom-up: first the children, then the formula itself,
+// for as long as one of the rules applies to it. Only rules that make a formula smaller are used,
+// so this always terminates.
+//
+// All rules hold on every infinite path, so a simplified formula
+// holds in exactly the same states as the original one.
+use std::fmt;
+
+use super::ltl_formula_macros as f;
+use super::LTLFormula;
+
+/// A rule that was used to simplify a LTL formula.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LTLSimplification {
+    /// ¬¬φ ≡ φ
+    DoubleNegation,
+    /// Removing ⊤ and ⊥ from connectives, like φ ∧ ⊤ ≡ φ, φ ∨ ⊤ ≡ ⊤, and ⊥ → φ ≡ ⊤
+    Absorption,
+    /// φ ∧ φ ≡ φ, φ ∨ φ ≡ φ, and φ U φ ≡ φ
+    Idempotence,
+    /// F F φ ≡ F φ
+    NestedF,
+    /// G G φ ≡ G φ
+    NestedG,
+    /// F G F φ ≡ G F φ
+    InfinitelyOften,
+    /// G F G φ ≡ F G φ
+    EventuallyAlways,
+    /// G φ ∧ G ψ ≡ G (φ ∧ ψ)
+    MergeG,
+    /// F φ ∨ F ψ ≡ F (φ ∨ ψ)
+    MergeF,
+}
+
+impl fmt::Display for LTLSimplification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use LTLSimplification as S;
+        let rule = match self {
+            S::DoubleNegation => "¬¬φ ≡ φ",
+            S::Absorption => "⊤/⊥ absorption",
+            S::Idempotence => "idempotence",
+            S::NestedF => "F F φ ≡ F φ",
+            S::NestedG => "G G φ ≡ G φ",
+            S::InfinitelyOften => "F G F φ ≡ G F φ",
+            S::EventuallyAlways => "G F G φ ≡ F G φ",
+            S::MergeG => "G φ ∧ G ψ ≡ G (φ ∧ ψ)",
+            S::MergeF => "F φ ∨ F ψ ≡ F (φ ∨ ψ)",
+        };
+        write!(f, "{rule}")
+    }
+}
+
+impl LTLFormula {
+    /// Simplify this formula with propositional laws and temporal identities.
+    /// Returns the simplified formula, and the rules that were applied, in order.
+    /// A rule is listed once for every time it was applied.
+    pub fn simplify(&self) -> (Box<LTLFormula>, Vec<LTLSimplification>) {
+        let mut applied = Vec::new();
+        let simplified = simplify(self, &mut applied);
+        (simplified, applied)
+    }
+}
+
+fn simplify(formula: &LTLFormula, applied: &mut Vec<LTLSimplification>) -> Box<LTLFormula> {
+    let formula = map_children(formula, |child| simplify(child, applied));
+    match rewrite(&formula) {
+        Some((rewritten, rule)) => {
+            applied.push(rule);
+            simplify(&rewritten, applied)
+        }
+        None => formula,
+    }
+}
+
+fn map_children(
+    formula: &LTLFormula,
+    mut f: impl FnMut(&LTLFormula) -> Box<LTLFormula>,
+) -> Box<LTLFormula> {
+    use LTLFormula as F;
+    Box::new(match formula {
+        F::Top => F::Top,
+        F::Bot => F::Bot,
+        F::Atomic(var) => F::Atomic(var.clone()),
+        F::Neg(inner) => F::Neg(f(inner)),
+        F::And(lhs, rhs) => F::And(f(lhs), f(rhs)),
+        F::Or(lhs, rhs) => F::Or(f(lhs), f(rhs)),
+        F::ImpliesR(lhs, rhs) => F::ImpliesR(f(lhs), f(rhs)),
+        F::ImpliesL(lhs, rhs) => F::ImpliesL(f(lhs), f(rhs)),
+        F::BiImplies(lhs, rhs) => F::BiImplies(f(lhs), f(rhs)),
+        F::X(inner) => F::X(f(inner)),
+        F::F(inner) => F::F(f(inner)),
+        F::G(inner) => F::G(f(inner)),
+        F::U(lhs, rhs) => F::U(f(lhs), f(rhs)),
+        F::W(lhs, rhs) => F::W(f(lhs), f(rhs)),
+        F::R(lhs, rhs) => F::R(f(lhs), f(rhs)),
+    })
+}
+
+/// A single rewrite at the top of the formula, if any rule applies to it.
+fn rewrite(formula: &LTLFormula) -> Option<(Box<LTLFormula>, LTLSimplification)> {
+    use LTLFormula as F;
+    use LTLSimplification as S;
+    let rewritten = match formula {
+        F::Neg(inner) => match inner.as_ref() {
+            F::Neg(inner) => (inner.clone(), S::DoubleNegation),
+            F::Top => (f::bot!(), S::Absorption),
+            F::Bot => (f::top!(), S::Absorption),
+            _ => return None,
+        },
+        F::And(lhs, rhs) => match (lhs.as_ref(), rhs.as_ref()) {
+            (F::Bot, _) | (_, F::Bot) => (f::bot!(), S::Absorption),
+            (F::Top, _) => (rhs.clone(), S::Absorption),
+            (_, F::Top) => (lhs.clone(), S::Absorption),
+            _ if lhs == rhs => (lhs.clone(), S::Idempotence),
+            (F::G(lhs), F::G(rhs)) => (f::g!(f::and!(lhs.clone(), rhs.clone())), S::MergeG),
+            _ => return None,
+        },
+        F::Or(lhs, rhs) => match (lhs.as_ref(), rhs.as_ref()) {
+            (F::Top, _) | (_, F::Top) => (f::top!(), S::Absorption),
+            (F::Bot, _) => (rhs.clone(), S::Absorption),
+            (_, F::Bot) => (lhs.clone(), S::Absorption),
+            _ if lhs == rhs => (lhs.clone(), S::Idempotence),
+            (F::F(lhs), F::F(rhs)) => (f::f!(f::or!(lhs.clone(), rhs.clone())), S::MergeF),
+            _ => return None,
+        },
+        F::ImpliesR(lhs, rhs) => implication(lhs, rhs)?,
+        F::ImpliesL(lhs, rhs) => implication(rhs, lhs)?,
+        F::BiImplies(lhs, rhs) => match (lhs.as_ref(), rhs.as_ref()) {
+            (F::Top, _) => (rhs.clone(), S::Absorption),
+            (_, F::Top) => (lhs.clone(), S::Absorption),
+            (F::Bot, _) => (f::neg!(rhs.clone()), S::Absorption),
+            (_, F::Bot) => (f::neg!(lhs.clone()), S::Absorption),
+            _ => return None,
+        },
+        F::U(lhs, rhs) if lhs == rhs => (lhs.clone(), S::Idempotence),
+        F::F(inner) => match inner.as_ref() {
+            F::F(_) => (inner.clone(), S::NestedF),
+            F::G(inner) if matches!(inner.as_ref(), F::F(_)) => {
+                (f::g!(inner.clone()), S::InfinitelyOften)
+            }
+            _ => return None,
+        },
+        F::G(inner) => match inner.as_ref() {
+            F::G(_) => (inner.clone(), S::NestedG),
+            F::F(inner) if matches!(inner.as_ref(), F::G(_)) => {
+                (f::f!(inner.clone()), S::EventuallyAlways)
+            }
+            _ => return None,
+        },
+        _ => return None,
+    };
+    Some(rewritten)
+}
+
+fn implication(lhs: &LTLFormula, rhs: &LTLFormula) -> Option<(Box<LTLFormula>, LTLSimplification)> {
+    use LTLFormula as F;
+    match (lhs, rhs) {
+        (F::Bot, _) | (_, F::Top) => Some((f::top!(), LTLSimplification::Absorption)),
+        (F::Top, _) => Some((Box::new(rhs.clone()), LTLSimplification::Absorption)),
+        (_, F::Bot) => Some((
+            f::neg!(Box::new(lhs.clone())),
+            LTLSimplification::Absorption,
+        )),
+        _ => None,
+    }
+}
@@ -1,21 +1,20 @@
-// Allowing unused while LTLChecker isn't implemented.
-#![allow(unused)]
-
 use super::MLVariable;
-use std::collections::HashMap;
 
 #[derive(Debug, Hash, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct LTLVariable {
     pub inner: String,
 }
 impl LTLVariable {
-    pub(crate) fn new(inner: String) -> Self {
+    pub fn new(inner: String) -> Self {
         Self { inner }
     }
 }
-impl MLVariable for LTLFormula {}
+impl MLVariable for LTLVariable {}
 
 #[derive(Debug, Hash, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LTLFormula {
     Top,
     Bot,
@@ -35,7 +34,7 @@ pub enum LTLFormula {
 }
 
 impl LTLFormula {
-    pub(crate) fn for_each_child(&self, mut f: impl FnMut(&LTLFormula)) {
+    pub fn for_each_child(&self, mut f: impl FnMut(&LTLFormula)) {
         use LTLFormula as F;
         match self {
             F::X(x) | F::F(x) | F::G(x) | F::Neg(x) => f(x),
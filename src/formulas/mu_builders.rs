@@ -0,0 +1,66 @@
+//! Public functions to build mu-calculus formulas in code, without going through the parser.
+//! Every function returns a boxed formula, the same as what the parser returns.
+//! As `box` is a reserved word in rust, the box modality is built with `square`.
+//!
+//! ```
+//! use minictl::formulas::mu_builders::*;
+//! // mu Z. p or <>Z, which is EF p
+//! let formula = mu("Z", or(atom("p"), diamond(var("Z"))));
+//! ```
+use super::{MuFormula, MuVariable};
+
+pub fn top() -> Box<MuFormula> {
+    Box::new(MuFormula::Top)
+}
+
+pub fn bot() -> Box<MuFormula> {
+    Box::new(MuFormula::Bot)
+}
+
+pub fn atom(name: impl Into<String>) -> Box<MuFormula> {
+    Box::new(MuFormula::Atomic(MuVariable::new(name.into())))
+}
+
+pub fn var(name: impl Into<String>) -> Box<MuFormula> {
+    Box::new(MuFormula::Var(name.into()))
+}
+
+pub fn neg(inner: Box<MuFormula>) -> Box<MuFormula> {
+    Box::new(MuFormula::Neg(inner))
+}
+
+pub fn and(lhs: Box<MuFormula>, rhs: Box<MuFormula>) -> Box<MuFormula> {
+    Box::new(MuFormula::And(lhs, rhs))
+}
+
+pub fn or(lhs: Box<MuFormula>, rhs: Box<MuFormula>) -> Box<MuFormula> {
+    Box::new(MuFormula::Or(lhs, rhs))
+}
+
+pub fn implies_r(lhs: Box<MuFormula>, rhs: Box<MuFormula>) -> Box<MuFormula> {
+    Box::new(MuFormula::ImpliesR(lhs, rhs))
+}
+
+pub fn implies_l(lhs: Box<MuFormula>, rhs: Box<MuFormula>) -> Box<MuFormula> {
+    Box::new(MuFormula::ImpliesL(lhs, rhs))
+}
+
+pub fn implies_bi(lhs: Box<MuFormula>, rhs: Box<MuFormula>) -> Box<MuFormula> {
+    Box::new(MuFormula::BiImplies(lhs, rhs))
+}
+
+pub fn diamond(inner: Box<MuFormula>) -> Box<MuFormula> {
+    Box::new(MuFormula::Diamond(inner))
+}
+
+pub fn square(inner: Box<MuFormula>) -> Box<MuFormula> {
+    Box::new(MuFormula::Box(inner))
+}
+
+pub fn mu(name: impl Into<String>, inner: Box<MuFormula>) -> Box<MuFormula> {
+    Box::new(MuFormula::Mu(name.into(), inner))
+}
+
+pub fn nu(name: impl Into<String>, inner: Box<MuFormula>) -> Box<MuFormula> {
+    Box::new(MuFormula::Nu(name.into(), inner))
+}
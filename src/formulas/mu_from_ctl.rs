@@ -0,0 +1,86 @@
+// The translation from CTL into the mu-calculus.
+//
+// Every CTL operator is a fixpoint of a simple equation over its next-step modalities,
+// and this is exactly what the loops in the CTL checker compute:
+//     EX φ     = <>φ
+//     AX φ     = []φ
+//     EF φ     = μZ. φ ∨ <>Z
+//     AF φ     = μZ. φ ∨ ([]Z ∧ <>⊤)
+//     EG φ     = νZ. φ ∧ (<>Z ∨ []⊥)
+//     AG φ     = νZ. φ ∧ []Z
+//     E[φ U ψ] = μZ. ψ ∨ (φ ∧ <>Z)
+//     A[φ U ψ] = μZ. ψ ∨ (φ ∧ []Z ∧ <>⊤)
+//     E[φ R ψ] = νZ. ψ ∧ (φ ∨ <>Z ∨ []⊥)
+//     A[φ R ψ] = νZ. ψ ∧ (φ ∨ []Z)
+//
+// The `<>⊤` and `[]⊥` conjuncts deal with sink states in the same way as the CTL checker:
+// a path that ends in a sink counts as a full path, so AF φ and A[φ U ψ] cannot be
+// satisfied vacuously by a sink (where []Z is always true), and EG φ may stop at one.
+use super::mu_formula_macros as f;
+use super::{CTLFormula, MuFormula, MuVariable};
+
+impl MuFormula {
+    /// Translate a CTL formula into an equivalent mu-calculus formula.
+    /// The fixpoint variables are named `Z0`, `Z1`, ... after how deeply they are nested.
+    pub fn from_ctl(formula: &CTLFormula) -> Box<MuFormula> {
+        from_ctl(formula, 0)
+    }
+}
+
+fn from_ctl(formula: &CTLFormula, depth: usize) -> Box<MuFormula> {
+    use CTLFormula as C;
+    let z = || format!("Z{depth}");
+    let rec = |inner: &CTLFormula| from_ctl(inner, depth + 1);
+    let has_next = || f::diamond!(f::top!());
+    match formula {
+        C::Top => f::top!(),
+        C::Bot => f::bot!(),
+        C::Atomic(var) => f::atom!(var.inner.clone()),
+        C::Neg(inner) => f::neg!(from_ctl(inner, depth)),
+        C::And(lhs, rhs) => f::and!(from_ctl(lhs, depth), from_ctl(rhs, depth)),
+        C::Or(lhs, rhs) => f::or!(from_ctl(lhs, depth), from_ctl(rhs, depth)),
+        C::ImpliesR(lhs, rhs) => f::impies_r!(from_ctl(lhs, depth), from_ctl(rhs, depth)),
+        C::ImpliesL(lhs, rhs) => f::impies_l!(from_ctl(lhs, depth), from_ctl(rhs, depth)),
+        C::BiImplies(lhs, rhs) => f::implies_bi!(from_ctl(lhs, depth), from_ctl(rhs, depth)),
+        C::EX(inner) => f::diamond!(from_ctl(inner, depth)),
+        C::AX(inner) => f::square!(from_ctl(inner, depth)),
+        C::EF(inner) => f::mu!(z(), f::or!(rec(inner), f::diamond!(f::var!(z())))),
+        C::AF(inner) => f::mu!(
+            z(),
+            f::or!(rec(inner), f::and!(f::square!(f::var!(z())), has_next()))
+        ),
+        C::EG(inner) => f::nu!(
+            z(),
+            f::and!(
+                rec(inner),
+                f::or!(f::diamond!(f::var!(z())), f::square!(f::bot!()))
+            )
+        ),
+        C::AG(inner) => f::nu!(z(), f::and!(rec(inner), f::square!(f::var!(z())))),
+        C::EU(lhs, rhs) => f::mu!(
+            z(),
+            f::or!(rec(rhs), f::and!(rec(lhs), f::diamond!(f::var!(z()))))
+        ),
+        C::AU(lhs, rhs) => f::mu!(
+            z(),
+            f::or!(
+                rec(rhs),
+                f::and!(rec(lhs), f::and!(f::square!(f::var!(z())), has_next()))
+            )
+        ),
+        C::ER(lhs, rhs) => f::nu!(
+            z(),
+            f::and!(
+                rec(rhs),
+                f::or!(
+                    rec(lhs),
+                    f::or!(f::diamond!(f::var!(z())), f::square!(f::bot!()))
+                )
+            )
+        ),
+        C::AR(lhs, rhs) => f::nu!(
+            z(),
+            f::and!(rec(rhs), f::or!(rec(lhs), f::square!(f::var!(z()))))
+        ),
+    }
+}
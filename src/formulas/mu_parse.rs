@@ -0,0 +1,282 @@
+use std::iter::Peekable;
+use std::str::Chars;
+use thiserror::Error;
+
+use super::mu_types::{MuFormula, MuVariable};
+
+#[derive(Debug, Clone, PartialEq)]
+enum MuToken {
+    Top,
+    Bot,
+    Variable(String),
+    FixpointVariable(String),
+    Not,
+    And,
+    Or,
+    ImpliesR,
+    ImpliesL,
+    BiImplies,
+    Diamond,
+    Box,
+    Mu,
+    Nu,
+    Dot,
+    LParen,
+    RParen,
+}
+
+#[derive(Debug, PartialEq, Error)]
+pub enum MuParseError {
+    #[error("Unexpected character: {0}")]
+    UnexpectedCharacter(char),
+    #[error("Unexpected Token: {0}")]
+    UnexpectedToken(String),
+    #[error("Unexpected end of input: {0}")]
+    UnexpectedEndOfInput(String),
+}
+
+struct MuLexer<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl<'a> MuLexer<'a> {
+    fn new(input: &'a str) -> Self {
+        Self {
+            chars: input.chars().peekable(),
+        }
+    }
+
+    fn expect_char(&mut self, expected: char, err_str: &str) -> Result<(), MuParseError> {
+        match self.chars.next() {
+            Some(a) if a == expected => Ok(()),
+            Some(other) => Err(MuParseError::UnexpectedCharacter(other)),
+            None => {
+                let err_message = format!("{}, got end of input", err_str);
+                Err(MuParseError::UnexpectedEndOfInput(err_message))
+            }
+        }
+    }
+
+    fn next_token(&mut self) -> Option<Result<MuToken, MuParseError>> {
+        while let Some(&ch) = self.chars.peek() {
+            match ch {
+                ch if ch.is_whitespace() => {
+                    self.chars.next();
+                }
+                '(' => {
+                    self.chars.next();
+                    return Some(Ok(MuToken::LParen));
+                }
+                ')' => {
+                    self.chars.next();
+                    return Some(Ok(MuToken::RParen));
+                }
+                '.' => {
+                    self.chars.next();
+                    return Some(Ok(MuToken::Dot));
+                }
+                '[' => {
+                    self.chars.next();
+                    return Some(
+                        self.expect_char(']', "Expected ] after [")
+                            .map(|_| MuToken::Box),
+                    );
+                }
+                '!' => {
+                    self.chars.next();
+                    return Some(Ok(MuToken::Not));
+                }
+                '-' => {
+                    self.chars.next();
+                    return Some(
+                        self.expect_char('>', "Expected > after - that didn't start with <")
+                            .map(|_| MuToken::ImpliesR),
+                    );
+                }
+                '<' => {
+                    self.chars.next();
+                    // `<>` is the diamond, while `<-` and `<->` are implications.
+                    if self.chars.peek() == Some(&'>') {
+                        self.chars.next();
+                        return Some(Ok(MuToken::Diamond));
+                    }
+                    if let Err(error) = self.expect_char('-', "Expected - or > after <") {
+                        return Some(Err(error));
+                    }
+                    if self.chars.peek() == Some(&'>') {
+                        self.chars.next();
+                        return Some(Ok(MuToken::BiImplies));
+                    }
+                    return Some(Ok(MuToken::ImpliesL));
+                }
+                ch if ch.is_lowercase() => return self.consume_lowercase_variable_or_keyword(),
+                ch if ch.is_uppercase() => return self.consume_keyword(),
+                _ => return Some(Err(MuParseError::UnexpectedCharacter(ch))),
+            }
+        }
+        None
+    }
+
+    fn consume_lowercase_variable_or_keyword(&mut self) -> Option<Result<MuToken, MuParseError>> {
+        let mut name = String::new();
+        while let Some(&ch) = self.chars.peek() {
+            if ch.is_lowercase() || ch.is_numeric() || matches!(ch, '=' | '_' | '.') {
+                name.push(ch);
+                self.chars.next();
+            } else {
+                break;
+            }
+        }
+        match name.as_str() {
+            "and" => Some(Ok(MuToken::And)),
+            "or" => Some(Ok(MuToken::Or)),
+            "mu" => Some(Ok(MuToken::Mu)),
+            "nu" => Some(Ok(MuToken::Nu)),
+            _ => Some(Ok(MuToken::Variable(name))),
+        }
+    }
+
+    // Any uppercase word that is not a keyword is the name of a fixpoint variable,
+    // so they are easy to tell apart from the propositions of the model.
+    fn consume_keyword(&mut self) -> Option<Result<MuToken, MuParseError>> {
+        use MuToken as T;
+        let mut name = String::new();
+        while let Some(&ch) = self.chars.peek() {
+            if ch.is_uppercase() || ch.is_numeric() || ch == '_' {
+                name.push(ch);
+                self.chars.next();
+            } else {
+                break;
+            }
+        }
+        match name.as_str() {
+            "TOP" => Some(Ok(T::Top)),
+            "BOT" => Some(Ok(T::Bot)),
+            _ => Some(Ok(T::FixpointVariable(name))),
+        }
+    }
+}
+
+impl Iterator for MuLexer<'_> {
+    type Item = Result<MuToken, MuParseError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_token()
+    }
+}
+
+struct MuParser<'a> {
+    tokens: Peekable<MuLexer<'a>>,
+}
+
+impl<'a> MuParser<'a> {
+    fn new(lexer: MuLexer<'a>) -> Self {
+        Self {
+            tokens: lexer.peekable(),
+        }
+    }
+    fn expect_token(&mut self, expected: MuToken, err_str: &str) -> Result<(), MuParseError> {
+        match self.tokens.next() {
+            Some(Ok(a)) if a == expected => Ok(()),
+            Some(other) => {
+                let err_message = format!("{}, got {:?}", err_str, other);
+                Err(MuParseError::UnexpectedToken(err_message))
+            }
+            None => {
+                let err_message = format!("{}, got end of input", err_str);
+                Err(MuParseError::UnexpectedEndOfInput(err_message))
+            }
+        }
+    }
+    fn parse(&mut self) -> Result<Box<MuFormula>, MuParseError> {
+        self.parse_expression(1)
+    }
+    fn parse_expression(&mut self, min_precedence: u8) -> Result<Box<MuFormula>, MuParseError> {
+        use MuFormula as F;
+        use MuToken as T;
+        let mut left = self.parse_primary()?;
+
+        while let Some(Ok(token)) = self.tokens.peek() {
+            let token_precedence = match token {
+                T::ImpliesR | T::ImpliesL | T::BiImplies => 1,
+                T::Or => 2,
+                T::And => 3,
+                _ => break,
+            };
+
+            if token_precedence < min_precedence {
+                break;
+            }
+
+            let token = self.tokens.next().unwrap()?;
+            let right = self.parse_expression(token_precedence + 1)?;
+
+            left = Box::new(match token {
+                T::ImpliesR => F::ImpliesR(left, right),
+                T::ImpliesL => F::ImpliesL(left, right),
+                T::BiImplies => F::BiImplies(left, right),
+                T::Or => F::Or(left, right),
+                T::And => F::And(left, right),
+                _ => unreachable!(),
+            });
+        }
+
+        Ok(left)
+    }
+    /// The `X.` after `mu` or `nu`.
+    fn parse_binder(&mut self) -> Result<String, MuParseError> {
+        let name = match self.tokens.next() {
+            Some(Ok(MuToken::FixpointVariable(name))) => name,
+            Some(Ok(other)) => {
+                let err_message = format!("Expected fixpoint variable, got {:?}", other);
+                return Err(MuParseError::UnexpectedToken(err_message));
+            }
+            Some(Err(error)) => return Err(error),
+            None => {
+                return Err(MuParseError::UnexpectedEndOfInput(
+                    "Expected fixpoint variable".to_owned(),
+                ))
+            }
+        };
+        self.expect_token(MuToken::Dot, "Expected . after fixpoint variable")?;
+        Ok(name)
+    }
+    fn parse_primary(&mut self) -> Result<Box<MuFormula>, MuParseError> {
+        use super::mu_formula_macros as f;
+        use MuToken as T;
+        match self.tokens.next() {
+            Some(Ok(T::Top)) => Ok(f::top!()),
+            Some(Ok(T::Bot)) => Ok(f::bot!()),
+            Some(Ok(T::Variable(var))) => Ok(f::atom!(var)),
+            Some(Ok(T::Not)) => Ok(f::neg!(self.parse_primary()?)),
+            Some(Ok(T::FixpointVariable(name))) => Ok(f::var!(name)),
+            Some(Ok(T::Diamond)) => Ok(f::diamond!(self.parse_primary()?)),
+            Some(Ok(T::Box)) => Ok(f::square!(self.parse_primary()?)),
+            // The body of a fixpoint extends as far to the right as possible.
+            Some(Ok(T::Mu)) => {
+                let name = self.parse_binder()?;
+                Ok(f::mu!(name, self.parse_expression(1)?))
+            }
+            Some(Ok(T::Nu)) => {
+                let name = self.parse_binder()?;
+                Ok(f::nu!(name, self.parse_expression(1)?))
+            }
+            Some(Ok(T::LParen)) => {
+                let expr = self.parse_expression(1)?;
+                self.expect_token(T::RParen, "Expected closing parentheses")?;
+                Ok(expr)
+            }
+            Some(Ok(other)) => Err(MuParseError::UnexpectedToken(format!("{:?}", other))),
+            Some(Err(error)) => Err(error),
+            None => Err(MuParseError::UnexpectedEndOfInput(
+                "Expected primary expression".to_owned(),
+            )),
+        }
+    }
+}
+
+#[inline(always)]
+pub fn parse_mu(input: &str) -> Result<Box<MuFormula>, MuParseError> {
+    let lexer = MuLexer::new(input);
+    let mut parser = MuParser::new(lexer);
+    parser.parse()
+}
@@ -0,0 +1,229 @@
+// Like the other formula files, this is a big switchboard matching over the formula types.
+// The only new thing is the fixpoint variables, which are represented in python as formulas
+// with an uppercase name and no arguments, like `MuFormula("X")`. The binders then take
+// such a variable as their first argument, like `MuFormula("Mu", MuFormula("X"), body)`.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyTuple;
+
+use std::fmt;
+
+use crate::formulas::ctl_python::PyCTLFormula;
+use crate::formulas::MuVariable;
+
+use super::{parse_mu, MuFormula};
+
+fn is_fixpoint_variable(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_uppercase())
+        && name
+            .chars()
+            .all(|c| c.is_uppercase() || c.is_numeric() || c == '_')
+}
+
+fn is_proposition(name: &str) -> bool {
+    name.chars()
+        .all(|c| c.is_alphanumeric() && !c.is_uppercase())
+}
+
+/// The Python view of a `MuFormula`: the name of its operator and its arguments.
+/// Fixpoint variables are formulas with an uppercase name and no arguments, and the binders
+/// `Mu` and `Nu` take such a variable as their first argument.
+/// Objects are frozen once created.
+///
+/// Formulas can be built from their parts, like
+/// `MuFormula("Mu", MuFormula("X"), MuFormula("Or", MuFormula("p"), MuFormula("Diamond", MuFormula("X"))))`,
+/// or parsed, like `MuFormula.parse("mu X. p or <>X")`.
+/// They can be printed, compared, and hashed.
+#[pyclass(
+    module = "minictl",
+    name = "MuFormula",
+    get_all,
+    frozen,
+    eq,
+    hash,
+    str,
+    from_py_object
+)]
+#[derive(Debug, Hash, PartialEq, Eq, Clone)]
+pub struct PyMuFormula {
+    pub name: String,
+    pub arguments: Vec<PyMuFormula>,
+}
+
+impl PyMuFormula {
+    #[inline(always)]
+    fn new_bare(name: &str, arguments: Vec<PyMuFormula>) -> Self {
+        Self {
+            name: name.to_owned(),
+            arguments,
+        }
+    }
+    fn new_with_pyargs(
+        name: String,
+        py_arguments: &Bound<'_, PyTuple>,
+        nr_args: usize,
+    ) -> PyResult<Self> {
+        let nr_found = py_arguments.len();
+        let mut arguments = Vec::with_capacity(nr_found);
+        if nr_found == nr_args {
+            for item in py_arguments.iter() {
+                arguments.push(item.extract::<PyMuFormula>()?);
+            }
+            Ok(Self { name, arguments })
+        } else {
+            Err(PyValueError::new_err(
+                "Expected {nr_args} arguments for {name}, found {nr_found}",
+            ))
+        }
+    }
+    pub(crate) fn from_rust(formula: &MuFormula) -> Self {
+        use MuFormula as F;
+        use PyMuFormula as PF;
+        match formula {
+            F::Top => Self::new_bare("TOP", Vec::default()),
+            F::Bot => Self::new_bare("BOT", Vec::default()),
+            F::Neg(f) => Self::new_bare("Neg", vec![PF::from_rust(f)]),
+            F::Diamond(f) => Self::new_bare("Diamond", vec![PF::from_rust(f)]),
+            F::Box(f) => Self::new_bare("Box", vec![PF::from_rust(f)]),
+            F::And(f1, f2) => Self::new_bare("And", vec![PF::from_rust(f1), PF::from_rust(f2)]),
+            F::Or(f1, f2) => Self::new_bare("Or", vec![PF::from_rust(f1), PF::from_rust(f2)]),
+            F::ImpliesR(f1, f2) => {
+                Self::new_bare("ImpliesR", vec![PF::from_rust(f1), PF::from_rust(f2)])
+            }
+            F::ImpliesL(f1, f2) => {
+                Self::new_bare("ImpliesL", vec![PF::from_rust(f1), PF::from_rust(f2)])
+            }
+            F::BiImplies(f1, f2) => {
+                Self::new_bare("BiImplies", vec![PF::from_rust(f1), PF::from_rust(f2)])
+            }
+            F::Mu(name, f) => {
+                Self::new_bare("Mu", vec![Self::new_bare(name, vec![]), PF::from_rust(f)])
+            }
+            F::Nu(name, f) => {
+                Self::new_bare("Nu", vec![Self::new_bare(name, vec![]), PF::from_rust(f)])
+            }
+            F::Var(name) => Self::new_bare(name, Vec::default()),
+            F::Atomic(variable) => Self {
+                name: variable.inner.clone(),
+                arguments: Vec::default(),
+            },
+        }
+    }
+    #[inline(always)]
+    fn arg_to_rust(&self, index: usize) -> Option<Box<MuFormula>> {
+        self.arguments.get(index)?.to_rust()
+    }
+    #[inline(always)]
+    fn binder_name(&self) -> Option<String> {
+        let binder = self.arguments.first()?;
+        match is_fixpoint_variable(&binder.name) && binder.arguments.is_empty() {
+            true => Some(binder.name.clone()),
+            false => None,
+        }
+    }
+
+    pub(crate) fn to_rust(&self) -> Option<Box<MuFormula>> {
+        use super::mu_formula_macros as f;
+        let ret = match self.name.as_str() {
+            "TOP" => f::top!(),
+            "BOT" => f::bot!(),
+            "Neg" => f::neg!(self.arg_to_rust(0)?),
+            "Diamond" => f::diamond!(self.arg_to_rust(0)?),
+            "Box" => f::square!(self.arg_to_rust(0)?),
+            "And" => f::and!(self.arg_to_rust(0)?, self.arg_to_rust(1)?),
+            "Or" => f::or!(self.arg_to_rust(0)?, self.arg_to_rust(1)?),
+            "ImpliesR" => f::impies_r!(self.arg_to_rust(0)?, self.arg_to_rust(1)?),
+            "ImpliesL" => f::impies_l!(self.arg_to_rust(0)?, self.arg_to_rust(1)?),
+            "BiImplies" => f::implies_bi!(self.arg_to_rust(0)?, self.arg_to_rust(1)?),
+            "Mu" => f::mu!(self.binder_name()?, self.arg_to_rust(1)?),
+            "Nu" => f::nu!(self.binder_name()?, self.arg_to_rust(1)?),
+            other if is_fixpoint_variable(other) => f::var!(other.to_string()),
+            other => f::atom!(other.to_string()),
+        };
+        Some(ret)
+    }
+}
+
+impl fmt::Display for PyMuFormula {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The constructor checks the number of arguments, so the fallback should not be
+        // needed, but printing a malformed formula should not panic either.
+        let get_argstr = |index| {
+            self.arguments
+                .get(index)
+                .map(|f| format!("{}", f))
+                .unwrap_or("ERR: missing".to_string())
+        };
+        match self.name.as_str() {
+            "TOP" => write!(f, "⊤"),
+            "BOT" => write!(f, "⊥"),
+            "Neg" => write!(f, "¬({})", get_argstr(0)),
+            "Diamond" => write!(f, "⟨⟩({})", get_argstr(0)),
+            "Box" => write!(f, "[]({})", get_argstr(0)),
+            "And" => write!(f, "({})∧({})", get_argstr(0), get_argstr(1)),
+            "Or" => write!(f, "({})∨({})", get_argstr(0), get_argstr(1)),
+            "ImpliesR" => write!(f, "({})→({})", get_argstr(0), get_argstr(1)),
+            "ImpliesL" => write!(f, "({})←({})", get_argstr(0), get_argstr(1)),
+            "BiImplies" => write!(f, "({})↔({})", get_argstr(0), get_argstr(1)),
+            "Mu" => write!(f, "μ{}.({})", get_argstr(0), get_argstr(1)),
+            "Nu" => write!(f, "ν{}.({})", get_argstr(0), get_argstr(1)),
+            _ => write!(f, "{}", self.name),
+        }
+    }
+}
+
+#[pymethods]
+impl PyMuFormula {
+    #[new]
+    #[pyo3(signature=(name, *py_args))]
+    fn new(name: String, py_args: &Bound<'_, PyTuple>) -> PyResult<Self> {
+        match name.as_str() {
+            "TOP" => Self::new_with_pyargs(name, py_args, 0),
+            "BOT" => Self::new_with_pyargs(name, py_args, 0),
+            "Neg" => Self::new_with_pyargs(name, py_args, 1),
+            "Diamond" => Self::new_with_pyargs(name, py_args, 1),
+            "Box" => Self::new_with_pyargs(name, py_args, 1),
+            "And" => Self::new_with_pyargs(name, py_args, 2),
+            "Or" => Self::new_with_pyargs(name, py_args, 2),
+            "ImpliesR" => Self::new_with_pyargs(name, py_args, 2),
+            "ImpliesL" => Self::new_with_pyargs(name, py_args, 2),
+            "BiImplies" => Self::new_with_pyargs(name, py_args, 2),
+            "Mu" | "Nu" => {
+                let ret = Self::new_with_pyargs(name, py_args, 2)?;
+                match ret.binder_name() {
+                    Some(_) => Ok(ret),
+                    None => Err(PyValueError::new_err(
+                        "The first argument of a fixpoint has to be a fixpoint variable",
+                    )),
+                }
+            }
+            _ if py_args.is_empty() => {
+                if is_proposition(&name) || is_fixpoint_variable(&name) {
+                    Self::new_with_pyargs(name, py_args, 0)
+                } else {
+                    Err(PyValueError::new_err(
+                        "{name} is not a valid formula name: not a lowercase proposition, or an uppercase fixpoint variable",
+                    ))
+                }
+            }
+            _ => Err(PyValueError::new_err(
+                "{name} is not a valid formula name, or, if variable, arguments are nonempty",
+            )),
+        }
+    }
+    #[staticmethod]
+    fn parse(formula: String) -> PyResult<Self> {
+        let res = parse_mu(&formula).map_err(|err| {
+            PyValueError::new_err(format!("Cannot parse {} into formula: {}", formula, err))
+        })?;
+        Ok(Self::from_rust(&res))
+    }
+    #[staticmethod]
+    fn from_ctl(formula: PyCTLFormula) -> PyResult<Self> {
+        let rsformula = formula.to_rust().ok_or(PyValueError::new_err(
+            "provided formula is not a valid CTL formula",
+        ))?;
+        Ok(Self::from_rust(&MuFormula::from_ctl(&rsformula)))
+    }
+}
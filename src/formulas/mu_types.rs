@@ -0,0 +1,165 @@
+use super::MLVariable;
+
+#[derive(Debug, Hash, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct MuVariable {
+    pub inner: String,
+}
+impl MuVariable {
+    pub fn new(inner: String) -> Self {
+        Self { inner }
+    }
+}
+impl MLVariable for MuVariable {}
+
+/// A formula of the modal mu-calculus.
+///
+/// `Atomic` is a proposition of the model, like in the other logics, while `Var` refers
+/// to the fixpoint variable bound by an enclosing `Mu` or `Nu` with the same name.
+/// `Diamond` holds if some successor satisfies the inner formula, and `Box` if all of them do.
+#[derive(Debug, Hash, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MuFormula {
+    Top,
+    Bot,
+    Atomic(MuVariable),
+    Var(String),
+    Neg(Box<MuFormula>),
+    And(Box<MuFormula>, Box<MuFormula>),
+    Or(Box<MuFormula>, Box<MuFormula>),
+    ImpliesR(Box<MuFormula>, Box<MuFormula>),
+    ImpliesL(Box<MuFormula>, Box<MuFormula>),
+    BiImplies(Box<MuFormula>, Box<MuFormula>),
+    Diamond(Box<MuFormula>),
+    Box(Box<MuFormula>),
+    Mu(String, Box<MuFormula>),
+    Nu(String, Box<MuFormula>),
+}
+
+impl MuFormula {
+    pub fn for_each_child(&self, mut f: impl FnMut(&MuFormula)) {
+        use MuFormula as F;
+        match self {
+            F::Neg(x) | F::Diamond(x) | F::Box(x) | F::Mu(_, x) | F::Nu(_, x) => f(x),
+            F::And(l, r)
+            | F::Or(l, r)
+            | F::ImpliesR(l, r)
+            | F::ImpliesL(l, r)
+            | F::BiImplies(l, r) => {
+                f(l);
+                f(r);
+            }
+            F::Top | F::Bot | F::Atomic(_) | F::Var(_) => {}
+        }
+    }
+    pub fn total_size(&self) -> usize {
+        let mut sum = 1;
+        self.for_each_child(|child| {
+            sum += child.total_size();
+        });
+        sum
+    }
+}
+
+pub(crate) mod mu_formula_macros {
+    #![allow(unused)]
+
+    macro_rules! top {
+        () => {
+            Box::new(MuFormula::Top)
+        };
+    }
+    pub(crate) use top;
+
+    macro_rules! bot {
+        () => {
+            Box::new(MuFormula::Bot)
+        };
+    }
+    pub(crate) use bot;
+
+    macro_rules! atom {
+        ($inner:expr) => {
+            Box::new(MuFormula::Atomic(MuVariable::new($inner)))
+        };
+    }
+    pub(crate) use atom;
+
+    macro_rules! var {
+        ($name:expr) => {
+            Box::new(MuFormula::Var($name))
+        };
+    }
+    pub(crate) use var;
+
+    macro_rules! neg {
+        ($inner:expr) => {
+            Box::new(MuFormula::Neg($inner))
+        };
+    }
+    pub(crate) use neg;
+
+    macro_rules! and {
+        ($lhs:expr, $rhs:expr) => {
+            Box::new(MuFormula::And($lhs, $rhs))
+        };
+    }
+    pub(crate) use and;
+
+    macro_rules! or {
+        ($lhs:expr, $rhs:expr) => {
+            Box::new(MuFormula::Or($lhs, $rhs))
+        };
+    }
+    pub(crate) use or;
+
+    macro_rules! impies_r {
+        ($lhs:expr, $rhs:expr) => {
+            Box::new(MuFormula::ImpliesR($lhs, $rhs))
+        };
+    }
+    pub(crate) use impies_r;
+
+    macro_rules! impies_l {
+        ($lhs:expr, $rhs:expr) => {
+            Box::new(MuFormula::ImpliesL($lhs, $rhs))
+        };
+    }
+    pub(crate) use impies_l;
+
+    macro_rules! implies_bi {
+        ($lhs:expr, $rhs:expr) => {
+            Box::new(MuFormula::BiImplies($lhs, $rhs))
+        };
+    }
+    pub(crate) use implies_bi;
+
+    macro_rules! diamond {
+        ($inner:expr) => {
+            Box::new(MuFormula::Diamond($inner))
+        };
+    }
+    pub(crate) use diamond;
+
+    macro_rules! square {
+        ($inner:expr) => {
+            Box::new(MuFormula::Box($inner))
+        };
+    }
+    pub(crate) use square;
+
+    macro_rules! mu {
+        ($name:expr, $inner:expr) => {
+            Box::new(MuFormula::Mu($name, $inner))
+        };
+    }
+    pub(crate) use mu;
+
+    macro_rules! nu {
+        ($name:expr, $inner:expr) => {
+            Box::new(MuFormula::Nu($name, $inner))
+        };
+    }
+    pub(crate) use nu;
+}
@@ -0,0 +1,124 @@
+use std::str::FromStr;
+use thiserror::Error;
+
+/// How formulas are printed by their `print` methods.
+///
+/// All styles only add the parentheses that are needed with the precedence of the parser:
+/// implications bind loosest, then "or", then "and", then the binary temporal operators of LTL,
+/// and negation and the other unary operators bind tightest.
+/// Operators with the same precedence associate to the left.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PrintStyle {
+    /// The syntax of the parsers, like `AG (p -> EF !q)`.
+    /// Parsing the output gives back the same formula.
+    Ascii,
+    /// Mathematical symbols, like `AG (p → EF ¬q)`.
+    #[default]
+    Unicode,
+    /// LaTeX math mode, like `\mathrm{AG}\, (p \rightarrow \mathrm{EF}\, \neg q)`.
+    Latex,
+}
+
+#[derive(Debug, PartialEq, Error)]
+#[error("Unknown print style: {0}, expected one of ascii, unicode, or latex")]
+pub struct UnknownPrintStyle(pub String);
+
+impl FromStr for PrintStyle {
+    type Err = UnknownPrintStyle;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "ascii" => Ok(Self::Ascii),
+            "unicode" => Ok(Self::Unicode),
+            "latex" => Ok(Self::Latex),
+            _ => Err(UnknownPrintStyle(s.to_owned())),
+        }
+    }
+}
+
+impl PrintStyle {
+    pub(crate) fn top(self) -> &'static str {
+        match self {
+            Self::Ascii => "TOP",
+            Self::Unicode => "⊤",
+            Self::Latex => "\\top",
+        }
+    }
+    pub(crate) fn bot(self) -> &'static str {
+        match self {
+            Self::Ascii => "BOT",
+            Self::Unicode => "⊥",
+            Self::Latex => "\\bot",
+        }
+    }
+    pub(crate) fn not(self) -> &'static str {
+        match self {
+            Self::Ascii => "!",
+            Self::Unicode => "¬",
+            Self::Latex => "\\neg ",
+        }
+    }
+    pub(crate) fn and(self) -> &'static str {
+        match self {
+            Self::Ascii => " and ",
+            Self::Unicode => " ∧ ",
+            Self::Latex => " \\land ",
+        }
+    }
+    pub(crate) fn or(self) -> &'static str {
+        match self {
+            Self::Ascii => " or ",
+            Self::Unicode => " ∨ ",
+            Self::Latex => " \\lor ",
+        }
+    }
+    pub(crate) fn implies_r(self) -> &'static str {
+        match self {
+            Self::Ascii => " -> ",
+            Self::Unicode => " → ",
+            Self::Latex => " \\rightarrow ",
+        }
+    }
+    pub(crate) fn implies_l(self) -> &'static str {
+        match self {
+            Self::Ascii => " <- ",
+            Self::Unicode => " ← ",
+            Self::Latex => " \\leftarrow ",
+        }
+    }
+    pub(crate) fn bi_implies(self) -> &'static str {
+        match self {
+            Self::Ascii => " <-> ",
+            Self::Unicode => " ↔ ",
+            Self::Latex => " \\leftrightarrow ",
+        }
+    }
+    /// A prefix operator like EX or G, followed by the space before its argument.
+    pub(crate) fn prefix(self, keyword: &str) -> String {
+        match self {
+            Self::Ascii | Self::Unicode => format!("{keyword} "),
+            Self::Latex => format!("\\mathrm{{{keyword}}}\\, "),
+        }
+    }
+    /// An infix operator like U, with the spaces around it.
+    pub(crate) fn infix(self, keyword: &str) -> String {
+        match self {
+            Self::Ascii | Self::Unicode => format!(" {keyword} "),
+            Self::Latex => format!(" \\mathbin{{\\mathrm{{{keyword}}}}} "),
+        }
+    }
+    /// A path quantifier in front of square brackets, like the E in E[p U q].
+    pub(crate) fn quantifier(self, keyword: &str) -> String {
+        match self {
+            Self::Ascii | Self::Unicode => keyword.to_owned(),
+            Self::Latex => format!("\\mathrm{{{keyword}}}"),
+        }
+    }
+    pub(crate) fn variable(self, name: &str) -> String {
+        match self {
+            Self::Latex if name.chars().count() > 1 => {
+                format!("\\mathit{{{}}}", name.replace('_', "\\_"))
+            }
+            _ => name.to_owned(),
+        }
+    }
+}
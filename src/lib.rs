@@ -1,3 +1,24 @@
+//! A small model checker for CTL, LTL, CTL*, the modal mu-calculus, and Hennessy–Milner logic
+//! on finite Kripke structures.
+//!
+//! ```
+//! use std::collections::{HashMap, HashSet};
+//! use minictl::formulas::{ctl_builders::*, parse_ctl};
+//! use minictl::modelcheckers::CTLChecker;
+//! use minictl::models::{Model, State};
+//!
+//! let s1 = State::new("s1".to_string(), HashSet::from(["p".to_string()]));
+//! let s2 = State::new("s2".to_string(), HashSet::new());
+//! let edges = HashMap::from([
+//!     ("s1".to_string(), vec!["s2".to_string()]),
+//!     ("s2".to_string(), vec!["s1".to_string()]),
+//! ]);
+//! let model = Model::new(vec![s1, s2], edges, vec!["s1".to_string()]).unwrap();
+//!
+//! let mut checker = CTLChecker::new(model);
+//! assert_eq!(checker.check(&ag(af(atom("p")))).len(), 2);
+//! assert_eq!(*parse_ctl("AG AF p").unwrap(), *ag(af(atom("p"))));
+//! ```
 pub mod formulas;
 pub mod modelcheckers;
 pub mod models;
@@ -17,9 +38,18 @@ fn hello_world() -> PyResult<String> {
 fn minictl(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<formulas::ctl_python::PyCTLFormula>()?;
     m.add_class::<formulas::ltl_python::PyLTLFormula>()?;
+    m.add_class::<formulas::ctlstar_python::PyCTLStarFormula>()?;
+    m.add_class::<formulas::mu_python::PyMuFormula>()?;
+    m.add_class::<formulas::hml_python::PyHMLFormula>()?;
     m.add_class::<models::models_python::PyState>()?;
     m.add_class::<models::models_python::PyModel>()?;
     m.add_class::<modelcheckers::ctl_checker_python::PyCTLChecker>()?;
+    m.add_class::<modelcheckers::ctl_checker_python::PyTraceStep>()?;
+    m.add_class::<modelcheckers::ltl_checker_python::PyLTLChecker>()?;
+    m.add_class::<modelcheckers::symbolic_checker_python::PySymbolicCTLChecker>()?;
+    m.add_class::<modelcheckers::ctlstar_checker_python::PyCTLStarChecker>()?;
+    m.add_class::<modelcheckers::mu_checker_python::PyMuChecker>()?;
+    m.add_class::<modelcheckers::hml_checker_python::PyHMLChecker>()?;
     m.add_function(wrap_pyfunction!(hello_world, m)?)?;
     Ok(())
 }
@@ -1,3 +1,225 @@
-fn main() {
-    println!("Hello, world!");
+// The minictl command line tool.
+//
+// Usage: minictl [OPTIONS] <MODEL> [FORMULA]...
+//
+// It loads a model from a text file (or compiles it from a program), checks every formula given on the command line
+// or in a spec file, and prints the states in which each of them hold.
+// The exit code is 0 if all formulas hold in all initial states, 1 if some formula does not,
+// and 2 if something went wrong before any checking could happen (bad arguments, files, or formulas)
+// This makes it usable from shell scripts and Makefiles.
+use std::collections::HashSet;
+use std::process::ExitCode;
+use std::{env, fs};
+
+use minictl::formulas::{parse_ctl, parse_ltl, CTLFormula, LTLFormula};
+use minictl::modelcheckers::{CTLChecker, LTLChecker};
+use minictl::models::{parse_model, parse_program, Model, Specification};
+
+const USAGE: &str = "\
+Usage: minictl [OPTIONS] <MODEL> [FORMULA]...
+
+Checks CTL or LTL formulas against the model in the file MODEL.
+
+Options:
+  -s, --spec <FILE>  Also read formulas from FILE, one per line.
+                     Lines can be prefixed with `CTL:` or `LTL:`, and default to CTL.
+                     Empty lines and lines starting with `#` are ignored.
+  -l, --ltl          Read the FORMULA arguments as LTL instead of CTL
+      --allow-sink   Accept models with states that have no outgoing transitions
+  -h, --help         Print this message
+
+The model file consists of lines of the following forms, where `#` starts a comment:
+  state <name> [: <prop>...]   A state with the propositions true in it
+  <name> -> <name>...          Transitions from a state
+  initial <name>...            Initial states (by default, the first state)
+Every state needs a transition, unless --allow-sink is given.
+Names containing whitespace or any of `#:\"` or `->` can be written in double quotes.
+
+Model files ending in `.smv` are programs in a guarded-command language instead:
+  VAR x : {a, b}; n : 0..3;    Variables, with their values
+  INIT x=a and n=0;            Initial conditions
+  TRANS x=a : x := b, n := {0, 1};
+                               Guarded updates, where a set is a non-deterministic choice
+  CTLSPEC AG(n=0 -> EF x=b);   Specs, which are checked along with the FORMULA arguments
+  LTLSPEC G F x=b;
+States satisfy the propositions `var=value`, as in `x=a`.
+
+Exit status is 0 if all formulas hold in all initial states, 1 if not, and 2 on errors.";
+
+#[derive(Debug)]
+enum Spec {
+    Ctl(String, Box<CTLFormula>),
+    Ltl(String, Box<LTLFormula>),
+}
+
+impl Spec {
+    fn parse(text: &str, ltl: bool) -> Result<Self, String> {
+        let text = text.trim().to_string();
+        if ltl {
+            let formula =
+                parse_ltl(&text).map_err(|e| format!("Invalid LTL formula {text}: {e}"))?;
+            Ok(Self::Ltl(text, formula))
+        } else {
+            let formula =
+                parse_ctl(&text).map_err(|e| format!("Invalid CTL formula {text}: {e}"))?;
+            Ok(Self::Ctl(text, formula))
+        }
+    }
+    fn from_program(spec: Specification) -> Self {
+        match spec {
+            Specification::Ctl(text, formula) => Self::Ctl(text, formula),
+            Specification::Ltl(text, formula) => Self::Ltl(text, formula),
+        }
+    }
+    fn parse_line(line: &str) -> Result<Self, String> {
+        match line.split_once(':') {
+            Some((kind, rest)) if kind.trim().eq_ignore_ascii_case("ltl") => {
+                Self::parse(rest, true)
+            }
+            Some((kind, rest)) if kind.trim().eq_ignore_ascii_case("ctl") => {
+                Self::parse(rest, false)
+            }
+            _ => Self::parse(line, false),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Args {
+    model: Option<String>,
+    spec: Option<String>,
+    ltl: bool,
+    allow_sink: bool,
+    formulas: Vec<String>,
+}
+
+impl Args {
+    /// Returns Ok(None) if the help message was requested.
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Option<Self>, String> {
+        let mut ret = Self::default();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" | "--help" => return Ok(None),
+                "-l" | "--ltl" => ret.ltl = true,
+                "--allow-sink" => ret.allow_sink = true,
+                "-s" | "--spec" => {
+                    let file = args.next().ok_or(format!("{arg} expects a file"))?;
+                    ret.spec = Some(file);
+                }
+                "--" => {
+                    ret.positional(args.by_ref());
+                }
+                s if s.starts_with('-') && s.len() > 1 => {
+                    return Err(format!("Unknown option: {s}"));
+                }
+                _ => ret.positional(std::iter::once(arg)),
+            }
+        }
+        Ok(Some(ret))
+    }
+    fn positional(&mut self, args: impl Iterator<Item = String>) {
+        for arg in args {
+            match self.model {
+                None => self.model = Some(arg),
+                Some(_) => self.formulas.push(arg),
+            }
+        }
+    }
+}
+
+fn read_file(path: &str) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("Could not read {path}: {e}"))
+}
+
+/// Reads the model, along with its specs if it is a program.
+fn read_model(path: &str, allow_sink: bool) -> Result<(Model, Vec<Spec>), String> {
+    let text = read_file(path)?;
+    let (model, specs) = if path.ends_with(".smv") {
+        let program = parse_program(&text).map_err(|e| format!("Invalid program {path}: {e}"))?;
+        let specs = program.specs.into_iter().map(Spec::from_program).collect();
+        (program.model, specs)
+    } else {
+        let model = parse_model(&text).map_err(|e| format!("Invalid model {path}: {e}"))?;
+        (model, Vec::new())
+    };
+    if !allow_sink {
+        model
+            .check_no_sinks()
+            .map_err(|e| format!("Invalid model {path}: {e} (pass --allow-sink to accept it)"))?;
+    }
+    Ok((model, specs))
+}
+
+fn sorted(names: &HashSet<String>) -> String {
+    let mut names: Vec<&str> = names.iter().map(String::as_str).collect();
+    names.sort();
+    names.join(", ")
+}
+
+/// Checks the spec, prints the result, and returns whether it holds in all initial states.
+fn report(spec: &Spec, ctl: &mut CTLChecker, ltl: &mut LTLChecker) -> bool {
+    let (kind, text, states) = match spec {
+        Spec::Ctl(text, formula) => ("CTL", text, ctl.check(formula)),
+        Spec::Ltl(text, formula) => ("LTL", text, ltl.check(formula)),
+    };
+    let failing: HashSet<String> = ctl
+        .get_model()
+        .all_initial()
+        .difference(&states)
+        .cloned()
+        .collect();
+    let holds = failing.is_empty();
+
+    println!("{kind} {text}: {}", if holds { "holds" } else { "fails" });
+    println!("  satisfied in: {{{}}}", sorted(&states));
+    if !holds {
+        println!("  failing initial states: {{{}}}", sorted(&failing));
+    }
+    holds
+}
+
+fn run() -> Result<bool, String> {
+    let Some(args) = Args::parse(env::args().skip(1))? else {
+        println!("{USAGE}");
+        return Ok(true);
+    };
+    let model_path = args.model.ok_or(format!("No model given\n\n{USAGE}"))?;
+    let (model, mut specs) = read_model(&model_path, args.allow_sink)?;
+
+    specs.extend(
+        args.formulas
+            .iter()
+            .map(|f| Spec::parse(f, args.ltl))
+            .collect::<Result<Vec<_>, _>>()?,
+    );
+    if let Some(spec_path) = args.spec {
+        for line in read_file(&spec_path)?.lines().map(str::trim) {
+            if !line.is_empty() && !line.starts_with('#') {
+                specs.push(Spec::parse_line(line)?);
+            }
+        }
+    }
+    if specs.is_empty() {
+        return Err(format!("No formulas given\n\n{USAGE}"));
+    }
+
+    let mut ctl = CTLChecker::new(model.clone());
+    let mut ltl = LTLChecker::new(model);
+    // No short-circuiting: we want to report on every formula.
+    let results: Vec<bool> = specs
+        .iter()
+        .map(|s| report(s, &mut ctl, &mut ltl))
+        .collect();
+    Ok(results.into_iter().all(|b| b))
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(1),
+        Err(e) => {
+            eprintln!("minictl: {e}");
+            ExitCode::from(2)
+        }
+    }
 }
@@ -1,5 +1,37 @@
+mod bdd;
 mod ctl_checker;
-pub use ctl_checker::CTLChecker;
+mod ctlstar_checker;
+mod equivalence;
+mod hml_checker;
+mod lasso;
+mod ltl_checker;
+mod mu_checker;
+mod scc;
+mod state_set;
+mod symbolic_checker;
+pub use ctl_checker::{CTLChecker, TraceStep};
+pub use ctlstar_checker::CTLStarChecker;
+pub use equivalence::{bisimulation, simulation};
+pub use hml_checker::HMLChecker;
+pub use lasso::{Lasso, Path};
+pub use ltl_checker::LTLChecker;
+pub use mu_checker::{MuCheckError, MuChecker};
+pub use symbolic_checker::SymbolicCTLChecker;
 
 #[cfg(feature = "python")]
 pub mod ctl_checker_python;
+
+#[cfg(feature = "python")]
+pub mod ltl_checker_python;
+
+#[cfg(feature = "python")]
+pub mod ctlstar_checker_python;
+
+#[cfg(feature = "python")]
+pub mod mu_checker_python;
+
+#[cfg(feature = "python")]
+pub mod hml_checker_python;
+
+#[cfg(feature = "python")]
+pub mod symbolic_checker_python;
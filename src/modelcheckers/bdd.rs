@@ -0,0 +1,243 @@
+// A minimal reduced ordered binary decision diagram package,
+// with only what the symbolic checker needs.
+//
+// Nodes live in one big vec, and are referred to by index. The indexes 0 and 1 are the
+// false and true terminals. Every node is hash-consed through the unique table,
+// so two BDDs represent the same function exactly if they have the same index.
+// This makes the fixpoint checks in the symbolic checker a simple integer comparison.
+use std::collections::HashMap;
+
+pub(crate) type BddRef = usize;
+
+pub(crate) const FALSE: BddRef = 0;
+pub(crate) const TRUE: BddRef = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Node {
+    var: u32,
+    lo: BddRef,
+    hi: BddRef,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Op {
+    And,
+    Or,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Bdd {
+    nodes: Vec<Node>,
+    unique: HashMap<Node, BddRef>,
+    apply_cache: HashMap<(Op, BddRef, BddRef), BddRef>,
+    not_cache: HashMap<BddRef, BddRef>,
+}
+
+impl Bdd {
+    pub(crate) fn new() -> Self {
+        // The terminals get a variable index larger than any real variable,
+        // so they always sort below every other node.
+        let terminal = |i| Node {
+            var: u32::MAX,
+            lo: i,
+            hi: i,
+        };
+        Self {
+            nodes: vec![terminal(FALSE), terminal(TRUE)],
+            unique: HashMap::new(),
+            apply_cache: HashMap::new(),
+            not_cache: HashMap::new(),
+        }
+    }
+    fn var_of(&self, f: BddRef) -> u32 {
+        self.nodes[f].var
+    }
+    fn mk(&mut self, var: u32, lo: BddRef, hi: BddRef) -> BddRef {
+        if lo == hi {
+            return lo;
+        }
+        let node = Node { var, lo, hi };
+        if let Some(&existing) = self.unique.get(&node) {
+            return existing;
+        }
+        self.nodes.push(node);
+        self.unique.insert(node, self.nodes.len() - 1);
+        self.nodes.len() - 1
+    }
+    /// The function that is true exactly if variable `var` is.
+    pub(crate) fn var(&mut self, var: u32) -> BddRef {
+        self.mk(var, FALSE, TRUE)
+    }
+    /// The function that is true exactly if variable `var` is false.
+    pub(crate) fn nvar(&mut self, var: u32) -> BddRef {
+        self.mk(var, TRUE, FALSE)
+    }
+    pub(crate) fn not(&mut self, f: BddRef) -> BddRef {
+        match f {
+            FALSE => return TRUE,
+            TRUE => return FALSE,
+            _ => {}
+        }
+        if let Some(&ret) = self.not_cache.get(&f) {
+            return ret;
+        }
+        let Node { var, lo, hi } = self.nodes[f];
+        let (lo, hi) = (self.not(lo), self.not(hi));
+        let ret = self.mk(var, lo, hi);
+        self.not_cache.insert(f, ret);
+        ret
+    }
+    pub(crate) fn and(&mut self, f: BddRef, g: BddRef) -> BddRef {
+        self.apply(Op::And, f, g)
+    }
+    pub(crate) fn or(&mut self, f: BddRef, g: BddRef) -> BddRef {
+        self.apply(Op::Or, f, g)
+    }
+    fn apply(&mut self, op: Op, f: BddRef, g: BddRef) -> BddRef {
+        match (op, f, g) {
+            (Op::And, FALSE, _) | (Op::And, _, FALSE) => return FALSE,
+            (Op::And, TRUE, x) | (Op::And, x, TRUE) => return x,
+            (Op::Or, TRUE, _) | (Op::Or, _, TRUE) => return TRUE,
+            (Op::Or, FALSE, x) | (Op::Or, x, FALSE) => return x,
+            _ if f == g => return f,
+            _ => {}
+        }
+        // Both operations are commutative, so we can normalise the cache key.
+        let key = (op, f.min(g), f.max(g));
+        if let Some(&ret) = self.apply_cache.get(&key) {
+            return ret;
+        }
+        let var = self.var_of(f).min(self.var_of(g));
+        let (f_lo, f_hi) = self.cofactors(f, var);
+        let (g_lo, g_hi) = self.cofactors(g, var);
+        let lo = self.apply(op, f_lo, g_lo);
+        let hi = self.apply(op, f_hi, g_hi);
+        let ret = self.mk(var, lo, hi);
+        self.apply_cache.insert(key, ret);
+        ret
+    }
+    fn cofactors(&self, f: BddRef, var: u32) -> (BddRef, BddRef) {
+        let node = self.nodes[f];
+        match node.var == var {
+            true => (node.lo, node.hi),
+            false => (f, f),
+        }
+    }
+    /// Existentially quantify away all variables for which `quantify` returns true
+    /// from the conjunction of `f` and `g`, without building that conjunction first,
+    /// which is usually much larger than the result.
+    pub(crate) fn and_exists(
+        &mut self,
+        f: BddRef,
+        g: BddRef,
+        quantify: &impl Fn(u32) -> bool,
+    ) -> BddRef {
+        let mut cache = HashMap::new();
+        self.and_exists_rec(f, g, quantify, &mut cache)
+    }
+    fn and_exists_rec(
+        &mut self,
+        f: BddRef,
+        g: BddRef,
+        quantify: &impl Fn(u32) -> bool,
+        cache: &mut HashMap<(BddRef, BddRef), BddRef>,
+    ) -> BddRef {
+        if f == FALSE || g == FALSE {
+            return FALSE;
+        }
+        if f == TRUE && g == TRUE {
+            return TRUE;
+        }
+        let key = (f.min(g), f.max(g));
+        if let Some(&ret) = cache.get(&key) {
+            return ret;
+        }
+        let var = self.var_of(f).min(self.var_of(g));
+        let (f_lo, f_hi) = self.cofactors(f, var);
+        let (g_lo, g_hi) = self.cofactors(g, var);
+        let lo = self.and_exists_rec(f_lo, g_lo, quantify, cache);
+        let ret = match quantify(var) {
+            // Either branch being true is enough.
+            true if lo == TRUE => TRUE,
+            true => {
+                let hi = self.and_exists_rec(f_hi, g_hi, quantify, cache);
+                self.or(lo, hi)
+            }
+            false => {
+                let hi = self.and_exists_rec(f_hi, g_hi, quantify, cache);
+                self.mk(var, lo, hi)
+            }
+        };
+        cache.insert(key, ret);
+        ret
+    }
+    /// Replace every variable `v` with `rename(v)`.
+    /// The renaming has to be strictly monotone on the variables in `f`,
+    /// so the variable order is kept without having to re-sort the diagram.
+    pub(crate) fn rename(&mut self, f: BddRef, rename: &impl Fn(u32) -> u32) -> BddRef {
+        let mut cache = HashMap::new();
+        self.rename_rec(f, rename, &mut cache)
+    }
+    fn rename_rec(
+        &mut self,
+        f: BddRef,
+        rename: &impl Fn(u32) -> u32,
+        cache: &mut HashMap<BddRef, BddRef>,
+    ) -> BddRef {
+        if f == FALSE || f == TRUE {
+            return f;
+        }
+        if let Some(&ret) = cache.get(&f) {
+            return ret;
+        }
+        let Node { var, lo, hi } = self.nodes[f];
+        let lo = self.rename_rec(lo, rename, cache);
+        let hi = self.rename_rec(hi, rename, cache);
+        let ret = self.mk(rename(var), lo, hi);
+        cache.insert(f, ret);
+        ret
+    }
+    /// All assignments to `vars`, which must be sorted, that satisfy `f`.
+    /// `f` may only depend on variables in `vars`; the others in there can have any value.
+    pub(crate) fn sat_all(&self, f: BddRef, vars: &[u32]) -> Vec<Vec<bool>> {
+        let mut ret = Vec::new();
+        self.sat_all_rec(f, vars, &mut Vec::with_capacity(vars.len()), &mut ret);
+        ret
+    }
+    fn sat_all_rec(
+        &self,
+        f: BddRef,
+        vars: &[u32],
+        prefix: &mut Vec<bool>,
+        ret: &mut Vec<Vec<bool>>,
+    ) {
+        if f == FALSE {
+            return;
+        }
+        let Some(&var) = vars.get(prefix.len()) else {
+            ret.push(prefix.clone());
+            return;
+        };
+        let (lo, hi) = self.cofactors(f, var);
+        for (value, f) in [(false, lo), (true, hi)] {
+            prefix.push(value);
+            self.sat_all_rec(f, vars, prefix, ret);
+            prefix.pop();
+        }
+    }
+    /// One assignment to `vars` that satisfies `f`, under the same conditions as `sat_all`.
+    pub(crate) fn sat_one(&self, mut f: BddRef, vars: &[u32]) -> Option<Vec<bool>> {
+        if f == FALSE {
+            return None;
+        }
+        let mut ret = Vec::with_capacity(vars.len());
+        for &var in vars {
+            let (lo, hi) = self.cofactors(f, var);
+            // Every node other than FALSE has a satisfying assignment below it.
+            let value = lo == FALSE;
+            f = if value { hi } else { lo };
+            ret.push(value);
+        }
+        Some(ret)
+    }
+}
@@ -3,178 +3,272 @@ use std::collections::{HashMap, HashSet};
 
 use typed_arena::Arena;
 
+use super::lasso::{fair_lasso, shortest_path, Lasso, Path};
+use super::scc::{is_nontrivial, tarjan_scc};
+use super::state_set::StateSet;
 use crate::formulas::ctl_formula_macros as f;
 use crate::formulas::CTLFormula;
 use crate::models::Model;
 
+/// Fairness constraints, stored as the sets of states in which each of them holds,
+/// together with the set of states from which at least one fair path starts.
+#[derive(Debug, Clone)]
+struct Fairness {
+    constraints: Vec<StateSet>,
+    fair: StateSet,
+}
+
+/// One step in the computation of a formula, as recorded by a checker with tracing enabled.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TraceStep {
+    /// The formula was computed through an equivalent one, like `AG φ` through `¬EF¬φ`.
+    Rewrite {
+        formula: Box<CTLFormula>,
+        rewritten: Box<CTLFormula>,
+    },
+    /// An approximation of the fixpoint of an `EU` or `AF` formula, counting from 0.
+    /// The last iteration has the same states as the one before it: that is the fixpoint.
+    Iteration {
+        formula: Box<CTLFormula>,
+        iteration: usize,
+        states: HashSet<String>,
+    },
+}
+
+// Trace steps as they are recorded while checking, before the states are named.
+enum RawStep {
+    Rewrite(CTLFormula, CTLFormula),
+    Iteration(CTLFormula, usize, StateSet),
+}
+
 struct CTLCheckerInner<'a> {
-    map: HashMap<&'a CTLFormula, &'a HashSet<usize>>,
-    result_arena: &'a Arena<HashSet<usize>>,
+    map: HashMap<&'a CTLFormula, &'a StateSet>,
+    result_arena: &'a Arena<StateSet>,
     formula_arena: &'a Arena<CTLFormula>,
+    fairness: Option<&'a Fairness>,
+    trace: Option<Vec<RawStep>>,
 }
 impl<'a> CTLCheckerInner<'a> {
-    fn map(&self) -> &HashMap<&'a CTLFormula, &'a HashSet<usize>> {
+    fn map(&self) -> &HashMap<&'a CTLFormula, &'a StateSet> {
         &self.map
     }
-    fn memoise_alloc(
-        &mut self,
-        formula: &'a CTLFormula,
-        ret: HashSet<usize>,
-    ) -> &'a HashSet<usize> {
+    fn memoise_alloc(&mut self, formula: &'a CTLFormula, ret: StateSet) -> &'a StateSet {
         let ret_ref = self.result_arena.alloc(ret);
         self.map.insert(formula, ret_ref);
         ret_ref
     }
-    fn memoise_ref(
-        &mut self,
-        formula: &'a CTLFormula,
-        ret: &'a HashSet<usize>,
-    ) -> &'a HashSet<usize> {
+    fn memoise_ref(&mut self, formula: &'a CTLFormula, ret: &'a StateSet) -> &'a StateSet {
         self.map.insert(formula, ret);
         ret
     }
-    fn sat_ex(&mut self, formula: &'a CTLFormula, model: &Model) -> HashSet<usize> {
-        model.pre_e_idx(self.check(formula, model))
+    /// Check the formula by checking the equivalent `rewritten` formula instead.
+    fn check_rewritten(
+        &mut self,
+        formula: &'a CTLFormula,
+        rewritten: CTLFormula,
+        model: &Model,
+    ) -> &'a StateSet {
+        let rewritten = self.formula_arena.alloc(rewritten);
+        if let Some(trace) = &mut self.trace {
+            trace.push(RawStep::Rewrite(formula.clone(), rewritten.clone()));
+        }
+        let ret = self.check(rewritten, model);
+        self.memoise_ref(rewritten, ret)
+    }
+    fn record_iteration(&mut self, formula: &CTLFormula, iteration: usize, set: &StateSet) {
+        if let Some(trace) = &mut self.trace {
+            trace.push(RawStep::Iteration(formula.clone(), iteration, set.clone()));
+        }
+    }
+    // Under fairness, the path quantifiers only range over fair paths,
+    // so we only want to move into states from which a fair path continues.
+    fn restrict_fair(&self, set: &'a StateSet) -> Cow<'a, StateSet> {
+        match self.fairness {
+            Some(fairness) => Cow::Owned(set.intersection(&fairness.fair)),
+            None => Cow::Borrowed(set),
+        }
+    }
+    fn sat_ex(&mut self, formula: &'a CTLFormula, model: &Model) -> StateSet {
+        let set = self.check(formula, model);
+        let set = self.restrict_fair(set);
+        let pre = model.get_pre_idx();
+        StateSet::from_indexes(
+            model.nr_states(),
+            set.iter().flat_map(|i| pre[i].iter().copied()),
+        )
     }
+    // Instead of recomputing the pre-image of the whole set every iteration,
+    // we only look at the predecessors of the states that were added in the last one.
+    // Every state is added at most once, so every edge is looked at at most once.
+    // The sets after every iteration are still those of the textbook fixpoint computation.
     fn sat_eu(
         &mut self,
+        formula: &'a CTLFormula,
         formula1: &'a CTLFormula,
         formula2: &'a CTLFormula,
         model: &Model,
-    ) -> HashSet<usize> {
-        // We are using Cow only to have some type generic over T and &T,
-        // as our initial `solve()` returns a reference, but calls to pre_a return owned values.
-        let mut set = Cow::Borrowed(self.check(formula2, model));
+    ) -> StateSet {
+        let set = self.check(formula2, model);
+        let mut ret = self.restrict_fair(set).into_owned();
         let base = self.check(formula1, model);
-        loop {
-            let next = model
-                .pre_e_idx(&set)
-                .intersection(base)
-                .copied()
-                .collect::<HashSet<usize>>()
-                .union(&set)
-                .copied()
-                .collect::<HashSet<usize>>();
-            if next == *set {
-                return next;
-            }
-            set = Cow::Owned(next);
-        }
-    }
-    fn sat_af(&mut self, formula: &'a CTLFormula, model: &Model) -> HashSet<usize> {
-        // We are using Cow only to have some type generic over T and &T,
-        // as our initial `solve()` returns a reference, but calls to pre_a return owned values.
-        let mut set = Cow::Borrowed(self.check(formula, model));
-        loop {
-            let next: HashSet<usize> = model.pre_a_idx(&set).union(&set).copied().collect();
-            if next == *set {
-                return next;
-            }
-            set = Cow::Owned(next);
-        }
-    }
-    fn check(&mut self, formula: &'a CTLFormula, model: &Model) -> &'a HashSet<usize> {
+        let pre = model.get_pre_idx();
+        let mut frontier: Vec<usize> = ret.iter().collect();
+        let mut iteration = 0;
+        self.record_iteration(formula, iteration, &ret);
+        while !frontier.is_empty() {
+            let mut added = Vec::new();
+            for idx in frontier {
+                for &prev in pre[idx].iter() {
+                    if base.contains(prev) && ret.insert(prev) {
+                        added.push(prev);
+                    }
+                }
+            }
+            iteration += 1;
+            self.record_iteration(formula, iteration, &ret);
+            frontier = added;
+        }
+        ret
+    }
+    // Like EU, but a state is only added once all of its successors are in the set,
+    // which we track by counting down the number of successors not in the set yet.
+    // Sink states never get counted down, so they are only in the result if they satisfy φ.
+    fn sat_af(
+        &mut self,
+        formula: &'a CTLFormula,
+        inner: &'a CTLFormula,
+        model: &Model,
+    ) -> StateSet {
+        let mut ret = self.check(inner, model).clone();
+        let pre = model.get_pre_idx();
+        let mut remaining: Vec<usize> = model.get_post_idx().iter().map(Vec::len).collect();
+        let mut frontier: Vec<usize> = ret.iter().collect();
+        let mut iteration = 0;
+        self.record_iteration(formula, iteration, &ret);
+        while !frontier.is_empty() {
+            let mut added = Vec::new();
+            for idx in frontier {
+                for &prev in pre[idx].iter() {
+                    if ret.contains(prev) {
+                        continue;
+                    }
+                    remaining[prev] -= 1;
+                    if remaining[prev] == 0 {
+                        ret.insert(prev);
+                        added.push(prev);
+                    }
+                }
+            }
+            iteration += 1;
+            self.record_iteration(formula, iteration, &ret);
+            frontier = added;
+        }
+        ret
+    }
+    fn sat_eg(&mut self, formula: &'a CTLFormula, model: &Model) -> StateSet {
+        let set = self.check(formula, model);
+        match self.fairness {
+            Some(fairness) => sat_eg_fair(model, set, &fairness.constraints),
+            None => sat_eg(model, set),
+        }
+    }
+    fn check(&mut self, formula: &'a CTLFormula, model: &Model) -> &'a StateSet {
         if let Some(ret) = self.map.get(formula) {
             return ret;
         }
         use CTLFormula as F;
+        let nr_states = model.nr_states();
         // We do the re-writing as we go instead of on the formula immediately.
         // I know it's probably slower this way, but it's also kinda fine.
         match formula {
-            F::Top => self.memoise_alloc(formula, model.all_idx()),
-            F::Bot => self.memoise_alloc(formula, HashSet::new()),
-            F::Atomic(var) => self.memoise_alloc(formula, model.all_containing_idx(&var.inner)),
+            F::Top => self.memoise_alloc(formula, StateSet::full(nr_states)),
+            F::Bot => self.memoise_alloc(formula, StateSet::empty(nr_states)),
+            F::Atomic(var) => {
+                let ret = StateSet::from_indexes(nr_states, model.all_containing_idx(&var.inner));
+                self.memoise_alloc(formula, ret)
+            }
             F::Neg(inner) => {
-                let ret = model.all_except_idx(self.check(inner, model));
+                let ret = self.check(inner, model).complement();
                 self.memoise_alloc(formula, ret)
             }
             F::And(lhs, rhs) => {
-                let ret = self
-                    .check(lhs, model)
-                    .intersection(self.check(rhs, model))
-                    .copied()
-                    .collect();
+                let ret = self.check(lhs, model).intersection(self.check(rhs, model));
                 self.memoise_alloc(formula, ret)
             }
             F::Or(lhs, rhs) => {
-                let ret = self
-                    .check(lhs, model)
-                    .union(self.check(rhs, model))
-                    .copied()
-                    .collect();
+                let ret = self.check(lhs, model).union(self.check(rhs, model));
                 self.memoise_alloc(formula, ret)
             }
             F::ImpliesR(lhs, rhs) => {
-                let rewritten = self
-                    .formula_arena
-                    .alloc(F::Or(f::neg!(lhs.clone()), rhs.clone()));
-                let ret = self.check(rewritten, model);
-                self.memoise_ref(rewritten, ret)
+                self.check_rewritten(formula, F::Or(f::neg!(lhs.clone()), rhs.clone()), model)
             }
             F::ImpliesL(lhs, rhs) => {
-                let rewritten = self
-                    .formula_arena
-                    .alloc(F::Or(lhs.clone(), f::neg!(rhs.clone())));
-                let ret = self.check(rewritten, model);
-                self.memoise_ref(rewritten, ret)
-            }
-            F::BiImplies(lhs, rhs) => {
-                let rewritten = self.formula_arena.alloc(F::And(
+                self.check_rewritten(formula, F::Or(lhs.clone(), f::neg!(rhs.clone())), model)
+            }
+            F::BiImplies(lhs, rhs) => self.check_rewritten(
+                formula,
+                F::And(
                     f::impies_r!(lhs.clone(), rhs.clone()),
                     f::impies_r!(rhs.clone(), lhs.clone()),
-                ));
-                let ret = self.check(rewritten, model);
-                self.memoise_ref(rewritten, ret)
-            }
+                ),
+                model,
+            ),
             F::AX(inner) => {
-                let rewritten = self
-                    .formula_arena
-                    .alloc(F::Neg(f::ex!(f::neg!(inner.clone()))));
-                let ret = self.check(rewritten, model);
-                self.memoise_ref(rewritten, ret)
+                self.check_rewritten(formula, F::Neg(f::ex!(f::neg!(inner.clone()))), model)
             }
             F::EX(inner) => {
                 let ret = self.sat_ex(inner, model);
                 self.memoise_alloc(formula, ret)
             }
             // yuk
-            F::AU(lhs, rhs) => {
-                let rewritten = self.formula_arena.alloc(F::Or(
-                    f::neg!(f::eu!(
+            // A[φUψ] ≡ ¬(E[¬ψU(¬φ∧¬ψ)] ∨ EG¬ψ)
+            F::AU(lhs, rhs) => self.check_rewritten(
+                formula,
+                F::Neg(f::or!(
+                    f::eu!(
                         f::neg!(rhs.clone()),
                         f::and!(f::neg!(lhs.clone()), f::neg!(rhs.clone()))
-                    )),
-                    f::eg!(f::neg!(rhs.clone())),
-                ));
-                let ret = self.check(rewritten, model);
-                self.memoise_ref(rewritten, ret)
-            }
+                    ),
+                    f::eg!(f::neg!(rhs.clone()))
+                )),
+                model,
+            ),
+            // E[φRψ] ≡ E[ψU(φ∧ψ)] ∨ EGψ
+            F::ER(lhs, rhs) => self.check_rewritten(
+                formula,
+                F::Or(
+                    f::eu!(rhs.clone(), f::and!(lhs.clone(), rhs.clone())),
+                    f::eg!(rhs.clone()),
+                ),
+                model,
+            ),
+            // A[φRψ] ≡ ¬E[¬φU¬ψ]
+            F::AR(lhs, rhs) => self.check_rewritten(
+                formula,
+                F::Neg(f::eu!(f::neg!(lhs.clone()), f::neg!(rhs.clone()))),
+                model,
+            ),
             F::EU(lhs, rhs) => {
-                let ret = self.sat_eu(lhs, rhs, model);
+                let ret = self.sat_eu(formula, lhs, rhs, model);
                 self.memoise_alloc(formula, ret)
             }
-            F::EF(inner) => {
-                let rewritten = self.formula_arena.alloc(F::EU(f::top!(), inner.clone()));
-                let ret = self.check(rewritten, model);
-                self.memoise_ref(rewritten, ret)
+            F::EF(inner) => self.check_rewritten(formula, F::EU(f::top!(), inner.clone()), model),
+            // With fairness, AF can no longer be computed directly with pre_a,
+            // as the paths through the states that pre_a looks at might not be fair.
+            // Instead, we compute EG natively, and rewrite AF in terms of it.
+            F::AF(inner) if self.fairness.is_some() => {
+                self.check_rewritten(formula, F::Neg(f::eg!(f::neg!(inner.clone()))), model)
             }
             F::AF(inner) => {
-                let ret = self.sat_af(inner, model);
+                let ret = self.sat_af(formula, inner, model);
                 self.memoise_alloc(formula, ret)
             }
             F::EG(inner) => {
-                let rewritten = self
-                    .formula_arena
-                    .alloc(F::Neg(f::af!(f::neg!(inner.clone()))));
-                let ret = self.check(rewritten, model);
-                self.memoise_ref(rewritten, ret)
+                let ret = self.sat_eg(inner, model);
+                self.memoise_alloc(formula, ret)
             }
             F::AG(inner) => {
-                let rewritten = self
-                    .formula_arena
-                    .alloc(F::Neg(f::ef!(f::neg!(inner.clone()))));
-                let ret = self.check(rewritten, model);
-                self.memoise_ref(rewritten, ret)
+                self.check_rewritten(formula, F::Neg(f::ef!(f::neg!(inner.clone()))), model)
             }
         }
     }
@@ -183,47 +277,171 @@ impl<'a> CTLCheckerInner<'a> {
 #[derive(Debug, Clone)]
 pub struct CTLChecker {
     model: Model,
-    cache: HashMap<Box<CTLFormula>, HashSet<usize>>,
+    cache: HashMap<Box<CTLFormula>, StateSet>,
+    fairness: Option<Fairness>,
+    // The number of states removed by `restrict_to_reachable`.
+    pruned: usize,
+    // The steps recorded so far, if tracing is enabled.
+    trace: Option<Vec<TraceStep>>,
 }
 impl CTLChecker {
     pub fn new(model: Model) -> Self {
         Self {
             model,
             cache: HashMap::new(),
+            fairness: None,
+            pruned: 0,
+            trace: None,
+        }
+    }
+    /// A checker for fair CTL: all path quantifiers only range over the fair paths,
+    /// which are those paths on which every one of the constraints holds infinitely often.
+    /// The constraints themselves are evaluated as normal CTL formulas.
+    ///
+    /// States that have no fair path starting in them vacuously satisfy all universal formulas,
+    /// much like sink states without fairness.
+    pub fn with_fairness(model: Model, constraints: &[CTLFormula]) -> Self {
+        let mut unfair = Self::new(model);
+        let constraints: Vec<StateSet> = constraints
+            .iter()
+            .map(|c| unfair.sat_idx(c).clone())
+            .collect();
+        let all = StateSet::full(unfair.model.nr_states());
+        let fair = sat_eg_fair(&unfair.model, &all, &constraints);
+        Self {
+            model: unfair.model,
+            cache: HashMap::new(),
+            fairness: Some(Fairness { constraints, fair }),
+            pruned: 0,
+            trace: None,
         }
     }
+    /// Remove all states that cannot be reached from an initial state from the model,
+    /// which can make checking much cheaper, and returns the number of states removed.
+    /// As CTL formulas only talk about the future, this does not change whether they hold
+    /// in the remaining states, so results that were already computed are kept.
+    pub fn restrict_to_reachable(&mut self) -> usize {
+        let reachable = self.model.reachable_idx();
+        let removed = self.model.nr_states() - reachable.len();
+        if removed == 0 {
+            return 0;
+        }
+        // The reachable states keep their order, so the i'th one is the new state i.
+        let restrict = |set: &StateSet| {
+            let kept = reachable
+                .iter()
+                .enumerate()
+                .filter(|(_, &old)| set.contains(old));
+            StateSet::from_indexes(reachable.len(), kept.map(|(new, _)| new))
+        };
+        for set in self.cache.values_mut() {
+            *set = restrict(set);
+        }
+        if let Some(fairness) = &mut self.fairness {
+            fairness.constraints = fairness.constraints.iter().map(restrict).collect();
+            fairness.fair = restrict(&fairness.fair);
+        }
+        self.model = self.model.reachable();
+        self.pruned += removed;
+        removed
+    }
+    /// The number of states removed from the model by `restrict_to_reachable`.
+    pub fn nr_pruned(&self) -> usize {
+        self.pruned
+    }
+    /// Start or stop recording every rewrite and every iteration of the fixpoint computations,
+    /// which can then be taken out with `take_trace`.
+    pub fn set_tracing(&mut self, enabled: bool) {
+        self.trace = match enabled {
+            true => self.trace.take().or(Some(Vec::new())),
+            false => None,
+        };
+    }
+    /// The steps recorded since tracing was enabled, or since the last call, in the order in
+    /// which they happened. As results are cached, every subformula is only computed once,
+    /// so only the first formula that needs it shows its steps.
+    pub fn take_trace(&mut self) -> Vec<TraceStep> {
+        self.trace.as_mut().map(std::mem::take).unwrap_or_default()
+    }
+    pub fn is_fair(&self) -> bool {
+        self.fairness.is_some()
+    }
     pub fn get_model(&self) -> &Model {
         &self.model
     }
     pub fn check(&mut self, formula: &CTLFormula) -> HashSet<String> {
+        self.fill_cache(formula);
+        self.to_names(&self.cache[formula])
+    }
+    /// For every initial state of the model, whether the formula holds in it.
+    pub fn holds_initially(&mut self, formula: &CTLFormula) -> HashMap<String, bool> {
+        self.fill_cache(formula);
+        let sat = &self.cache[formula];
+        self.model
+            .get_initial_idx()
+            .iter()
+            .map(|&i| (self.model.get_state_by_idx(i).name(), sat.contains(i)))
+            .collect()
+    }
+    fn to_names(&self, set: &StateSet) -> HashSet<String> {
+        set.iter()
+            .map(|i| self.model.get_state_by_idx(i).name())
+            .collect()
+    }
+    fn sat_idx(&mut self, formula: &CTLFormula) -> &StateSet {
+        self.fill_cache(formula);
+        &self.cache[formula]
+    }
+    fn fill_cache(&mut self, formula: &CTLFormula) {
+        if self.cache.contains_key(formula) {
+            return;
+        }
         // This function fights the borrow checker a lot.
         // There might be a better way to do it, as I'm not _that_ good at rust,
         // but I tried quite a few things already.
 
         // We need to clone the current cache because we want to modify it
         // Otherwise we would have two mutable references out to self.
-        let map: HashMap<&CTLFormula, &HashSet<usize>> =
+        let map: HashMap<&CTLFormula, &StateSet> =
             self.cache.iter().map(|(k, v)| (k.as_ref(), v)).collect();
 
         // We need to create an arena because we want to memoise
         // and memoising without arena would mean storing in the hashmap,
         // wich might get realocated and thus invalidating pointers.
         // We cannot own the arenea, because we want Clone to be implemented for Python.
-        let result_arena: Arena<HashSet<usize>> = Arena::new();
+        let result_arena: Arena<StateSet> = Arena::new();
         let formula_arena: Arena<CTLFormula> = Arena::new();
 
         // Once this is in place, we can create the solver, solve,
         // and get the reference map out of it again,
         // which needs to be cloned because it won't live as long as self
-        // We can perform this clone while transforming it from indexes to names.
         let mut solver = CTLCheckerInner {
             map,
             result_arena: &result_arena,
             formula_arena: &formula_arena,
+            fairness: self.fairness.as_ref(),
+            trace: self.trace.as_ref().map(|_| Vec::new()),
         };
-        let ret = self.model.get_names(solver.check(formula, &self.model));
+        let ret = solver.check(formula, &self.model).clone();
+        let steps: Vec<TraceStep> = solver
+            .trace
+            .take()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|step| match step {
+                RawStep::Rewrite(formula, rewritten) => TraceStep::Rewrite {
+                    formula: Box::new(formula),
+                    rewritten: Box::new(rewritten),
+                },
+                RawStep::Iteration(formula, iteration, set) => TraceStep::Iteration {
+                    formula: Box::new(formula),
+                    iteration,
+                    states: self.to_names(&set),
+                },
+            })
+            .collect();
 
-        let cache_update: HashMap<Box<CTLFormula>, HashSet<usize>> = solver
+        let cache_update: HashMap<Box<CTLFormula>, StateSet> = solver
             .map()
             .iter()
             // We only need to take the value out of it wasn't in cache already
@@ -233,16 +451,193 @@ impl CTLChecker {
             .collect();
 
         // Once everything is cloned and `solver` (with its reference to map) is dropped,
-        // we can extend the self cache and return.
+        // we can extend the self cache. The formula itself is added explicitly,
+        // as the solver only memoises the re-written version of some formulas.
         self.cache.extend(cache_update);
-        ret
+        self.cache.insert(Box::new(formula.clone()), ret);
+        if let Some(trace) = &mut self.trace {
+            trace.extend(steps);
+        }
+    }
+
+    /// A path starting in `state` showing that the existential formula holds there:
+    /// A finite path for `EX`, `EF`, and `EU`, and a lasso for `EG`,
+    /// or, without fairness, a finite path ending in a sink state for `EG`.
+    /// Returns `None` if the formula is not one of these, or if it does not hold in the state.
+    pub fn witness(&mut self, state: &str, formula: &CTLFormula) -> Option<Path> {
+        use CTLFormula as F;
+        let start = self.model.get_idx(state)?;
+        if !self.sat_idx(formula).contains(start) {
+            return None;
+        }
+        // We make sure everything we need is in cache first,
+        // so we can borrow from it while walking through the model.
+        match formula {
+            F::EX(inner) | F::EF(inner) | F::EG(inner) => self.fill_cache(inner),
+            F::EU(lhs, rhs) => {
+                self.fill_cache(lhs);
+                self.fill_cache(rhs);
+            }
+            _ => return None,
+        }
+        let post = self.model.get_post_idx();
+        // Under fairness, finite witnesses have to end in a state from which a fair path continues.
+        let is_fair = |i: usize| self.fairness.as_ref().is_none_or(|f| f.fair.contains(i));
+        let (prefix, cycle) = match formula {
+            F::EX(inner) => {
+                let sat = &self.cache[inner.as_ref()];
+                let next = post[start]
+                    .iter()
+                    .find(|&&i| sat.contains(i) && is_fair(i))?;
+                (vec![start, *next], Vec::new())
+            }
+            F::EF(inner) => {
+                let sat = &self.cache[inner.as_ref()];
+                let path =
+                    shortest_path(post, [start], |i| sat.contains(i) && is_fair(i), |_| true)?;
+                (path, Vec::new())
+            }
+            F::EU(lhs, rhs) => {
+                let (lhs_sat, rhs_sat) = (&self.cache[lhs.as_ref()], &self.cache[rhs.as_ref()]);
+                let path = shortest_path(
+                    post,
+                    [start],
+                    |i| rhs_sat.contains(i) && is_fair(i),
+                    |i| lhs_sat.contains(i) || rhs_sat.contains(i),
+                )?;
+                (path, Vec::new())
+            }
+            // We look for a loop within the states satisfying φ, which under fairness
+            // also has to go through every one of the fairness constraints.
+            // Without fairness, a path ending in a sink state within φ works just as well.
+            F::EG(inner) => {
+                let sat = &self.cache[inner.as_ref()];
+                let constraints = self.fairness.as_ref().map_or(&[][..], |f| &f.constraints);
+                let lasso = fair_lasso(
+                    post,
+                    [start],
+                    |i| sat.contains(i),
+                    constraints.len(),
+                    |k, i| constraints[k].contains(i),
+                );
+                match lasso {
+                    Some(lasso) => lasso,
+                    None if self.fairness.is_none() => {
+                        let is_sink = |i: usize| post[i].is_empty();
+                        let in_sat = |i: usize| sat.contains(i);
+                        let path =
+                            shortest_path(post, [start], |i| in_sat(i) && is_sink(i), in_sat)?;
+                        (path, Vec::new())
+                    }
+                    None => return None,
+                }
+            }
+            _ => unreachable!(),
+        };
+        let to_names = |path: Vec<usize>| {
+            path.into_iter()
+                .map(|i| self.model.get_state_by_idx(i).name())
+                .collect()
+        };
+        if cycle.is_empty() {
+            Some(Path::Finite(to_names(prefix)))
+        } else {
+            Some(Path::Infinite(Lasso {
+                prefix: to_names(prefix),
+                cycle: to_names(cycle),
+            }))
+        }
+    }
+    /// A path starting in `state` showing that the universal formula does not hold there:
+    /// A finite path for `AX` and `AG`, a lasso (or a path to a sink) for `AF`, and either one for `AU`.
+    /// Returns `None` if the formula is not one of these, or if it does hold in the state.
+    pub fn counterexample(&mut self, state: &str, formula: &CTLFormula) -> Option<Path> {
+        use CTLFormula as F;
+        let start = self.model.get_idx(state)?;
+        if self.sat_idx(formula).contains(start) {
+            return None;
+        }
+        // A counterexample to a universal formula is a witness for its existential dual.
+        let dual = match formula {
+            F::AX(inner) => F::EX(f::neg!(inner.clone())),
+            F::AF(inner) => F::EG(f::neg!(inner.clone())),
+            F::AG(inner) => F::EF(f::neg!(inner.clone())),
+            F::AU(lhs, rhs) => {
+                let until = F::EU(
+                    f::neg!(rhs.clone()),
+                    f::and!(f::neg!(lhs.clone()), f::neg!(rhs.clone())),
+                );
+                if self.sat_idx(&until).contains(start) {
+                    until
+                } else {
+                    F::EG(f::neg!(rhs.clone()))
+                }
+            }
+            _ => return None,
+        };
+        self.witness(state, &dual)
     }
 
     // This function is only there so I can play with the checker from python,
     // and insert different algorithms.
+    #[cfg(feature = "python")]
     pub(super) fn update_cache(&mut self, formula: CTLFormula, res: HashSet<String>) -> Option<()> {
         let indexes = self.model.get_idxs(&res)?;
-        self.cache.insert(Box::new(formula), indexes);
+        let set = StateSet::from_indexes(self.model.nr_states(), indexes);
+        self.cache.insert(Box::new(formula), set);
         Some(())
     }
 }
+
+/// The states from which there is a path staying within `states` forever.
+/// Such a path has to end up in some loop (a non-trivial SCC) within `states`,
+/// or, as paths stop at sink states, in a sink within `states`.
+/// Finding the SCCs and walking back from them is linear in the size of the model,
+/// unlike the fixpoint iteration of the textbook algorithm.
+fn sat_eg(model: &Model, states: &StateSet) -> StateSet {
+    let post = model.get_post_idx();
+    let loops = tarjan_scc(post, |i| states.contains(i))
+        .into_iter()
+        .filter(|scc| is_nontrivial(scc, post))
+        .flatten();
+    let sinks = states.iter().filter(|&i| post[i].is_empty());
+    backward_reach(model, states, loops.chain(sinks))
+}
+
+/// The states from which there is a path staying within `states` forever,
+/// visiting each of the constraint sets infinitely often. This is done by finding
+/// the loops (SCCs) within `states` that go through all the constraints,
+/// and then looking for all states that can reach those without leaving `states`.
+/// Fair paths are infinite, so unlike for normal EG, sink states never count.
+fn sat_eg_fair(model: &Model, states: &StateSet, constraints: &[StateSet]) -> StateSet {
+    let post = model.get_post_idx();
+    let fair_loops = tarjan_scc(post, |i| states.contains(i))
+        .into_iter()
+        .filter(|scc| {
+            is_nontrivial(scc, post)
+                && constraints
+                    .iter()
+                    .all(|c| scc.iter().any(|&i| c.contains(i)))
+        })
+        .flatten();
+    backward_reach(model, states, fair_loops)
+}
+
+/// All states that can reach one of the `targets` without leaving `within`,
+/// including the targets themselves.
+fn backward_reach(
+    model: &Model,
+    within: &StateSet,
+    targets: impl IntoIterator<Item = usize>,
+) -> StateSet {
+    let mut ret = StateSet::empty(model.nr_states());
+    let mut queue: Vec<usize> = targets.into_iter().filter(|&i| ret.insert(i)).collect();
+    while let Some(idx) = queue.pop() {
+        for &prev in model.get_pre_idx()[idx].iter() {
+            if within.contains(prev) && ret.insert(prev) {
+                queue.push(prev);
+            }
+        }
+    }
+    ret
+}
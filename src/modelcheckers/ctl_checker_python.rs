@@ -1,15 +1,86 @@
 use std::collections::{HashMap, HashSet};
+use std::fmt;
 
-use super::CTLChecker;
+use super::{CTLChecker, Path, TraceStep};
 use crate::formulas::ctl_python::PyCTLFormula;
 use crate::formulas::CTLFormula;
 use crate::models::models_python::PyModel;
 
-use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::exceptions::{PyKeyError, PyRuntimeError, PyValueError};
 use pyo3::prelude::*;
 use pyo3::types::IntoPyDict;
 use pyo3::types::{PyAny, PyTuple};
 
+/// One step of the computation of a CTLChecker created with `trace=True`.
+/// This class is frozen. Objects, once created, cannot be modified.
+///
+/// `kind` is either "rewrite", for a formula that was computed through the equivalent
+/// `rewritten` formula, or "iteration", for the set of `states` after iteration number
+/// `iteration` of the fixpoint computation of an EU or AF `formula`.
+/// The fields that do not apply to the kind of step are None.
+#[pyclass(
+    module = "minictl",
+    name = "TraceStep",
+    get_all,
+    frozen,
+    str,
+    skip_from_py_object
+)]
+#[derive(Debug, Clone)]
+pub struct PyTraceStep {
+    pub kind: String,
+    pub formula: PyCTLFormula,
+    pub rewritten: Option<PyCTLFormula>,
+    pub iteration: Option<usize>,
+    pub states: Option<HashSet<String>>,
+}
+
+impl PyTraceStep {
+    fn from_rust(step: &TraceStep) -> Self {
+        match step {
+            TraceStep::Rewrite { formula, rewritten } => Self {
+                kind: "rewrite".to_string(),
+                formula: PyCTLFormula::from_rust(formula),
+                rewritten: Some(PyCTLFormula::from_rust(rewritten)),
+                iteration: None,
+                states: None,
+            },
+            TraceStep::Iteration {
+                formula,
+                iteration,
+                states,
+            } => Self {
+                kind: "iteration".to_string(),
+                formula: PyCTLFormula::from_rust(formula),
+                rewritten: None,
+                iteration: Some(*iteration),
+                states: Some(states.clone()),
+            },
+        }
+    }
+}
+
+impl fmt::Display for PyTraceStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.rewritten, self.iteration, &self.states) {
+            (Some(rewritten), _, _) => write!(f, "{} ≡ {}", self.formula, rewritten),
+            (_, Some(iteration), Some(states)) => {
+                let mut states: Vec<&String> = states.iter().collect();
+                states.sort();
+                let states: Vec<&str> = states.into_iter().map(String::as_str).collect();
+                write!(
+                    f,
+                    "{} [{}]: {{{}}}",
+                    self.formula,
+                    iteration,
+                    states.join(", ")
+                )
+            }
+            _ => write!(f, "{}", self.formula),
+        }
+    }
+}
+
 /// The Python view into the CTL Checker
 /// Though this class is not frozen, you cannot modify it directly.
 /// The object will update itself on calls of `check` by updating the cache.
@@ -27,6 +98,20 @@ pub struct PyCTLChecker {
 }
 
 impl PyCTLChecker {
+    fn checked_state_formula(
+        &self,
+        state: &str,
+        formula: &PyCTLFormula,
+    ) -> PyResult<Box<CTLFormula>> {
+        if self.inner.get_model().get_state(state).is_none() {
+            return Err(PyKeyError::new_err(format!(
+                "{state} cannot be found in the model"
+            )));
+        }
+        formula.to_rust().ok_or(PyValueError::new_err(
+            "provided formula is not a valid CTL formula",
+        ))
+    }
     fn has_modification(&self, formula: &CTLFormula) -> bool {
         use CTLFormula as F;
         match formula {
@@ -38,6 +123,8 @@ impl PyCTLChecker {
             F::AG(..) => self.modifications.contains_key("AG"),
             F::EU(..) => self.modifications.contains_key("EU"),
             F::AU(..) => self.modifications.contains_key("AU"),
+            F::ER(..) => self.modifications.contains_key("ER"),
+            F::AR(..) => self.modifications.contains_key("AR"),
             _ => false,
         }
     }
@@ -101,6 +188,16 @@ impl PyCTLChecker {
                 let rhs_res = self.inner.check(rhs);
                 self.call_modification(py, "AU", &[lhs_res, rhs_res])
             },
+            F::ER(lhs, rhs) => {
+                let lhs_res = self.inner.check(lhs);
+                let rhs_res = self.inner.check(rhs);
+                self.call_modification(py, "ER", &[lhs_res, rhs_res])
+            },
+            F::AR(lhs, rhs) => {
+                let lhs_res = self.inner.check(lhs);
+                let rhs_res = self.inner.check(rhs);
+                self.call_modification(py, "AR", &[lhs_res, rhs_res])
+            },
             _ => Err(PyRuntimeError::new_err("Called modification on something that cannot recieve one. This is likely an internal error."))
         }
     }
@@ -109,13 +206,38 @@ impl PyCTLChecker {
 #[pymethods]
 impl PyCTLChecker {
     #[new]
-    fn new(model: PyModel) -> Self {
-        Self {
-            inner: CTLChecker::new(model.to_rust()),
-            pymodel: model,
+    #[pyo3(signature = (model, *, fairness = None, reachable_only = false, trace = false))]
+    fn new(
+        model: PyModel,
+        fairness: Option<Vec<PyCTLFormula>>,
+        reachable_only: bool,
+        trace: bool,
+    ) -> PyResult<Self> {
+        let mut inner = match fairness {
+            Some(constraints) => {
+                let constraints = constraints
+                    .iter()
+                    .map(|c| c.to_rust().map(|c| *c))
+                    .collect::<Option<Vec<CTLFormula>>>()
+                    .ok_or(PyValueError::new_err(
+                        "provided fairness constraint is not a valid CTL formula",
+                    ))?;
+                CTLChecker::with_fairness(model.to_rust(), &constraints)
+            }
+            None => CTLChecker::new(model.to_rust()),
+        };
+        inner.set_tracing(trace);
+        // The custom algorithms get the model passed in, which has to be the restricted one.
+        let pymodel = match reachable_only && inner.restrict_to_reachable() > 0 {
+            true => PyModel::from_rust(inner.get_model().clone()),
+            false => model,
+        };
+        Ok(Self {
+            inner,
+            pymodel,
             modifications: HashMap::new(),
             called: false,
-        }
+        })
     }
     #[pyo3(signature = (formula, *, debug = false))]
     fn check(
@@ -151,6 +273,58 @@ impl PyCTLChecker {
         // we can just return inner.check() and expect it to be the modified values.
         Ok(self.inner.check(&rsformula))
     }
+    fn holds_initially(
+        &mut self,
+        py: Python,
+        formula: PyCTLFormula,
+    ) -> PyResult<HashMap<String, bool>> {
+        let states = self.check(py, formula, false)?;
+        Ok(self
+            .inner
+            .get_model()
+            .all_initial()
+            .into_iter()
+            .map(|name| {
+                let holds = states.contains(&name);
+                (name, holds)
+            })
+            .collect())
+    }
+    fn nr_pruned(&self) -> usize {
+        self.inner.nr_pruned()
+    }
+    fn take_trace(&mut self) -> Vec<PyTraceStep> {
+        self.inner
+            .take_trace()
+            .iter()
+            .map(PyTraceStep::from_rust)
+            .collect()
+    }
+    fn witness(
+        &mut self,
+        py: Python,
+        state: &str,
+        formula: PyCTLFormula,
+    ) -> PyResult<Option<(Vec<String>, Vec<String>)>> {
+        let rsformula = self.checked_state_formula(state, &formula)?;
+        // Checking first makes sure any custom algorithms are applied,
+        // which the paths are then based on.
+        self.check(py, formula, false)?;
+        Ok(self.inner.witness(state, &rsformula).map(path_to_python))
+    }
+    fn counterexample(
+        &mut self,
+        py: Python,
+        state: &str,
+        formula: PyCTLFormula,
+    ) -> PyResult<Option<(Vec<String>, Vec<String>)>> {
+        let rsformula = self.checked_state_formula(state, &formula)?;
+        self.check(py, formula, false)?;
+        Ok(self
+            .inner
+            .counterexample(state, &rsformula)
+            .map(path_to_python))
+    }
     fn set_custom(&mut self, target: String, func: Py<PyAny>) -> PyResult<()> {
         if self.called {
             return Err(PyValueError::new_err(
@@ -158,8 +332,14 @@ impl PyCTLChecker {
                 Instead, create a new CTLChecker with the `.get_model()` from this one.",
             ));
         }
+        if self.inner.is_fair() {
+            return Err(PyValueError::new_err(
+                "Cannot set modification on a checker with fairness constraints,
+                as the custom algorithms would not take those into account.",
+            ));
+        }
         match target.as_str() {
-            "EX" | "AX" | "EF" | "AF" | "EG" | "AG" | "EU" | "AU" => {
+            "EX" | "AX" | "EF" | "AF" | "EG" | "AG" | "EU" | "AU" | "ER" | "AR" => {
                 self.modifications.insert(target, func);
                 Ok(())
             }
@@ -171,11 +351,22 @@ impl PyCTLChecker {
     fn is_modified(&self) -> bool {
         !self.modifications.is_empty()
     }
+    fn is_fair(&self) -> bool {
+        self.inner.is_fair()
+    }
     fn get_model(&self) -> PyModel {
         self.pymodel.clone()
     }
 }
 
+// Finite paths are returned with an empty loop, so both kinds fit in the same tuple.
+fn path_to_python(path: Path) -> (Vec<String>, Vec<String>) {
+    match path {
+        Path::Finite(path) => (path, Vec::new()),
+        Path::Infinite(lasso) => (lasso.prefix, lasso.cycle),
+    }
+}
+
 // It's nice to have good errors when working on algorithms.
 fn compare_sets(
     res: &HashSet<String>,
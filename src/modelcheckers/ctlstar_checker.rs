@@ -0,0 +1,167 @@
+use std::collections::{HashMap, HashSet};
+
+use super::ltl_checker::violating_idx;
+use crate::formulas::{CTLStarFormula, LTLFormula, LTLVariable};
+use crate::models::Model;
+
+// CTL* is checked by combining the CTL labelling with the LTL checker, as in Emerson and Lei.
+// For E ψ or A ψ, we first compute the states satisfying every maximal state subformula of ψ,
+// bottom-up, and label those states with a fresh atom. What is left of ψ is then a plain
+// LTL formula over the relabelled model, which we hand to the LTL checker:
+//     A ψ holds in exactly the states with no path violating ψ
+//     E ψ holds in exactly the states with a path violating ¬ψ
+
+#[derive(Debug, Clone)]
+pub struct CTLStarChecker {
+    model: Model,
+    // Every variable in the model or in a checked formula, so that the fresh atoms we introduce
+    // never clash with them.
+    variables: HashSet<String>,
+    cache: HashMap<Box<CTLStarFormula>, HashSet<usize>>,
+}
+
+impl CTLStarChecker {
+    pub fn new(model: Model) -> Self {
+        let variables = model
+            .get_states()
+            .iter()
+            .flat_map(|s| s.vars().iter().cloned())
+            .collect();
+        Self {
+            model,
+            variables,
+            cache: HashMap::new(),
+        }
+    }
+    pub fn get_model(&self) -> &Model {
+        &self.model
+    }
+    /// The set of states in which the formula holds.
+    /// A formula that is not a state formula, like `G F p`, is checked over all paths,
+    /// as if it was written `A(G F p)`, which is the same as what the LTL checker does.
+    ///
+    /// Path formulas are evaluated over infinite paths only, like in the LTL checker.
+    /// This means that in a state from which every path ends up in a sink, `A ψ` vacuously
+    /// holds and `E ψ` does not, which differs from the CTL checker on models with sinks.
+    pub fn check(&mut self, formula: &CTLStarFormula) -> HashSet<String> {
+        let indexes = self.sat_state(formula);
+        self.model.get_names(&indexes)
+    }
+    /// Whether the formula holds in all initial states of the model.
+    pub fn holds(&mut self, formula: &CTLStarFormula) -> bool {
+        let indexes = self.sat_state(formula);
+        self.model
+            .all_initial_idx()
+            .iter()
+            .all(|i| indexes.contains(i))
+    }
+    fn sat_state(&mut self, formula: &CTLStarFormula) -> HashSet<usize> {
+        self.add_variables(formula);
+        match formula.is_state_formula() {
+            true => self.sat(formula),
+            false => self.sat(&CTLStarFormula::A(Box::new(formula.clone()))),
+        }
+    }
+    fn add_variables(&mut self, formula: &CTLStarFormula) {
+        if let CTLStarFormula::Atomic(var) = formula {
+            self.variables.insert(var.inner.clone());
+        }
+        formula.for_each_child(|child| self.add_variables(child));
+    }
+    fn fresh_variable(&self, labels: &[(String, HashSet<usize>)]) -> String {
+        (0..)
+            .map(|i| format!("__ctlstar_{i}"))
+            .find(|name| {
+                !self.variables.contains(name) && labels.iter().all(|(label, _)| label != name)
+            })
+            .expect("There are infinitely many names to pick from")
+    }
+    /// Turn the path formula into an LTL formula, replacing every path quantifier in it
+    /// by a fresh atom that is added to `labels` together with the states in which it holds.
+    fn path_to_ltl(
+        &mut self,
+        formula: &CTLStarFormula,
+        labels: &mut Vec<(String, HashSet<usize>)>,
+    ) -> Box<LTLFormula> {
+        use CTLStarFormula as F;
+        use LTLFormula as L;
+        let ret = match formula {
+            F::Top => L::Top,
+            F::Bot => L::Bot,
+            F::Atomic(var) => L::Atomic(LTLVariable::new(var.inner.clone())),
+            F::E(_) | F::A(_) => {
+                let indexes = self.sat(formula);
+                let name = self.fresh_variable(labels);
+                labels.push((name.clone(), indexes));
+                L::Atomic(LTLVariable::new(name))
+            }
+            F::Neg(inner) => L::Neg(self.path_to_ltl(inner, labels)),
+            F::X(inner) => L::X(self.path_to_ltl(inner, labels)),
+            F::F(inner) => L::F(self.path_to_ltl(inner, labels)),
+            F::G(inner) => L::G(self.path_to_ltl(inner, labels)),
+            F::And(lhs, rhs) => {
+                L::And(self.path_to_ltl(lhs, labels), self.path_to_ltl(rhs, labels))
+            }
+            F::Or(lhs, rhs) => L::Or(self.path_to_ltl(lhs, labels), self.path_to_ltl(rhs, labels)),
+            F::ImpliesR(lhs, rhs) => {
+                L::ImpliesR(self.path_to_ltl(lhs, labels), self.path_to_ltl(rhs, labels))
+            }
+            F::ImpliesL(lhs, rhs) => {
+                L::ImpliesL(self.path_to_ltl(lhs, labels), self.path_to_ltl(rhs, labels))
+            }
+            F::BiImplies(lhs, rhs) => {
+                L::BiImplies(self.path_to_ltl(lhs, labels), self.path_to_ltl(rhs, labels))
+            }
+            F::U(lhs, rhs) => L::U(self.path_to_ltl(lhs, labels), self.path_to_ltl(rhs, labels)),
+            F::W(lhs, rhs) => L::W(self.path_to_ltl(lhs, labels), self.path_to_ltl(rhs, labels)),
+            F::R(lhs, rhs) => L::R(self.path_to_ltl(lhs, labels), self.path_to_ltl(rhs, labels)),
+        };
+        Box::new(ret)
+    }
+    fn sat(&mut self, formula: &CTLStarFormula) -> HashSet<usize> {
+        if let Some(ret) = self.cache.get(formula) {
+            return ret.clone();
+        }
+        use CTLStarFormula as F;
+        let ret = match formula {
+            F::Top => self.model.all_except_idx(&HashSet::new()),
+            F::Bot => HashSet::new(),
+            F::Atomic(var) => self.model.all_containing_idx(&var.inner),
+            F::Neg(inner) => {
+                let inner = self.sat(inner);
+                self.model.all_except_idx(&inner)
+            }
+            F::And(lhs, rhs) => &self.sat(lhs) & &self.sat(rhs),
+            F::Or(lhs, rhs) => &self.sat(lhs) | &self.sat(rhs),
+            F::ImpliesR(lhs, rhs) => {
+                let not_lhs = self.sat(&F::Neg(lhs.clone()));
+                &not_lhs | &self.sat(rhs)
+            }
+            F::ImpliesL(lhs, rhs) => {
+                let not_rhs = self.sat(&F::Neg(rhs.clone()));
+                &self.sat(lhs) | &not_rhs
+            }
+            F::BiImplies(lhs, rhs) => {
+                let (lhs, rhs) = (self.sat(lhs), self.sat(rhs));
+                let differ = &lhs ^ &rhs;
+                self.model.all_except_idx(&differ)
+            }
+            F::A(path) => {
+                let mut labels = Vec::new();
+                let ltl = self.path_to_ltl(path, &mut labels);
+                let violating = violating_idx(&self.model.with_labels(&labels), &ltl);
+                self.model.all_except_idx(&violating)
+            }
+            F::E(path) => {
+                let mut labels = Vec::new();
+                let ltl = self.path_to_ltl(path, &mut labels);
+                violating_idx(&self.model.with_labels(&labels), &LTLFormula::Neg(ltl))
+            }
+            F::X(_) | F::F(_) | F::G(_) | F::U(..) | F::W(..) | F::R(..) => {
+                unreachable!("Path formulas only get checked under a path quantifier")
+            }
+        };
+        self.cache.insert(Box::new(formula.clone()), ret.clone());
+        ret
+    }
+}
@@ -0,0 +1,47 @@
+use std::collections::HashSet;
+
+use super::CTLStarChecker;
+use crate::formulas::ctlstar_python::PyCTLStarFormula;
+use crate::models::models_python::PyModel;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// The Python view into the CTL* Checker
+/// Though this class is not frozen, you cannot modify it directly.
+/// The object will update itself on calls of `check` by updating the cache.
+///
+/// In Python, you can create this class from a model with the
+/// CTLStarChecker(model) constructor.
+#[pyclass(module = "minictl", name = "CTLStarChecker")]
+#[derive(Debug)]
+pub struct PyCTLStarChecker {
+    pymodel: PyModel,
+    inner: CTLStarChecker,
+}
+
+#[pymethods]
+impl PyCTLStarChecker {
+    #[new]
+    fn new(model: PyModel) -> Self {
+        Self {
+            inner: CTLStarChecker::new(model.to_rust()),
+            pymodel: model,
+        }
+    }
+    fn check(&mut self, formula: PyCTLStarFormula) -> PyResult<HashSet<String>> {
+        let rsformula = formula.to_rust().ok_or(PyValueError::new_err(
+            "provided formula is not a valid CTL* formula",
+        ))?;
+        Ok(self.inner.check(&rsformula))
+    }
+    fn holds(&mut self, formula: PyCTLStarFormula) -> PyResult<bool> {
+        let rsformula = formula.to_rust().ok_or(PyValueError::new_err(
+            "provided formula is not a valid CTL* formula",
+        ))?;
+        Ok(self.inner.holds(&rsformula))
+    }
+    fn get_model(&self) -> PyModel {
+        self.pymodel.clone()
+    }
+}
@@ -0,0 +1,296 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::formulas::{CTLFormula, CTLVariable};
+use crate::models::Model;
+
+// Bisimulation and simulation between two models, as the greatest relation on pairs of states,
+// computed by removing pairs that cannot be related until nothing changes. To do that without
+// rescanning all pairs, we count for every pair (s, t) how many successors of t are still
+// related to a given successor of s, and the other way around. Once such a count drops to zero,
+// all pairs that depended on it are removed.
+//
+// Every removed pair remembers why it was removed, which only ever refers to pairs that were
+// removed before it. That makes it possible to build a distinguishing formula afterwards,
+// by recursion on those reasons, as in the proof of the Hennessy–Milner theorem:
+//     - If s and t disagree on a variable, that variable tells them apart.
+//     - If s has a successor s' that is unrelated to every successor of t, then
+//       EX of the conjunction of the formulas for (s', t') holds in s but not t.
+//     - If t has a successor t' that is unrelated to every successor of s, then
+//       AX of the disjunction of the formulas for (s', t') holds in s but not t.
+// For simulation, only the second case applies, and the formulas are reversed: they hold in t,
+// but not in s. Those only use AX, disjunction, and negations of variables, so they are in ACTL,
+// which is exactly the fragment of CTL that is preserved from a model to all models it simulates.
+
+#[derive(Debug, Clone, Copy)]
+enum Reason {
+    Variables,
+    // A successor of the left state that is related to no successor of the right one.
+    Forth(usize),
+    // A successor of the right state that is related to no successor of the left one.
+    Back(usize),
+}
+
+struct Refinement<'a> {
+    lhs: &'a Model,
+    rhs: &'a Model,
+    // Whether both directions have to be matched, or only successors of the left state.
+    both_ways: bool,
+    // Indexed by `s * nr_rhs + t`, None if the pair is still related.
+    removed: Vec<Option<Reason>>,
+    // For s' and t, the number of successors t' of t with (s', t') related.
+    forth: Vec<usize>,
+    // For s and t', the number of successors s' of s with (s', t') related.
+    back: Vec<usize>,
+    worklist: Vec<(usize, usize)>,
+}
+
+impl<'a> Refinement<'a> {
+    fn new(lhs: &'a Model, rhs: &'a Model, both_ways: bool) -> Self {
+        let (nr_lhs, nr_rhs) = (lhs.nr_states(), rhs.nr_states());
+        let mut removed = vec![None; nr_lhs * nr_rhs];
+        for s in 0..nr_lhs {
+            for t in 0..nr_rhs {
+                if lhs.get_state_by_idx(s).vars() != rhs.get_state_by_idx(t).vars() {
+                    removed[s * nr_rhs + t] = Some(Reason::Variables);
+                }
+            }
+        }
+        let mut ret = Self {
+            lhs,
+            rhs,
+            both_ways,
+            removed,
+            forth: vec![0; nr_lhs * nr_rhs],
+            back: vec![0; nr_lhs * nr_rhs],
+            worklist: Vec::new(),
+        };
+        let (lhs_post, rhs_post) = (lhs.get_post_idx(), rhs.get_post_idx());
+        for (s, s_post) in lhs_post.iter().enumerate() {
+            for (t, t_post) in rhs_post.iter().enumerate() {
+                let forth = t_post.iter().filter(|&&t2| ret.related(s, t2)).count();
+                ret.forth[s * nr_rhs + t] = forth;
+                let back = s_post.iter().filter(|&&s2| ret.related(s2, t)).count();
+                ret.back[s * nr_rhs + t] = back;
+            }
+        }
+        for (s, s_post) in lhs_post.iter().enumerate() {
+            for (t, t_post) in rhs_post.iter().enumerate() {
+                if !ret.related(s, t) {
+                    continue;
+                }
+                if let Some(&s2) = s_post.iter().find(|&&s2| ret.forth[s2 * nr_rhs + t] == 0) {
+                    ret.remove(s, t, Reason::Forth(s2));
+                } else if let Some(&t2) = t_post
+                    .iter()
+                    .find(|&&t2| both_ways && ret.back[s * nr_rhs + t2] == 0)
+                {
+                    ret.remove(s, t, Reason::Back(t2));
+                }
+            }
+        }
+        ret
+    }
+    fn related(&self, s: usize, t: usize) -> bool {
+        self.removed[s * self.rhs.nr_states() + t].is_none()
+    }
+    fn remove(&mut self, s: usize, t: usize, reason: Reason) {
+        self.removed[s * self.rhs.nr_states() + t] = Some(reason);
+        self.worklist.push((s, t));
+    }
+    fn run(&mut self) {
+        let nr_rhs = self.rhs.nr_states();
+        let (lhs_pre, rhs_pre) = (self.lhs.get_pre_idx(), self.rhs.get_pre_idx());
+        while let Some((s2, t2)) = self.worklist.pop() {
+            for &t in &rhs_pre[t2] {
+                self.forth[s2 * nr_rhs + t] -= 1;
+                if self.forth[s2 * nr_rhs + t] == 0 {
+                    for &s in &lhs_pre[s2] {
+                        if self.related(s, t) {
+                            self.remove(s, t, Reason::Forth(s2));
+                        }
+                    }
+                }
+            }
+            if !self.both_ways {
+                continue;
+            }
+            for &s in &lhs_pre[s2] {
+                self.back[s * nr_rhs + t2] -= 1;
+                if self.back[s * nr_rhs + t2] == 0 {
+                    for &t in &rhs_pre[t2] {
+                        if self.related(s, t) {
+                            self.remove(s, t, Reason::Back(t2));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    fn relation(&self) -> HashSet<(String, String)> {
+        let nr_rhs = self.rhs.nr_states();
+        (0..self.removed.len())
+            .filter(|&i| self.removed[i].is_none())
+            .map(|i| {
+                (
+                    self.lhs.get_state_by_idx(i / nr_rhs).name(),
+                    self.rhs.get_state_by_idx(i % nr_rhs).name(),
+                )
+            })
+            .collect()
+    }
+    /// The first initial state on one side that is related to no initial state on the other,
+    /// with whether it is a state of the left model.
+    fn unmatched_initial(&self) -> Option<(usize, bool)> {
+        let (lhs_initial, rhs_initial) = (self.lhs.get_initial_idx(), self.rhs.get_initial_idx());
+        if let Some(&s) = lhs_initial
+            .iter()
+            .find(|&&s| !rhs_initial.iter().any(|&t| self.related(s, t)))
+        {
+            return Some((s, true));
+        }
+        if !self.both_ways {
+            return None;
+        }
+        rhs_initial
+            .iter()
+            .find(|&&t| !lhs_initial.iter().any(|&s| self.related(s, t)))
+            .map(|&t| (t, false))
+    }
+    /// A formula that holds in s but not in t, for a pair removed from a bisimulation,
+    /// or the other way around, for a pair removed from a simulation.
+    fn distinguish(
+        &self,
+        s: usize,
+        t: usize,
+        cache: &mut HashMap<(usize, usize), Box<CTLFormula>>,
+    ) -> Box<CTLFormula> {
+        if let Some(ret) = cache.get(&(s, t)) {
+            return ret.clone();
+        }
+        let reason = self.removed[s * self.rhs.nr_states() + t]
+            .expect("Only pairs that are not related can be distinguished");
+        let ret = match reason {
+            Reason::Variables => {
+                let (lhs_vars, rhs_vars) = (
+                    self.lhs.get_state_by_idx(s).vars(),
+                    self.rhs.get_state_by_idx(t).vars(),
+                );
+                let (holds, var) = match lhs_vars.symmetric_difference(rhs_vars).min() {
+                    Some(var) => (lhs_vars.contains(var) == self.both_ways, var),
+                    None => unreachable!("Pairs removed for their variables differ in one"),
+                };
+                let atom = Box::new(CTLFormula::Atomic(CTLVariable::new(var.clone())));
+                match holds {
+                    true => atom,
+                    false => Box::new(CTLFormula::Neg(atom)),
+                }
+            }
+            Reason::Forth(s2) => {
+                let parts = self.rhs.get_post_idx()[t]
+                    .iter()
+                    .map(|&t2| self.distinguish(s2, t2, cache));
+                match self.both_ways {
+                    true => Box::new(CTLFormula::EX(conjunction(parts))),
+                    false => Box::new(CTLFormula::AX(disjunction(parts))),
+                }
+            }
+            Reason::Back(t2) => {
+                let parts = self.lhs.get_post_idx()[s]
+                    .iter()
+                    .map(|&s2| self.distinguish(s2, t2, cache));
+                Box::new(CTLFormula::AX(disjunction(parts)))
+            }
+        };
+        cache.insert((s, t), ret.clone());
+        ret
+    }
+}
+
+/// Combine the parts with the binary operator, leaving out duplicates,
+/// or return `empty` if there are no parts at all.
+fn combine(
+    parts: impl Iterator<Item = Box<CTLFormula>>,
+    operator: fn(Box<CTLFormula>, Box<CTLFormula>) -> CTLFormula,
+    empty: CTLFormula,
+) -> Box<CTLFormula> {
+    let mut unique = Vec::new();
+    for part in parts {
+        if !unique.contains(&part) {
+            unique.push(part);
+        }
+    }
+    unique
+        .into_iter()
+        .reduce(|lhs, rhs| Box::new(operator(lhs, rhs)))
+        .unwrap_or(Box::new(empty))
+}
+
+fn conjunction(parts: impl Iterator<Item = Box<CTLFormula>>) -> Box<CTLFormula> {
+    combine(parts, CTLFormula::And, CTLFormula::Top)
+}
+
+fn disjunction(parts: impl Iterator<Item = Box<CTLFormula>>) -> Box<CTLFormula> {
+    combine(parts, CTLFormula::Or, CTLFormula::Bot)
+}
+
+/// Whether the initial states of both models are bisimilar: every initial state of the one is
+/// bisimilar to some initial state of the other. Action labels are ignored, as they are in CTL.
+///
+/// If so, this returns the largest bisimulation between them, as pairs of a state on the left
+/// and a state on the right. If not, this returns a CTL formula that holds in all initial states
+/// of exactly one of the two models, which shows how they differ.
+/// It only uses variables, negation, conjunction, disjunction, EX, and AX.
+pub fn bisimulation(
+    lhs: &Model,
+    rhs: &Model,
+) -> Result<HashSet<(String, String)>, Box<CTLFormula>> {
+    let mut refinement = Refinement::new(lhs, rhs, true);
+    refinement.run();
+    let mut cache = HashMap::new();
+    match refinement.unmatched_initial() {
+        None => Ok(refinement.relation()),
+        // Every initial state on the right satisfies the negation of one of these,
+        // while the unmatched state on the left satisfies all of them.
+        Some((s, true)) => {
+            let parts = rhs.get_initial_idx().iter().map(|&t| {
+                let formula = refinement.distinguish(s, t, &mut cache);
+                Box::new(CTLFormula::Neg(formula))
+            });
+            Err(disjunction(parts))
+        }
+        Some((t, false)) => {
+            let parts = lhs
+                .get_initial_idx()
+                .iter()
+                .map(|&s| refinement.distinguish(s, t, &mut cache));
+            Err(disjunction(parts))
+        }
+    }
+}
+
+/// Whether the right model simulates the left one: every initial state on the left is simulated
+/// by some initial state on the right. Action labels are ignored, as they are in CTL.
+///
+/// If so, this returns the largest simulation between them, as pairs of a state on the left and
+/// a state on the right that simulates it. If not, this returns an ACTL formula that holds in all
+/// initial states of the right model, but not in those of the left one. As ACTL formulas that hold
+/// in a model also hold in all models it simulates, this shows why it is not simulated.
+/// The formula only uses variables, their negations, disjunction, and AX.
+///
+/// On models with sinks, that preservation only holds for ACTL formulas without AF and AU,
+/// as the simulating model might still go on where the simulated one has stopped.
+pub fn simulation(lhs: &Model, rhs: &Model) -> Result<HashSet<(String, String)>, Box<CTLFormula>> {
+    let mut refinement = Refinement::new(lhs, rhs, false);
+    refinement.run();
+    let mut cache = HashMap::new();
+    match refinement.unmatched_initial() {
+        None => Ok(refinement.relation()),
+        Some((s, _)) => {
+            let parts = rhs
+                .get_initial_idx()
+                .iter()
+                .map(|&t| refinement.distinguish(s, t, &mut cache));
+            Err(disjunction(parts))
+        }
+    }
+}
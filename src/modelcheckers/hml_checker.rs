@@ -0,0 +1,80 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::formulas::HMLFormula;
+use crate::models::Model;
+
+/// A checker for Hennessy–Milner logic, over the action labels on the transitions of a model.
+/// Unlabelled transitions are never taken by a modality, so on a model without any actions,
+/// every diamond is false and every box is true.
+#[derive(Debug, Clone)]
+pub struct HMLChecker {
+    model: Model,
+    cache: HashMap<Box<HMLFormula>, HashSet<usize>>,
+}
+
+impl HMLChecker {
+    pub fn new(model: Model) -> Self {
+        Self {
+            model,
+            cache: HashMap::new(),
+        }
+    }
+    pub fn get_model(&self) -> &Model {
+        &self.model
+    }
+    /// The set of states in which the formula holds.
+    pub fn check(&mut self, formula: &HMLFormula) -> HashSet<String> {
+        let indexes = self.sat(formula);
+        self.model.get_names(&indexes)
+    }
+    /// Whether the formula holds in all initial states of the model.
+    pub fn holds(&mut self, formula: &HMLFormula) -> bool {
+        let indexes = self.sat(formula);
+        self.model
+            .all_initial_idx()
+            .iter()
+            .all(|i| indexes.contains(i))
+    }
+    fn sat(&mut self, formula: &HMLFormula) -> HashSet<usize> {
+        if let Some(ret) = self.cache.get(formula) {
+            return ret.clone();
+        }
+        use HMLFormula as F;
+        let ret = match formula {
+            F::Top => self.model.all_except_idx(&HashSet::new()),
+            F::Bot => HashSet::new(),
+            F::Atomic(var) => self.model.all_containing_idx(&var.inner),
+            F::Neg(inner) => {
+                let inner = self.sat(inner);
+                self.model.all_except_idx(&inner)
+            }
+            F::And(lhs, rhs) => &self.sat(lhs) & &self.sat(rhs),
+            F::Or(lhs, rhs) => &self.sat(lhs) | &self.sat(rhs),
+            F::ImpliesR(lhs, rhs) => {
+                let not_lhs = self.sat(&F::Neg(lhs.clone()));
+                &not_lhs | &self.sat(rhs)
+            }
+            F::ImpliesL(lhs, rhs) => {
+                let not_rhs = self.sat(&F::Neg(rhs.clone()));
+                &self.sat(lhs) | &not_rhs
+            }
+            F::BiImplies(lhs, rhs) => {
+                let (lhs, rhs) = (self.sat(lhs), self.sat(rhs));
+                let differ = &lhs ^ &rhs;
+                self.model.all_except_idx(&differ)
+            }
+            F::Diamond(action, inner) => {
+                let inner = self.sat(inner);
+                let actions = self.model.get_action_idxs(&HashSet::from([action.clone()]));
+                self.model.pre_e_actions_idx(&inner, &actions)
+            }
+            F::Box(action, inner) => {
+                let inner = self.sat(inner);
+                let actions = self.model.get_action_idxs(&HashSet::from([action.clone()]));
+                self.model.pre_a_actions_idx(&inner, &actions)
+            }
+        };
+        self.cache.insert(Box::new(formula.clone()), ret.clone());
+        ret
+    }
+}
@@ -0,0 +1,47 @@
+use std::collections::HashSet;
+
+use super::HMLChecker;
+use crate::formulas::hml_python::PyHMLFormula;
+use crate::models::models_python::PyModel;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// The Python view into the HMLChecker
+/// Though this class is not frozen, you cannot modify it directly.
+/// The object will update itself on calls of `check` by updating the cache.
+///
+/// In Python, you can create this class from a model with the
+/// HMLChecker(model) constructor.
+#[pyclass(module = "minictl", name = "HMLChecker")]
+#[derive(Debug)]
+pub struct PyHMLChecker {
+    pymodel: PyModel,
+    inner: HMLChecker,
+}
+
+#[pymethods]
+impl PyHMLChecker {
+    #[new]
+    fn new(model: PyModel) -> Self {
+        Self {
+            inner: HMLChecker::new(model.to_rust()),
+            pymodel: model,
+        }
+    }
+    fn check(&mut self, formula: PyHMLFormula) -> PyResult<HashSet<String>> {
+        let rsformula = formula.to_rust().ok_or(PyValueError::new_err(
+            "provided formula is not a valid HML formula",
+        ))?;
+        Ok(self.inner.check(&rsformula))
+    }
+    fn holds(&mut self, formula: PyHMLFormula) -> PyResult<bool> {
+        let rsformula = formula.to_rust().ok_or(PyValueError::new_err(
+            "provided formula is not a valid HML formula",
+        ))?;
+        Ok(self.inner.holds(&rsformula))
+    }
+    fn get_model(&self) -> PyModel {
+        self.pymodel.clone()
+    }
+}
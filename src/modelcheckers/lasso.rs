@@ -0,0 +1,107 @@
+use std::collections::VecDeque;
+
+use super::scc::{is_nontrivial, tarjan_scc};
+
+/// An infinite path through a model, written as a finite prefix
+/// followed by a cycle that is repeated forever.
+///
+/// The path `s1 s2 s3 s4 s3 s4 ...` is represented with `prefix: [s1, s2]`
+/// and `cycle: [s3, s4]`. The cycle is never empty.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Lasso {
+    pub prefix: Vec<String>,
+    pub cycle: Vec<String>,
+}
+
+/// The shortest path from any of the nodes in `from` to a node for which `target` is true,
+/// only going through nodes for which `include` is true.
+/// The returned path contains both the start and the end node.
+pub(crate) fn shortest_path(
+    graph: &[Vec<usize>],
+    from: impl IntoIterator<Item = usize>,
+    target: impl Fn(usize) -> bool,
+    include: impl Fn(usize) -> bool,
+) -> Option<Vec<usize>> {
+    let mut parent: Vec<Option<usize>> = vec![None; graph.len()];
+    let mut seen = vec![false; graph.len()];
+    let mut queue = VecDeque::new();
+    for start in from {
+        if include(start) && !seen[start] {
+            seen[start] = true;
+            queue.push_back(start);
+        }
+    }
+    while let Some(node) = queue.pop_front() {
+        if target(node) {
+            let mut path = vec![node];
+            while let Some(prev) = parent[*path.last().expect("Path is never empty")] {
+                path.push(prev);
+            }
+            path.reverse();
+            return Some(path);
+        }
+        for &next in graph[node].iter() {
+            if include(next) && !seen[next] {
+                seen[next] = true;
+                parent[next] = Some(node);
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+/// A path through a model, used as a witness or counterexample for CTL formulas.
+/// Formulas like `EF p` are witnessed by a finite path, while `EG p` needs an infinite one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Path {
+    Finite(Vec<String>),
+    Infinite(Lasso),
+}
+
+/// A lasso through the part of the graph for which `include` holds, starting in one of the
+/// nodes in `from`, of which the cycle visits every one of the `nr_sets` sets of nodes,
+/// where `in_set(k, node)` tells whether the node is in the k'th set.
+/// This is returned as the prefix and cycle of node indexes.
+pub(crate) fn fair_lasso(
+    graph: &[Vec<usize>],
+    from: impl IntoIterator<Item = usize>,
+    include: impl Fn(usize) -> bool,
+    nr_sets: usize,
+    in_set: impl Fn(usize, usize) -> bool,
+) -> Option<(Vec<usize>, Vec<usize>)> {
+    let mut scc_of = vec![None; graph.len()];
+    for (i, scc) in tarjan_scc(graph, &include).iter().enumerate() {
+        let fair = is_nontrivial(scc, graph)
+            && (0..nr_sets).all(|k| scc.iter().any(|&node| in_set(k, node)));
+        if fair {
+            scc.iter().for_each(|&node| scc_of[node] = Some(i));
+        }
+    }
+    let mut prefix = shortest_path(graph, from, |i| scc_of[i].is_some(), &include)?;
+    let start = prefix.pop().expect("Paths are never empty");
+    let in_scc = |i: usize| scc_of[i] == scc_of[start];
+
+    // Walk from set to set within the loop, and then back to the start.
+    let mut cycle = vec![start];
+    for k in 0..nr_sets {
+        if cycle.iter().any(|&node| in_set(k, node)) {
+            continue;
+        }
+        let current = *cycle.last().expect("Cycle is never empty");
+        let path = shortest_path(graph, [current], |i| in_set(k, i), in_scc)?;
+        cycle.extend(path.into_iter().skip(1));
+    }
+    let current = *cycle.last().expect("Cycle is never empty");
+    let mut back = shortest_path(
+        graph,
+        graph[current].iter().copied(),
+        |i| i == start,
+        in_scc,
+    )?;
+    back.pop();
+    cycle.extend(back);
+    Some((prefix, cycle))
+}
@@ -0,0 +1,405 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use super::lasso::{fair_lasso, Lasso};
+use super::scc::{is_nontrivial, tarjan_scc};
+use crate::formulas::LTLFormula;
+use crate::models::{Model, State};
+
+// LTL is checked the "classic" way: we build a tableau (a generalised Büchi automaton)
+// for the negation of the formula, take the product of that with the model, and look
+// for an accepting run in that product. Any state that starts such a run has a path
+// violating the formula, and all other states satisfy it.
+//
+// The tableau is built from the formula in negation normal form, where all negations
+// are pushed down to the atoms, and only ∧, ∨, X, U, and R are left. This keeps the
+// expansion rules below nice and small.
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum NnfNode {
+    Top,
+    Bot,
+    Literal(String, bool),
+    And(usize, usize),
+    Or(usize, usize),
+    X(usize),
+    U(usize, usize),
+    R(usize, usize),
+}
+
+/// All subformulas of the formula in negation normal form.
+/// These are hash-consed, so we can refer to them by index everywhere else.
+#[derive(Debug, Default)]
+struct Closure {
+    nodes: Vec<NnfNode>,
+    index: HashMap<NnfNode, usize>,
+}
+impl Closure {
+    fn intern(&mut self, node: NnfNode) -> usize {
+        if let Some(&idx) = self.index.get(&node) {
+            return idx;
+        }
+        self.nodes.push(node.clone());
+        self.index.insert(node, self.nodes.len() - 1);
+        self.nodes.len() - 1
+    }
+    fn nnf(&mut self, formula: &LTLFormula, negated: bool) -> usize {
+        use LTLFormula as F;
+        use NnfNode as N;
+        let node = match (formula, negated) {
+            (F::Top, false) | (F::Bot, true) => N::Top,
+            (F::Top, true) | (F::Bot, false) => N::Bot,
+            (F::Atomic(var), _) => N::Literal(var.inner.clone(), !negated),
+            (F::Neg(inner), _) => return self.nnf(inner, !negated),
+            (F::And(lhs, rhs), false) => N::And(self.nnf(lhs, false), self.nnf(rhs, false)),
+            (F::And(lhs, rhs), true) => N::Or(self.nnf(lhs, true), self.nnf(rhs, true)),
+            (F::Or(lhs, rhs), false) => N::Or(self.nnf(lhs, false), self.nnf(rhs, false)),
+            (F::Or(lhs, rhs), true) => N::And(self.nnf(lhs, true), self.nnf(rhs, true)),
+            (F::ImpliesR(lhs, rhs), false) => N::Or(self.nnf(lhs, true), self.nnf(rhs, false)),
+            (F::ImpliesR(lhs, rhs), true) => N::And(self.nnf(lhs, false), self.nnf(rhs, true)),
+            (F::ImpliesL(lhs, rhs), false) => N::Or(self.nnf(lhs, false), self.nnf(rhs, true)),
+            (F::ImpliesL(lhs, rhs), true) => N::And(self.nnf(lhs, true), self.nnf(rhs, false)),
+            (F::BiImplies(lhs, rhs), false) => {
+                let right = N::Or(self.nnf(lhs, true), self.nnf(rhs, false));
+                let left = N::Or(self.nnf(rhs, true), self.nnf(lhs, false));
+                N::And(self.intern(right), self.intern(left))
+            }
+            (F::BiImplies(lhs, rhs), true) => {
+                let right = N::And(self.nnf(lhs, false), self.nnf(rhs, true));
+                let left = N::And(self.nnf(rhs, false), self.nnf(lhs, true));
+                N::Or(self.intern(right), self.intern(left))
+            }
+            // X is its own dual, as every state on an infinite path has a next one.
+            (F::X(inner), _) => N::X(self.nnf(inner, negated)),
+            (F::F(inner), false) => N::U(self.intern(N::Top), self.nnf(inner, false)),
+            (F::F(inner), true) => N::R(self.intern(N::Bot), self.nnf(inner, true)),
+            (F::G(inner), false) => N::R(self.intern(N::Bot), self.nnf(inner, false)),
+            (F::G(inner), true) => N::U(self.intern(N::Top), self.nnf(inner, true)),
+            (F::U(lhs, rhs), false) => N::U(self.nnf(lhs, false), self.nnf(rhs, false)),
+            (F::U(lhs, rhs), true) => N::R(self.nnf(lhs, true), self.nnf(rhs, true)),
+            (F::R(lhs, rhs), false) => N::R(self.nnf(lhs, false), self.nnf(rhs, false)),
+            (F::R(lhs, rhs), true) => N::U(self.nnf(lhs, true), self.nnf(rhs, true)),
+            // φWψ is the same as ψR(φ∨ψ), so its negation is ¬ψU(¬φ∧¬ψ)
+            (F::W(lhs, rhs), false) => {
+                let either = N::Or(self.nnf(lhs, false), self.nnf(rhs, false));
+                N::R(self.nnf(rhs, false), self.intern(either))
+            }
+            (F::W(lhs, rhs), true) => {
+                let neither = N::And(self.nnf(lhs, true), self.nnf(rhs, true));
+                N::U(self.nnf(rhs, true), self.intern(neither))
+            }
+        };
+        self.intern(node)
+    }
+}
+
+/// A node in the tableau: the formulas that have to hold in the current state (`old`),
+/// and the formulas that have to hold in the next state (`next`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+struct TableauNode {
+    old: BTreeSet<usize>,
+    next: BTreeSet<usize>,
+}
+
+#[derive(Debug)]
+struct Tableau {
+    closure: Closure,
+    root: usize,
+    nodes: Vec<TableauNode>,
+    node_idx: HashMap<TableauNode, usize>,
+    expansions: HashMap<BTreeSet<usize>, Vec<usize>>,
+}
+impl Tableau {
+    /// The tableau for the paths that violate the formula.
+    fn for_negation_of(formula: &LTLFormula) -> Self {
+        let mut closure = Closure::default();
+        let root = closure.nnf(formula, true);
+        Self {
+            closure,
+            root,
+            nodes: Vec::new(),
+            node_idx: HashMap::new(),
+            expansions: HashMap::new(),
+        }
+    }
+    fn intern_node(&mut self, node: TableauNode) -> usize {
+        if let Some(&idx) = self.node_idx.get(&node) {
+            return idx;
+        }
+        self.nodes.push(node.clone());
+        self.node_idx.insert(node, self.nodes.len() - 1);
+        self.nodes.len() - 1
+    }
+    fn initial_nodes(&mut self) -> Vec<usize> {
+        self.expand(&BTreeSet::from([self.root]))
+    }
+    fn next_nodes(&mut self, node: usize) -> Vec<usize> {
+        let next = self.nodes[node].next.clone();
+        self.expand(&next)
+    }
+    /// All the ways in which the set of obligations can be fulfilled in a single state,
+    /// each of which becomes a node in the tableau.
+    fn expand(&mut self, obligations: &BTreeSet<usize>) -> Vec<usize> {
+        use NnfNode as N;
+        if let Some(ret) = self.expansions.get(obligations) {
+            return ret.clone();
+        }
+
+        let mut done = Vec::new();
+        // Every branch has a list of formulas still to process, and the node built so far.
+        let mut branches = vec![(
+            obligations.iter().copied().collect::<Vec<usize>>(),
+            TableauNode::default(),
+        )];
+        while let Some((mut todo, mut node)) = branches.pop() {
+            let Some(formula) = todo.pop() else {
+                done.push(node);
+                continue;
+            };
+            if !node.old.insert(formula) {
+                branches.push((todo, node));
+                continue;
+            }
+            match &self.closure.nodes[formula] {
+                N::Top => {}
+                N::Bot => continue,
+                N::Literal(var, value) => {
+                    let negation = N::Literal(var.clone(), !value);
+                    if let Some(idx) = self.closure.index.get(&negation) {
+                        if node.old.contains(idx) {
+                            continue;
+                        }
+                    }
+                }
+                N::And(lhs, rhs) => {
+                    todo.push(*lhs);
+                    todo.push(*rhs);
+                }
+                N::Or(lhs, rhs) => {
+                    let mut other_todo = todo.clone();
+                    other_todo.push(*rhs);
+                    branches.push((other_todo, node.clone()));
+                    todo.push(*lhs);
+                }
+                N::X(inner) => {
+                    node.next.insert(*inner);
+                }
+                // Either the rhs holds now, or the lhs holds now and we try again later.
+                N::U(lhs, rhs) => {
+                    let (mut other_todo, mut other_node) = (todo.clone(), node.clone());
+                    other_todo.push(*lhs);
+                    other_node.next.insert(formula);
+                    branches.push((other_todo, other_node));
+                    todo.push(*rhs);
+                }
+                // The rhs holds now, and either the lhs holds now too, or we try again later.
+                N::R(lhs, rhs) => {
+                    let (mut other_todo, mut other_node) = (todo.clone(), node.clone());
+                    other_todo.push(*rhs);
+                    other_node.next.insert(formula);
+                    branches.push((other_todo, other_node));
+                    todo.push(*lhs);
+                    todo.push(*rhs);
+                }
+            }
+            branches.push((todo, node));
+        }
+
+        let mut ret: Vec<usize> = done.into_iter().map(|n| self.intern_node(n)).collect();
+        ret.sort_unstable();
+        ret.dedup();
+        self.expansions.insert(obligations.clone(), ret.clone());
+        ret
+    }
+    /// Whether the literals the node requires to be true now hold in the state.
+    fn matches(&self, node: usize, state: &State) -> bool {
+        self.nodes[node]
+            .old
+            .iter()
+            .all(|&f| match &self.closure.nodes[f] {
+                NnfNode::Literal(var, value) => state.contains(var) == *value,
+                _ => true,
+            })
+    }
+    /// The acceptance sets of the automaton, one for every until formula:
+    /// A run is only accepting if it does not postpone the rhs of an until forever.
+    fn acceptance_sets(&self) -> Vec<(usize, usize)> {
+        self.closure
+            .nodes
+            .iter()
+            .enumerate()
+            .filter_map(|(i, n)| match n {
+                NnfNode::U(_, rhs) => Some((i, *rhs)),
+                _ => None,
+            })
+            .collect()
+    }
+    fn accepts(&self, (until, rhs): (usize, usize), node: usize) -> bool {
+        let old = &self.nodes[node].old;
+        !old.contains(&until) || old.contains(&rhs)
+    }
+}
+
+/// The product of the model and the tableau. Every product state is a pair of
+/// a state index and a tableau node, such that the state satisfies the literals of the node.
+#[derive(Debug, Default)]
+struct Product {
+    states: Vec<(usize, usize)>,
+    state_idx: HashMap<(usize, usize), usize>,
+    post: Vec<Vec<usize>>,
+    pre: Vec<Vec<usize>>,
+    initial: Vec<usize>,
+}
+impl Product {
+    /// Builds the reachable part of the product, starting from the states given.
+    fn build(model: &Model, tableau: &mut Tableau, from: impl IntoIterator<Item = usize>) -> Self {
+        let mut product = Self::default();
+        let mut queue = Vec::new();
+
+        let initial_nodes = tableau.initial_nodes();
+        for state in from {
+            for &node in initial_nodes.iter() {
+                if tableau.matches(node, model.get_state_by_idx(state)) {
+                    let idx = product.get_or_insert((state, node), &mut queue);
+                    product.initial.push(idx);
+                }
+            }
+        }
+        while let Some(idx) = queue.pop() {
+            let (state, node) = product.states[idx];
+            let next_nodes = tableau.next_nodes(node);
+            for &next_state in model.get_post_idx()[state].iter() {
+                for &next_node in next_nodes.iter() {
+                    if tableau.matches(next_node, model.get_state_by_idx(next_state)) {
+                        let next_idx = product.get_or_insert((next_state, next_node), &mut queue);
+                        product.post[idx].push(next_idx);
+                        product.pre[next_idx].push(idx);
+                    }
+                }
+            }
+        }
+        product
+    }
+    fn get_or_insert(&mut self, pair: (usize, usize), queue: &mut Vec<usize>) -> usize {
+        if let Some(&idx) = self.state_idx.get(&pair) {
+            return idx;
+        }
+        let idx = self.states.len();
+        self.states.push(pair);
+        self.state_idx.insert(pair, idx);
+        self.post.push(Vec::new());
+        self.pre.push(Vec::new());
+        queue.push(idx);
+        idx
+    }
+    /// The loops in the product that visit every acceptance set of the tableau.
+    /// Runs that end up in one of these forever are accepting.
+    fn fair_sccs(&self, tableau: &Tableau) -> Vec<Vec<usize>> {
+        let acceptance_sets = tableau.acceptance_sets();
+        tarjan_scc(&self.post, |_| true)
+            .into_iter()
+            .filter(|scc| {
+                is_nontrivial(scc, &self.post)
+                    && acceptance_sets
+                        .iter()
+                        .all(|&set| scc.iter().any(|&i| tableau.accepts(set, self.states[i].1)))
+            })
+            .collect()
+    }
+    /// Marks all product states from which an accepting run starts:
+    /// those from which one of the fair loops is reachable.
+    fn accepting(&self, tableau: &Tableau) -> Vec<bool> {
+        let mut ret = vec![false; self.states.len()];
+        let mut queue: Vec<usize> = self.fair_sccs(tableau).into_iter().flatten().collect();
+        queue.iter().for_each(|&i| ret[i] = true);
+        while let Some(idx) = queue.pop() {
+            for &prev in self.pre[idx].iter() {
+                if !ret[prev] {
+                    ret[prev] = true;
+                    queue.push(prev);
+                }
+            }
+        }
+        ret
+    }
+    /// An accepting run from one of the initial product states, as a prefix and a cycle
+    /// of product states. The cycle goes through every acceptance set.
+    fn accepting_lasso(&self, tableau: &Tableau) -> Option<(Vec<usize>, Vec<usize>)> {
+        let acceptance_sets = tableau.acceptance_sets();
+        fair_lasso(
+            &self.post,
+            self.initial.iter().copied(),
+            |_| true,
+            acceptance_sets.len(),
+            |k, i| tableau.accepts(acceptance_sets[k], self.states[i].1),
+        )
+    }
+}
+
+/// The indexes of all states that have some path starting in them violating the formula.
+pub(super) fn violating_idx(model: &Model, formula: &LTLFormula) -> HashSet<usize> {
+    let mut tableau = Tableau::for_negation_of(formula);
+    let product = Product::build(model, &mut tableau, 0..model.nr_states());
+    let accepting = product.accepting(&tableau);
+    product
+        .initial
+        .iter()
+        .filter(|&&i| accepting[i])
+        .map(|&i| product.states[i].0)
+        .collect()
+}
+
+#[derive(Debug, Clone)]
+pub struct LTLChecker {
+    model: Model,
+    cache: HashMap<Box<LTLFormula>, HashSet<usize>>,
+}
+impl LTLChecker {
+    pub fn new(model: Model) -> Self {
+        Self {
+            model,
+            cache: HashMap::new(),
+        }
+    }
+    pub fn get_model(&self) -> &Model {
+        &self.model
+    }
+    /// The set of states in which all paths starting there satisfy the formula.
+    /// As LTL is about infinite paths, states that cannot reach any infinite path
+    /// (because all their paths end up in a sink) vacuously satisfy everything.
+    pub fn check(&mut self, formula: &LTLFormula) -> HashSet<String> {
+        self.fill_cache(formula);
+        self.model.get_names(&self.cache[formula])
+    }
+    /// Whether every path starting in any of the initial states of the model satisfies the formula.
+    pub fn holds(&mut self, formula: &LTLFormula) -> bool {
+        self.fill_cache(formula);
+        let sat = &self.cache[formula];
+        self.model.all_initial_idx().iter().all(|i| sat.contains(i))
+    }
+    /// Searches for a path starting in one of the initial states that violates the formula.
+    /// Returns `None` if there is none, which is to say, if the formula holds.
+    pub fn counterexample(&self, formula: &LTLFormula) -> Option<Lasso> {
+        let mut initial: Vec<usize> = self.model.all_initial_idx().into_iter().collect();
+        initial.sort_unstable();
+
+        let mut tableau = Tableau::for_negation_of(formula);
+        let product = Product::build(&self.model, &mut tableau, initial);
+        let (prefix, cycle) = product.accepting_lasso(&tableau)?;
+        let to_names = |path: Vec<usize>| {
+            path.into_iter()
+                .map(|i| self.model.get_state_by_idx(product.states[i].0).name())
+                .collect()
+        };
+        Some(Lasso {
+            prefix: to_names(prefix),
+            cycle: to_names(cycle),
+        })
+    }
+    fn fill_cache(&mut self, formula: &LTLFormula) {
+        if !self.cache.contains_key(formula) {
+            let violating = violating_idx(&self.model, formula);
+            let ret = self.model.all_except_idx(&violating);
+            self.cache.insert(Box::new(formula.clone()), ret);
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use std::collections::HashSet;
+
+use super::LTLChecker;
+use crate::formulas::ltl_python::PyLTLFormula;
+use crate::models::models_python::PyModel;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// The Python view into the LTL Checker
+/// Though this class is not frozen, you cannot modify it directly.
+/// The object will update itself on calls of `check` by updating the cache.
+///
+/// In Python, you can create this class from a model with the
+/// LTLChecker(model) constructor.
+#[pyclass(module = "minictl", name = "LTLChecker")]
+#[derive(Debug)]
+pub struct PyLTLChecker {
+    pymodel: PyModel,
+    inner: LTLChecker,
+}
+
+#[pymethods]
+impl PyLTLChecker {
+    #[new]
+    fn new(model: PyModel) -> Self {
+        Self {
+            inner: LTLChecker::new(model.to_rust()),
+            pymodel: model,
+        }
+    }
+    fn check(&mut self, formula: PyLTLFormula) -> PyResult<HashSet<String>> {
+        let rsformula = formula.to_rust().ok_or(PyValueError::new_err(
+            "provided formula is not a valid LTL formula",
+        ))?;
+        Ok(self.inner.check(&rsformula))
+    }
+    fn holds(&mut self, formula: PyLTLFormula) -> PyResult<bool> {
+        let rsformula = formula.to_rust().ok_or(PyValueError::new_err(
+            "provided formula is not a valid LTL formula",
+        ))?;
+        Ok(self.inner.holds(&rsformula))
+    }
+    fn counterexample(
+        &self,
+        formula: PyLTLFormula,
+    ) -> PyResult<Option<(Vec<String>, Vec<String>)>> {
+        let rsformula = formula.to_rust().ok_or(PyValueError::new_err(
+            "provided formula is not a valid LTL formula",
+        ))?;
+        Ok(self
+            .inner
+            .counterexample(&rsformula)
+            .map(|lasso| (lasso.prefix, lasso.cycle)))
+    }
+    fn get_model(&self) -> PyModel {
+        self.pymodel.clone()
+    }
+}
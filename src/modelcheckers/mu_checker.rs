@@ -0,0 +1,477 @@
+use std::collections::{HashMap, HashSet};
+
+use thiserror::Error;
+
+use super::state_set::StateSet;
+use crate::formulas::MuFormula;
+use crate::models::Model;
+
+// Before checking, the formula is compiled into a flat list of nodes, where fixpoint variables
+// refer directly to their binder, and implications are rewritten away. This is also where we
+// check that the formula is closed and that all fixpoint variables occur positively,
+// as otherwise the fixpoints are not guaranteed to exist.
+//
+// A fixpoint is computed the way the CTL checker computes EU and AF: instead of evaluating
+// the whole body again after every approximation, we only look at what changed. Every node of
+// the body that depends on the fixpoint variable keeps its current set of states, and the
+// modalities keep a count, for every state, of its successors in the set below them.
+// When the variable gains (or loses) a state, this is pushed up through the body, and a
+// modality only has to look at the predecessors of that state. Every node flips every state
+// at most once, so one fixpoint takes time linear in the size of its body times the model.
+//
+// This does not work when the variable is free in a nested fixpoint, as the inner fixpoint
+// would have to be solved again for every change. Then, we fall back to plain iteration,
+// with the Emerson–Lei optimisation: once a fixpoint has been computed, we remember its value
+// and the values of the free variables it was computed under. If we need it again, and its
+// free variables have only moved in the same direction as its own iteration (up for μ, down
+// for ν, or the other way round for variables that occur under a negation relative to the
+// fixpoint), we can start from the remembered value instead of from ∅ or from all states.
+// By monotonicity, that is still below (or above) the new fixpoint. Closed fixpoints are
+// only ever computed once, and nested fixpoints of the same kind never restart.
+
+#[derive(Debug, PartialEq, Error)]
+pub enum MuCheckError {
+    #[error("Fixpoint variable {0} is not bound by any mu or nu")]
+    FreeVariable(String),
+    #[error("Fixpoint variable {0} occurs under an odd number of negations")]
+    NotPositive(String),
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Top,
+    Bot,
+    Atomic(String),
+    Var(usize),
+    Neg(usize),
+    And(usize, usize),
+    Or(usize, usize),
+    Diamond(usize),
+    Box(usize),
+    Fixpoint { binder: usize, body: usize },
+}
+
+#[derive(Debug, Clone)]
+struct Binder {
+    least: bool,
+    // Whether the fixpoint is under an odd number of negations.
+    negated: bool,
+    // The binders of the variables that occur free in the body of this fixpoint.
+    free: Vec<usize>,
+}
+
+#[derive(Debug, Default)]
+struct Compiled {
+    nodes: Vec<Node>,
+    binders: Vec<Binder>,
+}
+
+impl Compiled {
+    fn push(&mut self, node: Node) -> usize {
+        self.nodes.push(node);
+        self.nodes.len() - 1
+    }
+    /// Compile the formula, returning its node and the binders of its free variables.
+    /// `scope` maps the names of the variables in scope to their binders, innermost last,
+    /// together with whether the binder itself was under an odd number of negations.
+    /// A variable occurs positively if it is under as many negations as its binder, modulo two.
+    fn compile(
+        &mut self,
+        formula: &MuFormula,
+        scope: &mut Vec<(String, usize, bool)>,
+        negated: bool,
+    ) -> Result<(usize, HashSet<usize>), MuCheckError> {
+        use MuFormula as F;
+        let mut free = HashSet::new();
+        let node = match formula {
+            F::Top => Node::Top,
+            F::Bot => Node::Bot,
+            F::Atomic(var) => Node::Atomic(var.inner.clone()),
+            F::Var(name) => {
+                let (binder, binder_negated) = match scope.iter().rev().find(|(n, ..)| n == name) {
+                    Some(&(_, binder, binder_negated)) => (binder, binder_negated),
+                    None => return Err(MuCheckError::FreeVariable(name.clone())),
+                };
+                if negated != binder_negated {
+                    return Err(MuCheckError::NotPositive(name.clone()));
+                }
+                free.insert(binder);
+                Node::Var(binder)
+            }
+            F::Neg(inner) => Node::Neg(self.compile_into(inner, scope, !negated, &mut free)?),
+            F::And(lhs, rhs) => Node::And(
+                self.compile_into(lhs, scope, negated, &mut free)?,
+                self.compile_into(rhs, scope, negated, &mut free)?,
+            ),
+            F::Or(lhs, rhs) => Node::Or(
+                self.compile_into(lhs, scope, negated, &mut free)?,
+                self.compile_into(rhs, scope, negated, &mut free)?,
+            ),
+            F::ImpliesR(lhs, rhs) => {
+                let lhs = self.compile_into(lhs, scope, !negated, &mut free)?;
+                let not_lhs = self.push(Node::Neg(lhs));
+                Node::Or(not_lhs, self.compile_into(rhs, scope, negated, &mut free)?)
+            }
+            F::ImpliesL(lhs, rhs) => {
+                let rhs = self.compile_into(rhs, scope, !negated, &mut free)?;
+                let not_rhs = self.push(Node::Neg(rhs));
+                Node::Or(self.compile_into(lhs, scope, negated, &mut free)?, not_rhs)
+            }
+            // Both sides occur both positively and negatively here,
+            // so we compile them twice, to check both.
+            F::BiImplies(lhs, rhs) => {
+                let forward = F::ImpliesR(lhs.clone(), rhs.clone());
+                let backward = F::ImpliesL(lhs.clone(), rhs.clone());
+                Node::And(
+                    self.compile_into(&forward, scope, negated, &mut free)?,
+                    self.compile_into(&backward, scope, negated, &mut free)?,
+                )
+            }
+            F::Diamond(inner) => {
+                Node::Diamond(self.compile_into(inner, scope, negated, &mut free)?)
+            }
+            F::Box(inner) => Node::Box(self.compile_into(inner, scope, negated, &mut free)?),
+            F::Mu(name, body) | F::Nu(name, body) => {
+                let binder = self.binders.len();
+                self.binders.push(Binder {
+                    least: matches!(formula, F::Mu(..)),
+                    negated,
+                    free: Vec::new(),
+                });
+                scope.push((name.clone(), binder, negated));
+                let compiled = self.compile(body, scope, negated);
+                scope.pop();
+                let (body, mut body_free) = compiled?;
+                body_free.remove(&binder);
+                self.binders[binder].free = body_free.iter().copied().collect();
+                free = body_free;
+                Node::Fixpoint { binder, body }
+            }
+        };
+        Ok((self.push(node), free))
+    }
+    fn compile_into(
+        &mut self,
+        formula: &MuFormula,
+        scope: &mut Vec<(String, usize, bool)>,
+        negated: bool,
+        free: &mut HashSet<usize>,
+    ) -> Result<usize, MuCheckError> {
+        let (node, inner_free) = self.compile(formula, scope, negated)?;
+        free.extend(inner_free);
+        Ok(node)
+    }
+}
+
+/// The value of a fixpoint the last time it was computed,
+/// together with the values of its free variables at that time.
+struct Approximation {
+    value: StateSet,
+    free_values: Vec<StateSet>,
+}
+
+struct Evaluator<'a> {
+    model: &'a Model,
+    compiled: &'a Compiled,
+    // The current value of every fixpoint variable, by binder.
+    env: Vec<StateSet>,
+    approximations: Vec<Option<Approximation>>,
+}
+
+impl Evaluator<'_> {
+    fn diamond(&self, set: &StateSet) -> StateSet {
+        let pre = self.model.get_pre_idx();
+        StateSet::from_indexes(
+            self.model.nr_states(),
+            set.iter().flat_map(|i| pre[i].iter().copied()),
+        )
+    }
+    /// Where to start iterating the fixpoint of `binder` from.
+    fn start(&mut self, binder: usize) -> StateSet {
+        let info = &self.compiled.binders[binder];
+        if let Some(approximation) = self.approximations[binder].take() {
+            // A free variable under a different number of negations than this fixpoint
+            // makes the body smaller as it grows, so it has to move the other way.
+            let still_valid =
+                info.free
+                    .iter()
+                    .zip(approximation.free_values.iter())
+                    .all(|(&var, old)| {
+                        let antitone = self.compiled.binders[var].negated != info.negated;
+                        match info.least != antitone {
+                            true => old.is_subset(&self.env[var]),
+                            false => self.env[var].is_subset(old),
+                        }
+                    });
+            if still_valid {
+                return approximation.value;
+            }
+        }
+        match info.least {
+            true => StateSet::empty(self.model.nr_states()),
+            false => StateSet::full(self.model.nr_states()),
+        }
+    }
+    fn eval(&mut self, node: usize) -> StateSet {
+        let nr_states = self.model.nr_states();
+        match self.compiled.nodes[node] {
+            Node::Top => StateSet::full(nr_states),
+            Node::Bot => StateSet::empty(nr_states),
+            Node::Atomic(ref var) => {
+                StateSet::from_indexes(nr_states, self.model.all_containing_idx(var))
+            }
+            Node::Var(binder) => self.env[binder].clone(),
+            Node::Neg(inner) => self.eval(inner).complement(),
+            Node::And(lhs, rhs) => self.eval(lhs).intersection(&self.eval(rhs)),
+            Node::Or(lhs, rhs) => self.eval(lhs).union(&self.eval(rhs)),
+            Node::Diamond(inner) => {
+                let inner = self.eval(inner);
+                self.diamond(&inner)
+            }
+            Node::Box(inner) => {
+                let not_inner = self.eval(inner).complement();
+                self.diamond(&not_inner).complement()
+            }
+            Node::Fixpoint { binder, body } => {
+                let start = self.start(binder);
+                let value = match self.dependent(binder, body) {
+                    // The body does not mention the variable at all.
+                    Some(dependent) if dependent.is_empty() => self.eval(body),
+                    Some(dependent) => self.propagate(binder, body, &dependent, start),
+                    None => self.iterate(binder, body, start),
+                };
+                self.env[binder] = value.clone();
+                let free_values = self.compiled.binders[binder]
+                    .free
+                    .iter()
+                    .map(|&var| self.env[var].clone())
+                    .collect();
+                self.approximations[binder] = Some(Approximation {
+                    value: value.clone(),
+                    free_values,
+                });
+                value
+            }
+        }
+    }
+    fn iterate(&mut self, binder: usize, body: usize, start: StateSet) -> StateSet {
+        let mut value = start;
+        loop {
+            self.env[binder] = value.clone();
+            let next = self.eval(body);
+            if next == value {
+                return value;
+            }
+            value = next;
+        }
+    }
+    /// The nodes below `node` that depend on the variable of `binder`, children first,
+    /// or `None` if the variable is free in a nested fixpoint.
+    fn dependent(&self, binder: usize, node: usize) -> Option<Vec<usize>> {
+        let mut ret = Vec::new();
+        self.collect_dependent(binder, node, &mut ret)?;
+        Some(ret)
+    }
+    /// Returns whether `node` depends on the variable, adding it to `out` if it does.
+    fn collect_dependent(&self, binder: usize, node: usize, out: &mut Vec<usize>) -> Option<bool> {
+        let depends = match self.compiled.nodes[node] {
+            Node::Top | Node::Bot | Node::Atomic(_) => false,
+            Node::Var(var) => var == binder,
+            Node::Neg(inner) | Node::Diamond(inner) | Node::Box(inner) => {
+                self.collect_dependent(binder, inner, out)?
+            }
+            Node::And(lhs, rhs) | Node::Or(lhs, rhs) => {
+                let lhs = self.collect_dependent(binder, lhs, out)?;
+                self.collect_dependent(binder, rhs, out)? || lhs
+            }
+            Node::Fixpoint { binder: inner, .. } => {
+                if self.compiled.binders[inner].free.contains(&binder) {
+                    return None;
+                }
+                false
+            }
+        };
+        if depends {
+            out.push(node);
+        }
+        Some(depends)
+    }
+    /// Compute the fixpoint of `binder` from `start`, by pushing every change of the variable
+    /// up through the `dependent` nodes of the body, which have to be ordered children first.
+    fn propagate(
+        &mut self,
+        binder: usize,
+        body: usize,
+        dependent: &[usize],
+        start: StateSet,
+    ) -> StateSet {
+        let model = self.model;
+        let nr_states = model.nr_states();
+        let (post, pre) = (model.get_post_idx(), model.get_pre_idx());
+        let mut values: HashMap<usize, StateSet> = HashMap::new();
+        // For the modalities, the number of successors of every state in the set below them.
+        let mut counts: HashMap<usize, Vec<usize>> = HashMap::new();
+        let mut parents: HashMap<usize, usize> = HashMap::new();
+        let mut vars = Vec::new();
+
+        self.env[binder] = start.clone();
+        for &node in dependent {
+            let value = match self.compiled.nodes[node] {
+                Node::Var(_) => {
+                    vars.push(node);
+                    start.clone()
+                }
+                Node::Neg(inner) => {
+                    parents.insert(inner, node);
+                    values[&inner].complement()
+                }
+                Node::And(lhs, rhs) | Node::Or(lhs, rhs) => {
+                    for side in [lhs, rhs] {
+                        parents.insert(side, node);
+                        // The sides that don't depend on the variable are computed only once.
+                        values.entry(side).or_insert_with(|| self.eval(side));
+                    }
+                    match self.compiled.nodes[node] {
+                        Node::And(..) => values[&lhs].intersection(&values[&rhs]),
+                        _ => values[&lhs].union(&values[&rhs]),
+                    }
+                }
+                Node::Diamond(inner) | Node::Box(inner) => {
+                    parents.insert(inner, node);
+                    let count: Vec<usize> = post
+                        .iter()
+                        .map(|next| next.iter().filter(|&&t| values[&inner].contains(t)).count())
+                        .collect();
+                    let diamond = matches!(self.compiled.nodes[node], Node::Diamond(_));
+                    let value = StateSet::from_indexes(
+                        nr_states,
+                        (0..nr_states).filter(|&s| match diamond {
+                            true => count[s] > 0,
+                            false => count[s] == post[s].len(),
+                        }),
+                    );
+                    counts.insert(node, count);
+                    value
+                }
+                Node::Top | Node::Bot | Node::Atomic(_) | Node::Fixpoint { .. } => {
+                    unreachable!("Only nodes that depend on the variable are propagated")
+                }
+            };
+            values.insert(node, value);
+        }
+
+        // Each entry is a node and a state in which its value just flipped.
+        let mut changes: Vec<(usize, usize)> = (0..nr_states)
+            .filter(|&s| values[&body].contains(s) != start.contains(s))
+            .map(|s| (body, s))
+            .collect();
+        while let Some((node, s)) = changes.pop() {
+            let now = values[&node].contains(s);
+            if node == body {
+                // The variable takes the new value of the body.
+                for &var in &vars {
+                    if flip_to(values.get_mut(&var).expect("Vars have values"), s, now) {
+                        changes.push((var, s));
+                    }
+                }
+                continue;
+            }
+            let parent = parents[&node];
+            let new = match self.compiled.nodes[parent] {
+                Node::Neg(_) => !now,
+                Node::And(lhs, rhs) => values[&lhs].contains(s) && values[&rhs].contains(s),
+                Node::Or(lhs, rhs) => values[&lhs].contains(s) || values[&rhs].contains(s),
+                // A modality can flip in all predecessors of the state at once.
+                Node::Diamond(_) | Node::Box(_) => {
+                    let diamond = matches!(self.compiled.nodes[parent], Node::Diamond(_));
+                    let count = counts.get_mut(&parent).expect("Modalities have counts");
+                    let value = values.get_mut(&parent).expect("Parents have values");
+                    for &t in &pre[s] {
+                        match now {
+                            true => count[t] += 1,
+                            false => count[t] -= 1,
+                        }
+                        let new = match diamond {
+                            true => count[t] > 0,
+                            false => count[t] == post[t].len(),
+                        };
+                        if flip_to(value, t, new) {
+                            changes.push((parent, t));
+                        }
+                    }
+                    continue;
+                }
+                Node::Top | Node::Bot | Node::Atomic(_) | Node::Var(_) | Node::Fixpoint { .. } => {
+                    unreachable!("Only nodes with children are parents")
+                }
+            };
+            if flip_to(
+                values.get_mut(&parent).expect("Parents have values"),
+                s,
+                new,
+            ) {
+                changes.push((parent, s));
+            }
+        }
+        values
+            .remove(&body)
+            .expect("The body depends on the variable")
+    }
+}
+
+/// Set whether `idx` is in the set, returning whether that changed anything.
+fn flip_to(set: &mut StateSet, idx: usize, value: bool) -> bool {
+    match value {
+        true => set.insert(idx),
+        false => set.remove(idx),
+    }
+}
+
+/// A checker for closed formulas of the modal mu-calculus.
+#[derive(Debug, Clone)]
+pub struct MuChecker {
+    model: Model,
+    cache: HashMap<Box<MuFormula>, HashSet<usize>>,
+}
+
+impl MuChecker {
+    pub fn new(model: Model) -> Self {
+        Self {
+            model,
+            cache: HashMap::new(),
+        }
+    }
+    pub fn get_model(&self) -> &Model {
+        &self.model
+    }
+    /// The set of states in which the formula holds.
+    /// Fails if the formula has free fixpoint variables, or fixpoint variables that
+    /// occur under an odd number of negations, as then its fixpoints might not exist.
+    pub fn check(&mut self, formula: &MuFormula) -> Result<HashSet<String>, MuCheckError> {
+        self.fill_cache(formula)?;
+        Ok(self.model.get_names(&self.cache[formula]))
+    }
+    /// Whether the formula holds in all initial states of the model.
+    pub fn holds(&mut self, formula: &MuFormula) -> Result<bool, MuCheckError> {
+        self.fill_cache(formula)?;
+        let sat = &self.cache[formula];
+        Ok(self.model.all_initial_idx().iter().all(|i| sat.contains(i)))
+    }
+    fn fill_cache(&mut self, formula: &MuFormula) -> Result<(), MuCheckError> {
+        if self.cache.contains_key(formula) {
+            return Ok(());
+        }
+        let mut compiled = Compiled::default();
+        let (root, _) = compiled.compile(formula, &mut Vec::new(), false)?;
+        let nr_states = self.model.nr_states();
+        let mut evaluator = Evaluator {
+            model: &self.model,
+            compiled: &compiled,
+            env: vec![StateSet::empty(nr_states); compiled.binders.len()],
+            approximations: (0..compiled.binders.len()).map(|_| None).collect(),
+        };
+        let ret = evaluator.eval(root).iter().collect();
+        self.cache.insert(Box::new(formula.clone()), ret);
+        Ok(())
+    }
+}
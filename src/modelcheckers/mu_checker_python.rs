@@ -0,0 +1,53 @@
+use std::collections::HashSet;
+
+use super::{MuCheckError, MuChecker};
+use crate::formulas::mu_python::PyMuFormula;
+use crate::models::models_python::PyModel;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+impl From<MuCheckError> for PyErr {
+    fn from(err: MuCheckError) -> PyErr {
+        PyValueError::new_err(err.to_string())
+    }
+}
+
+/// The Python view into the mu-calculus Checker
+/// Though this class is not frozen, you cannot modify it directly.
+/// The object will update itself on calls of `check` by updating the cache.
+///
+/// In Python, you can create this class from a model with the
+/// MuChecker(model) constructor.
+#[pyclass(module = "minictl", name = "MuChecker")]
+#[derive(Debug)]
+pub struct PyMuChecker {
+    pymodel: PyModel,
+    inner: MuChecker,
+}
+
+#[pymethods]
+impl PyMuChecker {
+    #[new]
+    fn new(model: PyModel) -> Self {
+        Self {
+            inner: MuChecker::new(model.to_rust()),
+            pymodel: model,
+        }
+    }
+    fn check(&mut self, formula: PyMuFormula) -> PyResult<HashSet<String>> {
+        let rsformula = formula.to_rust().ok_or(PyValueError::new_err(
+            "provided formula is not a valid mu-calculus formula",
+        ))?;
+        Ok(self.inner.check(&rsformula)?)
+    }
+    fn holds(&mut self, formula: PyMuFormula) -> PyResult<bool> {
+        let rsformula = formula.to_rust().ok_or(PyValueError::new_err(
+            "provided formula is not a valid mu-calculus formula",
+        ))?;
+        Ok(self.inner.holds(&rsformula)?)
+    }
+    fn get_model(&self) -> PyModel {
+        self.pymodel.clone()
+    }
+}
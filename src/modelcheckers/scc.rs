@@ -0,0 +1,78 @@
+// Strongly connected components, shared by the checkers that need to find
+// infinite paths (loops) in some graph, instead of just doing fixpoint iteration.
+
+/// Tarjan's algorithm over the graph given as successor lists,
+/// only considering the nodes for which `include` returns true.
+///
+/// This is written iteratively, as the recursive version overflows the stack
+/// on long chains of states, which is exactly the sort of model we care about here.
+/// The components are returned in reverse topological order.
+pub(crate) fn tarjan_scc(graph: &[Vec<usize>], include: impl Fn(usize) -> bool) -> Vec<Vec<usize>> {
+    const UNVISITED: usize = usize::MAX;
+    let mut index = vec![UNVISITED; graph.len()];
+    let mut lowlink = vec![0; graph.len()];
+    let mut on_stack = vec![false; graph.len()];
+    let mut stack = Vec::new();
+    let mut sccs = Vec::new();
+    let mut counter = 0;
+
+    // Our own call stack, with (node, position in the successor list of that node)
+    let mut call_stack: Vec<(usize, usize)> = Vec::new();
+    for root in 0..graph.len() {
+        if index[root] != UNVISITED || !include(root) {
+            continue;
+        }
+        index[root] = counter;
+        lowlink[root] = counter;
+        counter += 1;
+        stack.push(root);
+        on_stack[root] = true;
+        call_stack.push((root, 0));
+
+        while let Some((v, pos)) = call_stack.pop() {
+            if let Some(&w) = graph[v].get(pos) {
+                call_stack.push((v, pos + 1));
+                if !include(w) {
+                    continue;
+                }
+                if index[w] == UNVISITED {
+                    index[w] = counter;
+                    lowlink[w] = counter;
+                    counter += 1;
+                    stack.push(w);
+                    on_stack[w] = true;
+                    call_stack.push((w, 0));
+                } else if on_stack[w] {
+                    lowlink[v] = lowlink[v].min(index[w]);
+                }
+                continue;
+            }
+            // All successors of v are done, so we "return" to the parent.
+            if let Some(&(parent, _)) = call_stack.last() {
+                lowlink[parent] = lowlink[parent].min(lowlink[v]);
+            }
+            if lowlink[v] == index[v] {
+                let mut scc = Vec::new();
+                loop {
+                    let w = stack.pop().expect("v is still on the stack");
+                    on_stack[w] = false;
+                    scc.push(w);
+                    if w == v {
+                        break;
+                    }
+                }
+                sccs.push(scc);
+            }
+        }
+    }
+    sccs
+}
+
+/// Whether an SCC actually contains a loop: either it has multiple nodes,
+/// or it is a single node with an edge to itself.
+pub(crate) fn is_nontrivial(scc: &[usize], graph: &[Vec<usize>]) -> bool {
+    match scc {
+        [single] => graph[*single].contains(single),
+        _ => !scc.is_empty(),
+    }
+}
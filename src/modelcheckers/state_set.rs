@@ -0,0 +1,123 @@
+// A dense set of state indexes, stored as one bit per state of the model.
+//
+// The explicit checker spends nearly all its time on unions, intersections, and complements
+// of sets of states, which on a bitset are a single pass over the words, without any hashing
+// or allocation of new sets.
+
+const WORD: usize = u64::BITS as usize;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct StateSet {
+    words: Vec<u64>,
+    // The number of states in the model, which is needed to complement without
+    // ending up with indexes past the last state.
+    len: usize,
+}
+
+impl StateSet {
+    pub(crate) fn empty(len: usize) -> Self {
+        Self {
+            words: vec![0; len.div_ceil(WORD)],
+            len,
+        }
+    }
+    pub(crate) fn full(len: usize) -> Self {
+        let mut ret = Self {
+            words: vec![u64::MAX; len.div_ceil(WORD)],
+            len,
+        };
+        ret.clear_tail();
+        ret
+    }
+    pub(crate) fn from_indexes(len: usize, indexes: impl IntoIterator<Item = usize>) -> Self {
+        let mut ret = Self::empty(len);
+        indexes.into_iter().for_each(|i| {
+            ret.insert(i);
+        });
+        ret
+    }
+    // The bits past `len` in the last word always have to stay zero,
+    // as otherwise `count`, `iter` and equality break.
+    fn clear_tail(&mut self) {
+        if let Some(last) = self.words.last_mut() {
+            let used = self.len % WORD;
+            if used != 0 {
+                *last &= (1 << used) - 1;
+            }
+        }
+    }
+    pub(crate) fn contains(&self, idx: usize) -> bool {
+        debug_assert!(idx < self.len);
+        self.words[idx / WORD] & (1 << (idx % WORD)) != 0
+    }
+    /// Returns whether the index was newly inserted.
+    pub(crate) fn insert(&mut self, idx: usize) -> bool {
+        debug_assert!(idx < self.len);
+        let word = &mut self.words[idx / WORD];
+        let bit = 1 << (idx % WORD);
+        let new = *word & bit == 0;
+        *word |= bit;
+        new
+    }
+    /// Returns whether the index was in the set.
+    pub(crate) fn remove(&mut self, idx: usize) -> bool {
+        debug_assert!(idx < self.len);
+        let word = &mut self.words[idx / WORD];
+        let bit = 1 << (idx % WORD);
+        let present = *word & bit != 0;
+        *word &= !bit;
+        present
+    }
+    pub(crate) fn union_with(&mut self, other: &StateSet) {
+        debug_assert_eq!(self.len, other.len);
+        self.words
+            .iter_mut()
+            .zip(other.words.iter())
+            .for_each(|(a, b)| *a |= b);
+    }
+    pub(crate) fn intersect_with(&mut self, other: &StateSet) {
+        debug_assert_eq!(self.len, other.len);
+        self.words
+            .iter_mut()
+            .zip(other.words.iter())
+            .for_each(|(a, b)| *a &= b);
+    }
+    pub(crate) fn is_subset(&self, other: &StateSet) -> bool {
+        debug_assert_eq!(self.len, other.len);
+        self.words
+            .iter()
+            .zip(other.words.iter())
+            .all(|(a, b)| a & !b == 0)
+    }
+    pub(crate) fn union(&self, other: &StateSet) -> StateSet {
+        let mut ret = self.clone();
+        ret.union_with(other);
+        ret
+    }
+    pub(crate) fn intersection(&self, other: &StateSet) -> StateSet {
+        let mut ret = self.clone();
+        ret.intersect_with(other);
+        ret
+    }
+    pub(crate) fn complement(&self) -> StateSet {
+        let mut ret = Self {
+            words: self.words.iter().map(|w| !w).collect(),
+            len: self.len,
+        };
+        ret.clear_tail();
+        ret
+    }
+    pub(crate) fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some(i * WORD + bit)
+            })
+        })
+    }
+}
@@ -0,0 +1,499 @@
+use std::collections::{HashMap, HashSet};
+
+use super::bdd::{Bdd, BddRef, FALSE, TRUE};
+use crate::formulas::CTLFormula;
+use crate::models::{
+    parse_program_text, Condition, Expression, Model, ModelCreationError, ParsedProgram,
+    ProgramError, Specification,
+};
+
+// States are encoded in binary, using a number of boolean variables ("bits").
+// A model that is given as a list of states is encoded by the index of every state, so building
+// the diagrams goes over all states and edges once, like the explicit checker does.
+// A program is encoded by the values of its variables, each in its own block of bits.
+// Its initial states, guards, and updates then become diagrams directly, and the reachable
+// states are a fixpoint like any other, so its states are never listed one by one.
+//
+// Every bit has a "current" and a "next" copy for the transition relation, which are
+// interleaved in the variable order (bit i is variable 2i now and 2i+1 next). This keeps
+// related variables close together, and makes renaming from current to next a simple shift.
+fn current(bit: u32) -> u32 {
+    2 * bit
+}
+fn next(bit: u32) -> u32 {
+    2 * bit + 1
+}
+fn is_next(var: u32) -> bool {
+    var % 2 == 1
+}
+
+/// The number of bits needed to tell `n` things apart.
+fn bits_for(n: usize) -> u32 {
+    usize::BITS - n.saturating_sub(1).leading_zeros()
+}
+
+/// The cube of `value`, written in the `bits` bits from `first` on,
+/// over the current or next copy of those bits.
+fn encode(bdd: &mut Bdd, first: u32, bits: u32, value: usize, copy: fn(u32) -> u32) -> BddRef {
+    (0..bits).fold(TRUE, |acc, bit| {
+        let var = match (value >> bit) & 1 {
+            1 => bdd.var(copy(first + bit)),
+            _ => bdd.nvar(copy(first + bit)),
+        };
+        bdd.and(acc, var)
+    })
+}
+
+/// The bits holding the value of one variable of a program.
+#[derive(Debug, Clone)]
+struct Block {
+    name: String,
+    values: Vec<String>,
+    first: u32,
+    bits: u32,
+}
+
+impl Block {
+    fn is(&self, bdd: &mut Bdd, value: usize, copy: fn(u32) -> u32) -> BddRef {
+        encode(bdd, self.first, self.bits, value, copy)
+    }
+    /// The variable keeps its value.
+    fn unchanged(&self, bdd: &mut Bdd) -> BddRef {
+        (self.first..self.first + self.bits).fold(TRUE, |acc, bit| {
+            let (now, then) = (bdd.var(current(bit)), bdd.var(next(bit)));
+            let both = bdd.and(now, then);
+            let (not_now, not_then) = (bdd.nvar(current(bit)), bdd.nvar(next(bit)));
+            let neither = bdd.and(not_now, not_then);
+            let same = bdd.or(both, neither);
+            bdd.and(acc, same)
+        })
+    }
+}
+
+/// The states in which a guard or INIT condition holds, over the current copy of the bits.
+fn condition(bdd: &mut Bdd, blocks: &[Block], condition: &Condition) -> BddRef {
+    let mut compile = |inner: &Condition| self::condition(bdd, blocks, inner);
+    match condition {
+        Condition::Top => TRUE,
+        Condition::Bot => FALSE,
+        Condition::Is(var, value) => blocks[*var].is(bdd, *value, current),
+        Condition::Not(inner) => {
+            let inner = compile(inner);
+            bdd.not(inner)
+        }
+        Condition::And(lhs, rhs) => {
+            let (lhs, rhs) = (compile(lhs), compile(rhs));
+            bdd.and(lhs, rhs)
+        }
+        Condition::Or(lhs, rhs) => {
+            let (lhs, rhs) = (compile(lhs), compile(rhs));
+            bdd.or(lhs, rhs)
+        }
+    }
+}
+
+/// How states are encoded, to turn sets of them back into names.
+#[derive(Debug, Clone)]
+enum Encoding {
+    Model(Box<Model>),
+    Program(Vec<Block>),
+}
+
+/// A CTL checker that represents sets of states and the transition relation
+/// as binary decision diagrams.
+///
+/// Created from a `Model`, it encodes every state by its index, and gives the same results as
+/// `CTLChecker`. Created with `from_program`, it encodes the values of the variables of the
+/// program instead, so it never lists the states, and can handle programs with far more
+/// reachable states than `parse_program` can. Its results then are the same as those of
+/// `CTLChecker` on the model `parse_program` compiles.
+///
+/// It does not support fairness, witnesses, or custom algorithms.
+#[derive(Debug, Clone)]
+pub struct SymbolicCTLChecker {
+    encoding: Encoding,
+    bdd: Bdd,
+    // The current copies of all bits, in order.
+    bits: Vec<u32>,
+    // All encodings of states: not every encoding is a state, as the number of states
+    // of a model is rarely a power of two, and programs only have their reachable states.
+    states: BddRef,
+    initial: BddRef,
+    transitions: BddRef,
+    cache: HashMap<Box<CTLFormula>, BddRef>,
+}
+
+impl SymbolicCTLChecker {
+    pub fn new(model: Model) -> Self {
+        let bits = bits_for(model.nr_states());
+        let mut bdd = Bdd::new();
+        let mut states = FALSE;
+        let mut transitions = FALSE;
+        for (from, targets) in model.get_post_idx().iter().enumerate() {
+            let from = encode(&mut bdd, 0, bits, from, current);
+            states = bdd.or(states, from);
+            for &to in targets {
+                let to = encode(&mut bdd, 0, bits, to, next);
+                let edge = bdd.and(from, to);
+                transitions = bdd.or(transitions, edge);
+            }
+        }
+        let mut initial = FALSE;
+        for &idx in model.get_initial_idx() {
+            let state = encode(&mut bdd, 0, bits, idx, current);
+            initial = bdd.or(initial, state);
+        }
+        Self {
+            encoding: Encoding::Model(Box::new(model)),
+            bdd,
+            bits: (0..bits).map(current).collect(),
+            states,
+            initial,
+            transitions,
+            cache: HashMap::new(),
+        }
+    }
+    /// Parse a program in the guarded-command language of `parse_program`, and encode it
+    /// without listing its states. The specs are returned alongside the checker.
+    ///
+    /// This fails on the same programs as `parse_program`, though if several updates are out
+    /// of range, the one reported can differ.
+    pub fn from_program(input: &str) -> Result<(Self, Vec<Specification>), ProgramError> {
+        let ParsedProgram {
+            vars,
+            init,
+            commands,
+            specs,
+        } = parse_program_text(input)?;
+        let mut first = 0;
+        let blocks: Vec<Block> = vars
+            .0
+            .iter()
+            .map(|var| {
+                let bits = bits_for(var.values.len());
+                first += bits;
+                Block {
+                    name: var.name.clone(),
+                    values: var.values.clone(),
+                    first: first - bits,
+                    bits,
+                }
+            })
+            .collect();
+        let mut bdd = Bdd::new();
+
+        let mut initial = TRUE;
+        for block in &blocks {
+            let mut valid = FALSE;
+            for value in 0..block.values.len() {
+                let is = block.is(&mut bdd, value, current);
+                valid = bdd.or(valid, is);
+            }
+            initial = bdd.and(initial, valid);
+        }
+        for condition in &init {
+            let condition = self::condition(&mut bdd, &blocks, condition);
+            initial = bdd.and(initial, condition);
+        }
+        if initial == FALSE {
+            return Err(ProgramError::NoInitialStates);
+        }
+
+        // A command that would set a variable out of its range has no transitions from the states
+        // in which it would, and it is an error if any of those states turns out to be reachable.
+        let mut out_of_range = Vec::new();
+        let mut transitions = FALSE;
+        for command in &commands {
+            let guard = self::condition(&mut bdd, &blocks, &command.guard);
+            // Going up from the last variable only ever puts new nodes on top of the relation.
+            let mut relation = TRUE;
+            for (target, block) in blocks.iter().enumerate().rev() {
+                let update = match command.updates.iter().find(|(var, _)| *var == target) {
+                    None => block.unchanged(&mut bdd),
+                    Some((_, Expression::Values(values))) => {
+                        let mut update = FALSE;
+                        for &value in values {
+                            let is = block.is(&mut bdd, value, next);
+                            update = bdd.or(update, is);
+                        }
+                        update
+                    }
+                    Some((_, expression @ (Expression::Copy(var) | Expression::Add(var, _)))) => {
+                        let mut update = FALSE;
+                        for source in 0..blocks[*var].values.len() {
+                            let read = blocks[*var].is(&mut bdd, source, current);
+                            match expression.apply(target, source, &vars) {
+                                Ok(value) => {
+                                    let write = block.is(&mut bdd, value, next);
+                                    let both = bdd.and(read, write);
+                                    update = bdd.or(update, both);
+                                }
+                                Err(value) => {
+                                    let bad = bdd.and(guard, read);
+                                    let error = ProgramError::OutOfRange(
+                                        command.line,
+                                        block.name.clone(),
+                                        value,
+                                    );
+                                    out_of_range.push((bad, error));
+                                }
+                            }
+                        }
+                        update
+                    }
+                };
+                relation = bdd.and(update, relation);
+            }
+            let relation = bdd.and(guard, relation);
+            transitions = bdd.or(transitions, relation);
+        }
+
+        let mut ret = Self {
+            encoding: Encoding::Program(blocks),
+            bdd,
+            bits: (0..first).map(current).collect(),
+            states: initial,
+            initial,
+            transitions,
+            cache: HashMap::new(),
+        };
+        loop {
+            let image = ret.image(ret.states);
+            let reachable = ret.bdd.or(ret.states, image);
+            if reachable == ret.states {
+                break;
+            }
+            ret.states = reachable;
+        }
+        for (bad, error) in out_of_range {
+            if ret.bdd.and(ret.states, bad) != FALSE {
+                return Err(error);
+            }
+        }
+        // Restricting the transitions to the reachable states keeps EX inside of them.
+        ret.transitions = ret.bdd.and(ret.transitions, ret.states);
+        Ok((ret, specs))
+    }
+    /// The model the checker was created with, or `None` if it was created from a program.
+    pub fn get_model(&self) -> Option<&Model> {
+        match &self.encoding {
+            Encoding::Model(model) => Some(model),
+            Encoding::Program(_) => None,
+        }
+    }
+    /// Fails with a state that has no outgoing transitions, if there is one,
+    /// like `Model::check_no_sinks`.
+    pub fn check_no_sinks(&mut self) -> Result<(), ModelCreationError> {
+        let has_next = self.ex(self.states);
+        let sinks = self.neg(has_next);
+        match self.bdd.sat_one(sinks, &self.bits) {
+            Some(sink) => Err(ModelCreationError::EmptyEdgeList(self.name(&sink))),
+            None => Ok(()),
+        }
+    }
+    pub fn check(&mut self, formula: &CTLFormula) -> HashSet<String> {
+        let set = self.sat(formula);
+        self.bdd
+            .sat_all(set, &self.bits)
+            .iter()
+            .map(|state| self.name(state))
+            .collect()
+    }
+    /// Whether the formula holds in all initial states.
+    pub fn holds(&mut self, formula: &CTLFormula) -> bool {
+        let set = self.sat(formula);
+        let fails = self.neg(set);
+        self.bdd.and(self.initial, fails) == FALSE
+    }
+
+    /// The name of the state with the given values of the bits.
+    fn name(&self, bits: &[bool]) -> String {
+        let value = |bits: &[bool]| {
+            bits.iter()
+                .enumerate()
+                .map(|(i, &bit)| usize::from(bit) << i)
+                .sum::<usize>()
+        };
+        match &self.encoding {
+            Encoding::Model(model) => model.get_state_by_idx(value(bits)).name(),
+            Encoding::Program(blocks) => blocks
+                .iter()
+                .map(|block| {
+                    let range = block.first as usize..(block.first + block.bits) as usize;
+                    format!("{}={}", block.name, block.values[value(&bits[range])])
+                })
+                .collect::<Vec<String>>()
+                .join(","),
+        }
+    }
+    /// The states with some predecessor in `set`.
+    fn image(&mut self, set: BddRef) -> BddRef {
+        let next = self
+            .bdd
+            .and_exists(self.transitions, set, &|var| !is_next(var));
+        self.bdd.rename(next, &|var| var - 1)
+    }
+    fn neg(&mut self, set: BddRef) -> BddRef {
+        let complement = self.bdd.not(set);
+        self.bdd.and(self.states, complement)
+    }
+    /// The states with some successor in `set`.
+    fn ex(&mut self, set: BddRef) -> BddRef {
+        let set_next = self.bdd.rename(set, &|var| var + 1);
+        self.bdd.and_exists(self.transitions, set_next, &is_next)
+    }
+    /// Least fixpoint of Z = rhs ∨ (lhs ∧ EX Z).
+    fn eu(&mut self, lhs: BddRef, rhs: BddRef) -> BddRef {
+        let mut set = rhs;
+        loop {
+            let pre = self.ex(set);
+            let step = self.bdd.and(lhs, pre);
+            let next = self.bdd.or(rhs, step);
+            if next == set {
+                return set;
+            }
+            set = next;
+        }
+    }
+    /// Greatest fixpoint of Z = inner ∧ (EX Z ∨ sink).
+    /// Like in the explicit checker, a path that ends in a sink state counts as a full path.
+    fn eg(&mut self, inner: BddRef) -> BddRef {
+        let has_next = self.ex(self.states);
+        let sinks = self.neg(has_next);
+        let mut set = inner;
+        loop {
+            let pre = self.ex(set);
+            let continues = self.bdd.or(pre, sinks);
+            let next = self.bdd.and(inner, continues);
+            if next == set {
+                return set;
+            }
+            set = next;
+        }
+    }
+    fn sat(&mut self, formula: &CTLFormula) -> BddRef {
+        if let Some(&ret) = self.cache.get(formula) {
+            return ret;
+        }
+        use CTLFormula as F;
+        let ret = match formula {
+            F::Top => self.states,
+            F::Bot => FALSE,
+            F::Atomic(var) => match &self.encoding {
+                Encoding::Model(model) => {
+                    let bits = self.bits.len() as u32;
+                    let containing = model.all_containing_idx(&var.inner);
+                    containing.into_iter().fold(FALSE, |acc, idx| {
+                        let state = encode(&mut self.bdd, 0, bits, idx, current);
+                        self.bdd.or(acc, state)
+                    })
+                }
+                // The states of a program only satisfy the propositions `var=value`.
+                Encoding::Program(blocks) => {
+                    let found = var.inner.split_once('=').and_then(|(name, value)| {
+                        let block = blocks.iter().find(|block| block.name == name)?;
+                        let value = block.values.iter().position(|v| v == value)?;
+                        Some((block, value))
+                    });
+                    match found {
+                        Some((block, value)) => {
+                            let is = block.is(&mut self.bdd, value, current);
+                            self.bdd.and(self.states, is)
+                        }
+                        None => FALSE,
+                    }
+                }
+            },
+            F::Neg(inner) => {
+                let inner = self.sat(inner);
+                self.neg(inner)
+            }
+            F::And(lhs, rhs) => {
+                let (lhs, rhs) = (self.sat(lhs), self.sat(rhs));
+                self.bdd.and(lhs, rhs)
+            }
+            F::Or(lhs, rhs) => {
+                let (lhs, rhs) = (self.sat(lhs), self.sat(rhs));
+                self.bdd.or(lhs, rhs)
+            }
+            F::ImpliesR(lhs, rhs) => {
+                let (lhs, rhs) = (self.sat(lhs), self.sat(rhs));
+                let not_lhs = self.neg(lhs);
+                self.bdd.or(not_lhs, rhs)
+            }
+            F::ImpliesL(lhs, rhs) => {
+                let (lhs, rhs) = (self.sat(lhs), self.sat(rhs));
+                let not_rhs = self.neg(rhs);
+                self.bdd.or(lhs, not_rhs)
+            }
+            F::BiImplies(lhs, rhs) => {
+                let (lhs, rhs) = (self.sat(lhs), self.sat(rhs));
+                let both = self.bdd.and(lhs, rhs);
+                let (not_lhs, not_rhs) = (self.neg(lhs), self.neg(rhs));
+                let neither = self.bdd.and(not_lhs, not_rhs);
+                self.bdd.or(both, neither)
+            }
+            F::EX(inner) => {
+                let inner = self.sat(inner);
+                self.ex(inner)
+            }
+            F::AX(inner) => {
+                let inner = self.sat(inner);
+                let not_inner = self.neg(inner);
+                let some_not = self.ex(not_inner);
+                self.neg(some_not)
+            }
+            F::EF(inner) => {
+                let inner = self.sat(inner);
+                self.eu(self.states, inner)
+            }
+            F::AF(inner) => {
+                let inner = self.sat(inner);
+                let not_inner = self.neg(inner);
+                let some_never = self.eg(not_inner);
+                self.neg(some_never)
+            }
+            F::EG(inner) => {
+                let inner = self.sat(inner);
+                self.eg(inner)
+            }
+            F::AG(inner) => {
+                let inner = self.sat(inner);
+                let not_inner = self.neg(inner);
+                let some_not = self.eu(self.states, not_inner);
+                self.neg(some_not)
+            }
+            F::EU(lhs, rhs) => {
+                let (lhs, rhs) = (self.sat(lhs), self.sat(rhs));
+                self.eu(lhs, rhs)
+            }
+            // A[φUψ] ≡ ¬(E[¬ψU(¬φ∧¬ψ)] ∨ EG¬ψ)
+            F::AU(lhs, rhs) => {
+                let (lhs, rhs) = (self.sat(lhs), self.sat(rhs));
+                let (not_lhs, not_rhs) = (self.neg(lhs), self.neg(rhs));
+                let neither = self.bdd.and(not_lhs, not_rhs);
+                let fails_before = self.eu(not_rhs, neither);
+                let never = self.eg(not_rhs);
+                let fails = self.bdd.or(fails_before, never);
+                self.neg(fails)
+            }
+            // E[φRψ] ≡ ¬A[¬φU¬ψ] ≡ E[ψU(φ∧ψ)] ∨ EGψ
+            F::ER(lhs, rhs) => {
+                let (lhs, rhs) = (self.sat(lhs), self.sat(rhs));
+                let both = self.bdd.and(lhs, rhs);
+                let released = self.eu(rhs, both);
+                let never_released = self.eg(rhs);
+                self.bdd.or(released, never_released)
+            }
+            // A[φRψ] ≡ ¬E[¬φU¬ψ]
+            F::AR(lhs, rhs) => {
+                let (lhs, rhs) = (self.sat(lhs), self.sat(rhs));
+                let (not_lhs, not_rhs) = (self.neg(lhs), self.neg(rhs));
+                let fails = self.eu(not_lhs, not_rhs);
+                self.neg(fails)
+            }
+        };
+        self.cache.insert(Box::new(formula.clone()), ret);
+        ret
+    }
+}
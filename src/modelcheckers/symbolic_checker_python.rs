@@ -0,0 +1,64 @@
+use std::collections::HashSet;
+
+use super::SymbolicCTLChecker;
+use crate::formulas::ctl_python::PyCTLFormula;
+use crate::models::models_python::{PyModel, PySpecification};
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// The Python view into the symbolic CTL Checker
+/// Though this class is not frozen, you cannot modify it directly.
+/// The object will update itself on calls of `check` by updating the cache.
+///
+/// In Python, you can create this class from a model with the
+/// SymbolicCTLChecker(model) constructor, or from a program with
+/// SymbolicCTLChecker.from_program(text).
+#[pyclass(module = "minictl", name = "SymbolicCTLChecker")]
+#[derive(Debug)]
+pub struct PySymbolicCTLChecker {
+    pymodel: Option<PyModel>,
+    inner: SymbolicCTLChecker,
+}
+
+#[pymethods]
+impl PySymbolicCTLChecker {
+    #[new]
+    fn new(model: PyModel) -> Self {
+        Self {
+            inner: SymbolicCTLChecker::new(model.to_rust()),
+            pymodel: Some(model),
+        }
+    }
+    #[staticmethod]
+    #[pyo3(signature = (text, *, allow_sink=false))]
+    fn from_program(text: &str, allow_sink: bool) -> PyResult<(Self, Vec<PySpecification>)> {
+        let (mut inner, specs) = SymbolicCTLChecker::from_program(text)?;
+        if !allow_sink {
+            inner.check_no_sinks()?;
+        }
+        let checker = Self {
+            pymodel: None,
+            inner,
+        };
+        Ok((
+            checker,
+            specs.iter().map(PySpecification::from_rust).collect(),
+        ))
+    }
+    fn check(&mut self, formula: PyCTLFormula) -> PyResult<HashSet<String>> {
+        let rsformula = formula.to_rust().ok_or(PyValueError::new_err(
+            "provided formula is not a valid CTL formula",
+        ))?;
+        Ok(self.inner.check(&rsformula))
+    }
+    fn holds(&mut self, formula: PyCTLFormula) -> PyResult<bool> {
+        let rsformula = formula.to_rust().ok_or(PyValueError::new_err(
+            "provided formula is not a valid CTL formula",
+        ))?;
+        Ok(self.inner.holds(&rsformula))
+    }
+    fn get_model(&self) -> Option<PyModel> {
+        self.pymodel.clone()
+    }
+}
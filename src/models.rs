@@ -1,5 +1,18 @@
 mod model;
 pub use model::{Model, ModelCreationError, State};
 
+mod model_bisimulation;
+mod model_dot;
+mod model_product;
+pub use model_product::ProductError;
+mod model_program;
+pub use model_program::{parse_program, Program, ProgramError, Specification};
+pub(crate) use model_program::{parse_program_text, Condition, Expression, ParsedProgram};
+mod model_text;
+pub use model_text::{parse_model, ModelParseError};
+
+#[cfg(feature = "serde")]
+mod model_serde;
+
 #[cfg(feature = "python")]
 pub mod models_python;
@@ -30,6 +30,9 @@ impl State {
     pub fn name(&self) -> String {
         self.name.clone()
     }
+    pub fn vars(&self) -> &HashSet<String> {
+        &self.vars
+    }
 }
 
 #[allow(unused)]
@@ -42,6 +45,13 @@ pub struct Model {
     pre_idx: Vec<Vec<usize>>,
     initial_states: Vec<String>,
     initial_idx: Vec<usize>,
+    // The names of the actions on the transitions, which the two below index into.
+    // Models built without actions simply have no actions, and all their labels are None.
+    actions: Vec<String>,
+    // The action of every transition, in the same order as the targets in post_idx.
+    post_actions: Vec<Vec<Option<usize>>>,
+    // The incoming transitions of every state, as their source and action.
+    pre_actions: Vec<Vec<(usize, Option<usize>)>>,
 }
 
 // Instead of strings, we will be dealing with usize indexes into the states vec
@@ -57,6 +67,20 @@ impl Model {
         states: Vec<State>,
         edges: HashMap<String, Vec<String>>,
         initial_states: Vec<String>,
+    ) -> Result<Self, ModelCreationError> {
+        let edges = edges
+            .into_iter()
+            .map(|(k, v)| (k, v.into_iter().map(|t| (None, t)).collect()))
+            .collect();
+        Self::new_labelled(states, edges, initial_states)
+    }
+    /// Create a labelled transition system, where every transition is given
+    /// as an optional action label together with its target state.
+    /// Checkers that don't know about actions see it as the model with the labels left out.
+    pub fn new_labelled(
+        states: Vec<State>,
+        edges: HashMap<String, Vec<(Option<String>, String)>>,
+        initial_states: Vec<String>,
     ) -> Result<Self, ModelCreationError> {
         if let Some(state) = states.iter().find(|s| !edges.contains_key(&s.name)) {
             return Err(ModelCreationError::StateNotMentionned(state.name()));
@@ -82,7 +106,7 @@ impl Model {
                     .get(&s.name)
                     .ok_or(ModelCreationError::StateNotMentionned(s.name()))?
                     .iter()
-                    .map(|n| {
+                    .map(|(_, n)| {
                         name_idx
                             .get(n)
                             .ok_or(ModelCreationError::DanglingEdge(n.to_string()))
@@ -101,6 +125,35 @@ impl Model {
                     .copied()
             })
             .collect::<Result<Vec<usize>, ModelCreationError>>()?;
+
+        let mut actions: Vec<String> = Vec::new();
+        let mut action_idx: HashMap<&str, usize> = HashMap::new();
+        let post_actions: Vec<Vec<Option<usize>>> = states
+            .iter()
+            .map(|s| {
+                edges[&s.name]
+                    .iter()
+                    .map(|(action, _)| {
+                        let action = action.as_deref()?;
+                        Some(*action_idx.entry(action).or_insert_with(|| {
+                            actions.push(action.to_string());
+                            actions.len() - 1
+                        }))
+                    })
+                    .collect()
+            })
+            .collect();
+        let mut pre_actions = vec![Vec::new(); states.len()];
+        for (src, (targets, labels)) in post_idx.iter().zip(post_actions.iter()).enumerate() {
+            for (&dest, &action) in targets.iter().zip(labels.iter()) {
+                pre_actions[dest].push((src, action));
+            }
+        }
+        let edges = edges
+            .into_iter()
+            .map(|(k, v)| (k, v.into_iter().map(|(_, t)| t).collect()))
+            .collect();
+
         Ok(Self {
             states,
             name_idx,
@@ -109,6 +162,9 @@ impl Model {
             pre_idx,
             initial_states,
             initial_idx,
+            actions,
+            post_actions,
+            pre_actions,
         })
     }
     pub(crate) fn get_idx(&self, name: &str) -> Option<usize> {
@@ -120,11 +176,75 @@ impl Model {
     pub fn get_state(&self, name: &str) -> Option<&State> {
         self.states.get(self.get_idx(name)?)
     }
+    pub fn get_states(&self) -> &[State] {
+        &self.states
+    }
+    /// The initial states, in the order they were given in.
+    pub(crate) fn get_initial(&self) -> &[String] {
+        &self.initial_states
+    }
+    pub(crate) fn get_initial_idx(&self) -> &[usize] {
+        &self.initial_idx
+    }
+    pub(crate) fn get_state_by_idx(&self, idx: usize) -> &State {
+        self.states.get(idx).expect("All indexes are valid")
+    }
+    pub(crate) fn nr_states(&self) -> usize {
+        self.states.len()
+    }
+    /// The successor lists of all states, indexed by state index.
+    pub(crate) fn get_post_idx(&self) -> &[Vec<usize>] {
+        &self.post_idx
+    }
+    /// The predecessor lists of all states, indexed by state index.
+    pub(crate) fn get_pre_idx(&self) -> &[Vec<usize>] {
+        &self.pre_idx
+    }
+    /// The actions of the outgoing transitions of all states, parallel to `get_post_idx`.
+    pub(crate) fn get_post_actions(&self) -> &[Vec<Option<usize>>] {
+        &self.post_actions
+    }
+    /// The incoming transitions of all states, as their source and action.
+    pub(crate) fn get_pre_actions(&self) -> &[Vec<(usize, Option<usize>)>] {
+        &self.pre_actions
+    }
     pub fn get_next(&self, name: &str) -> Option<Vec<String>> {
         self.edges.get(name).cloned()
     }
-    pub(crate) fn all_idx(&self) -> HashSet<usize> {
-        self.states.iter().enumerate().map(|(i, _)| i).collect()
+    /// The outgoing transitions of a state, as their action label and target,
+    /// in the order they were given when creating the model.
+    pub fn get_transitions(&self, name: &str) -> Option<Vec<(Option<String>, String)>> {
+        let idx = self.get_idx(name)?;
+        let transitions = self.post_idx[idx]
+            .iter()
+            .zip(self.post_actions[idx].iter())
+            .map(|(&target, action)| {
+                let action = action.map(|a| self.actions[a].clone());
+                (action, self.states[target].name())
+            })
+            .collect();
+        Some(transitions)
+    }
+    /// Fails with the first state that has no outgoing transitions, if there is one.
+    /// Checkers handle such sinks, but they are usually a mistake in the model.
+    pub fn check_no_sinks(&self) -> Result<(), ModelCreationError> {
+        match self.post_idx.iter().position(Vec::is_empty) {
+            Some(idx) => Err(ModelCreationError::EmptyEdgeList(self.states[idx].name())),
+            None => Ok(()),
+        }
+    }
+    /// All actions that label some transition of the model.
+    pub fn all_actions(&self) -> HashSet<String> {
+        self.actions.iter().cloned().collect()
+    }
+    /// Whether any transition of the model has an action label.
+    pub fn is_labelled(&self) -> bool {
+        !self.actions.is_empty()
+    }
+    pub(crate) fn get_action_idxs(&self, actions: &HashSet<String>) -> HashSet<usize> {
+        (0..self.actions.len())
+            .filter(|&a| actions.contains(&self.actions[a]))
+            .collect()
     }
     pub fn all(&self) -> HashSet<String> {
         self.states.iter().map(|s| s.name()).collect()
@@ -135,6 +255,41 @@ impl Model {
     pub fn all_initial_idx(&self) -> HashSet<usize> {
         HashSet::from_iter(self.initial_idx.iter().copied())
     }
+    /// The indexes of all states reachable from an initial state, in increasing order.
+    pub(crate) fn reachable_idx(&self) -> Vec<usize> {
+        let mut seen = vec![false; self.states.len()];
+        let mut worklist = self.initial_idx.clone();
+        for &i in &worklist {
+            seen[i] = true;
+        }
+        while let Some(i) = worklist.pop() {
+            for &next in &self.post_idx[i] {
+                if !seen[next] {
+                    seen[next] = true;
+                    worklist.push(next);
+                }
+            }
+        }
+        (0..self.states.len()).filter(|&i| seen[i]).collect()
+    }
+    /// The part of this model that is reachable from its initial states,
+    /// with the states in the same order, and all their transitions.
+    pub fn reachable(&self) -> Model {
+        let reachable = self.reachable_idx();
+        let states = reachable.iter().map(|&i| self.states[i].clone()).collect();
+        let edges = reachable
+            .iter()
+            .map(|&i| {
+                let name = self.states[i].name();
+                let transitions = self
+                    .get_transitions(&name)
+                    .expect("States are in the model");
+                (name, transitions)
+            })
+            .collect();
+        Model::new_labelled(states, edges, self.initial_states.clone())
+            .expect("The successors of reachable states are reachable")
+    }
     pub(crate) fn all_containing_idx(&self, var: &str) -> HashSet<usize> {
         self.states
             .iter()
@@ -166,6 +321,7 @@ impl Model {
             .collect()
     }
     /// The set of states that can transition into the ones given.
+    #[cfg_attr(not(feature = "python"), allow(dead_code))]
     pub(crate) fn pre_e_idx(&self, indexes: &HashSet<usize>) -> HashSet<usize> {
         debug_assert!(indexes.iter().all(|&i| self.states.get(i).is_some()));
         indexes
@@ -175,6 +331,7 @@ impl Model {
             .collect()
     }
     /// The set of states for which all transitions mean transitioning into a state given.
+    #[cfg_attr(not(feature = "python"), allow(dead_code))]
     pub(crate) fn pre_a_idx(&self, indexes: &HashSet<usize>) -> HashSet<usize> {
         debug_assert!(indexes.iter().all(|&i| self.states.get(i).is_some()));
         indexes
@@ -190,6 +347,50 @@ impl Model {
             .copied()
             .collect()
     }
+    /// The set of states with a transition labelled by one of `actions` into the ones given.
+    pub(crate) fn pre_e_actions_idx(
+        &self,
+        indexes: &HashSet<usize>,
+        actions: &HashSet<usize>,
+    ) -> HashSet<usize> {
+        debug_assert!(indexes.iter().all(|&i| self.states.get(i).is_some()));
+        indexes
+            .iter()
+            .flat_map(|&i| &self.pre_actions[i])
+            .filter(|(_, action)| action.is_some_and(|a| actions.contains(&a)))
+            .map(|&(src, _)| src)
+            .collect()
+    }
+    /// The set of states for which all transitions labelled by one of `actions`
+    /// go into a state given. This includes states without any such transitions.
+    pub(crate) fn pre_a_actions_idx(
+        &self,
+        indexes: &HashSet<usize>,
+        actions: &HashSet<usize>,
+    ) -> HashSet<usize> {
+        debug_assert!(indexes.iter().all(|&i| self.states.get(i).is_some()));
+        (0..self.states.len())
+            .filter(|&i| {
+                self.post_idx[i]
+                    .iter()
+                    .zip(self.post_actions[i].iter())
+                    .all(|(target, action)| {
+                        !action.is_some_and(|a| actions.contains(&a)) || indexes.contains(target)
+                    })
+            })
+            .collect()
+    }
+    /// A copy of this model where each variable in `labels` is additionally true
+    /// in the states with the given indexes.
+    pub(crate) fn with_labels(&self, labels: &[(String, HashSet<usize>)]) -> Model {
+        let mut ret = self.clone();
+        for (var, indexes) in labels {
+            for &i in indexes {
+                ret.states[i].vars.insert(var.clone());
+            }
+        }
+        ret
+    }
     pub(crate) fn get_names(&self, indexes: &HashSet<usize>) -> HashSet<String> {
         // All indexes should still be valid, pointing into the vec, as we don't allow
         // public modification.
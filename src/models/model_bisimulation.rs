@@ -0,0 +1,251 @@
+// Minimisation up to bisimulation, by the partition refinement of Paige and Tarjan.
+//
+// Two states are bisimilar if they satisfy the same propositions, and every transition of one
+// can be matched by a transition with the same action of the other, into bisimilar states.
+// We start from the partition of the states by their propositions, and keep splitting blocks
+// until every block is stable: for every action and every block B, either all of its states
+// have a transition into B, or none of them have.
+//
+// The trick of Paige and Tarjan is to keep a second, coarser partition of "compound" blocks,
+// with respect to which the fine partition is already stable. To make progress, we take a
+// compound block S with at least two blocks in it, and split off the smaller one, B.
+// Stability with respect to B and S \ B then only needs the transitions into B, as long as we
+// count, for every state and action, how many of its transitions go into each compound block:
+//     - a state with no transitions into B only has to be told apart from those that do
+//     - of the states that do, those with all their transitions into S going into B
+//       have to be told apart from those with some going into S \ B
+// As every state ends up in the smaller half only a logarithmic number of times,
+// this takes O(m log n) time, for m transitions and n states, up to the hashing of the counts.
+use std::collections::HashMap;
+
+use super::{Model, State};
+
+// The action of a transition, with `None` for unlabelled ones.
+type Action = Option<usize>;
+
+struct Refinement<'a> {
+    model: &'a Model,
+    // The fine partition, as the block of every state, and the states in every block.
+    // `position` is the index of every state in the list of its block,
+    // so that we can move it into another block in constant time.
+    block_of: Vec<usize>,
+    blocks: Vec<Vec<usize>>,
+    position: Vec<usize>,
+    // The coarse partition, as the compound block of every block, and the blocks in every one.
+    compound_of: Vec<usize>,
+    compounds: Vec<Vec<usize>>,
+    // The compound blocks that contain more than one block, which still have to be processed.
+    queue: Vec<usize>,
+    in_queue: Vec<bool>,
+    // For every state, action and compound block, the number of transitions going into it.
+    counts: HashMap<(usize, Action, usize), usize>,
+}
+
+impl<'a> Refinement<'a> {
+    fn new(model: &'a Model) -> Self {
+        let nr_states = model.nr_states();
+        let mut by_vars: HashMap<Vec<&String>, usize> = HashMap::new();
+        let mut blocks: Vec<Vec<usize>> = Vec::new();
+        let mut block_of = Vec::with_capacity(nr_states);
+        let mut position = Vec::with_capacity(nr_states);
+        for (i, state) in model.get_states().iter().enumerate() {
+            let mut vars: Vec<&String> = state.vars().iter().collect();
+            vars.sort();
+            let block = *by_vars.entry(vars).or_insert_with(|| {
+                blocks.push(Vec::new());
+                blocks.len() - 1
+            });
+            block_of.push(block);
+            position.push(blocks[block].len());
+            blocks[block].push(i);
+        }
+        let mut counts = HashMap::new();
+        for (src, actions) in model.get_post_actions().iter().enumerate() {
+            for &action in actions {
+                *counts.entry((src, action, 0)).or_insert(0) += 1;
+            }
+        }
+        let nr_blocks = blocks.len();
+        let mut ret = Self {
+            model,
+            block_of,
+            blocks,
+            position,
+            compound_of: vec![0; nr_blocks],
+            compounds: vec![(0..nr_blocks).collect()],
+            queue: Vec::new(),
+            in_queue: vec![false],
+            counts,
+        };
+        ret.enqueue(0);
+        // The initial partition also has to be stable with respect to the single compound block
+        // of all states, so we split off the states with transitions of every action.
+        let mut by_action: HashMap<Action, Vec<usize>> = HashMap::new();
+        for &(src, action, _) in ret.counts.keys() {
+            by_action.entry(action).or_default().push(src);
+        }
+        for states in by_action.values() {
+            ret.split(states);
+        }
+        ret
+    }
+    fn enqueue(&mut self, compound: usize) {
+        if self.compounds[compound].len() > 1 && !self.in_queue[compound] {
+            self.in_queue[compound] = true;
+            self.queue.push(compound);
+        }
+    }
+    /// Split every block into the states in `states` and the ones that are not.
+    /// Every state can only be in `states` once.
+    fn split(&mut self, states: &[usize]) {
+        let mut marked: HashMap<usize, Vec<usize>> = HashMap::new();
+        for &state in states {
+            marked.entry(self.block_of[state]).or_default().push(state);
+        }
+        for (block, moving) in marked {
+            if moving.len() == self.blocks[block].len() {
+                continue;
+            }
+            let new_block = self.blocks.len();
+            self.blocks.push(Vec::with_capacity(moving.len()));
+            for state in moving {
+                let pos = self.position[state];
+                self.blocks[block].swap_remove(pos);
+                if let Some(&swapped) = self.blocks[block].get(pos) {
+                    self.position[swapped] = pos;
+                }
+                self.block_of[state] = new_block;
+                self.position[state] = self.blocks[new_block].len();
+                self.blocks[new_block].push(state);
+            }
+            let compound = self.compound_of[block];
+            self.compound_of.push(compound);
+            self.compounds[compound].push(new_block);
+            self.enqueue(compound);
+        }
+    }
+    fn run(&mut self) {
+        while let Some(compound) = self.queue.pop() {
+            self.in_queue[compound] = false;
+            let parts = &self.compounds[compound];
+            let smallest = match self.blocks[parts[0]].len() <= self.blocks[parts[1]].len() {
+                true => 0,
+                false => 1,
+            };
+            let splitter = self.compounds[compound].swap_remove(smallest);
+            let new_compound = self.compounds.len();
+            self.compounds.push(vec![splitter]);
+            self.in_queue.push(false);
+            self.compound_of[splitter] = new_compound;
+            self.enqueue(compound);
+
+            let mut into_splitter: HashMap<(usize, Action), usize> = HashMap::new();
+            for &target in &self.blocks[splitter] {
+                for &(src, action) in &self.model.get_pre_actions()[target] {
+                    *into_splitter.entry((src, action)).or_insert(0) += 1;
+                }
+            }
+            let mut by_action: HashMap<Action, (Vec<usize>, Vec<usize>)> = HashMap::new();
+            for (&(src, action), &count) in &into_splitter {
+                let (some, all) = by_action.entry(action).or_default();
+                some.push(src);
+                let old = self.counts[&(src, action, compound)];
+                if count == old {
+                    all.push(src);
+                }
+            }
+            for (&(src, action), &count) in &into_splitter {
+                let old = self
+                    .counts
+                    .get_mut(&(src, action, compound))
+                    .expect("Transitions into the splitter are counted in its compound block");
+                *old -= count;
+                if *old == 0 {
+                    self.counts.remove(&(src, action, compound));
+                }
+                self.counts.insert((src, action, new_compound), count);
+            }
+            for (some, all) in by_action.values() {
+                self.split(some);
+                self.split(all);
+            }
+        }
+    }
+}
+
+impl Model {
+    /// The bisimulation classes of the states, as a class number for every state index.
+    /// The classes are numbered in the order of their first state.
+    pub(crate) fn bisimulation_classes(&self) -> Vec<usize> {
+        let mut refinement = Refinement::new(self);
+        refinement.run();
+        let mut numbers = HashMap::new();
+        refinement
+            .block_of
+            .iter()
+            .map(|&block| {
+                let next = numbers.len();
+                *numbers.entry(block).or_insert(next)
+            })
+            .collect()
+    }
+    /// The smallest model that is bisimilar to this one, where all bisimilar states are merged.
+    /// As CTL, LTL, CTL*, the mu-calculus, and Hennessy–Milner logic cannot tell bisimilar states
+    /// apart, every formula holds in the same states of both, which makes this a cheap way to
+    /// shrink models before checking them. Transitions are only matched with ones with the same
+    /// action, so it is also bisimilar as a labelled transition system.
+    ///
+    /// Every merged state is named after the first of its states, in the order of the states of
+    /// this model, and keeps its variables. The mapping from every state of this model to the
+    /// state of the quotient it was merged into is returned alongside it.
+    pub fn minimise_bisimulation(&self) -> (Model, HashMap<String, String>) {
+        let classes = self.bisimulation_classes();
+        let mut representatives: Vec<usize> = Vec::new();
+        for (i, &class) in classes.iter().enumerate() {
+            if class == representatives.len() {
+                representatives.push(i);
+            }
+        }
+        let states = self.get_states();
+        let name_of = |class: usize| states[representatives[class]].name();
+        let quotient_states = representatives
+            .iter()
+            .map(|&i| State::new(states[i].name(), states[i].vars().clone()))
+            .collect();
+        // All states in a class have the same transitions up to bisimulation,
+        // so the ones of the representative are all we need.
+        let edges = representatives
+            .iter()
+            .map(|&i| {
+                let name = states[i].name();
+                let mut transitions: Vec<(Option<String>, String)> = Vec::new();
+                for (action, target) in self
+                    .get_transitions(&name)
+                    .expect("States are in the model")
+                {
+                    let target = self.get_idx(&target).expect("Targets are in the model");
+                    let transition = (action, name_of(classes[target]));
+                    if !transitions.contains(&transition) {
+                        transitions.push(transition);
+                    }
+                }
+                (name, transitions)
+            })
+            .collect();
+        let mut initial_states: Vec<String> = Vec::new();
+        for &i in self.get_initial_idx() {
+            let name = name_of(classes[i]);
+            if !initial_states.contains(&name) {
+                initial_states.push(name);
+            }
+        }
+        let mapping = classes
+            .iter()
+            .enumerate()
+            .map(|(i, &class)| (states[i].name(), name_of(class)))
+            .collect();
+        let quotient = Model::new_labelled(quotient_states, edges, initial_states)
+            .expect("The quotient only contains states and transitions of this model");
+        (quotient, mapping)
+    }
+}
@@ -0,0 +1,61 @@
+// Graphviz DOT export of models, for drawing them instead of reading the edge map.
+//
+// Nodes are named by index (n0, n1, ...) and labelled with the state name and its variables,
+// so we never have to worry about state names clashing with each other or DOT keywords.
+// Initial states get an incoming arrow from an invisible point, as is usual in automata drawings.
+// Transitions with an action are labelled with it.
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use super::Model;
+
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+impl Model {
+    /// Export this model to Graphviz DOT, with every state labelled with its name
+    /// and the variables that are true in it, and the initial states marked with an incoming arrow.
+    ///
+    /// If `highlight` is given, the states in it are coloured.
+    /// This is meant to be passed the result of a checker, to show where a formula holds.
+    /// Returns None if `highlight` contains a name that is not in the model.
+    pub fn to_dot(&self, highlight: Option<&HashSet<String>>) -> Option<String> {
+        let highlight = match highlight {
+            Some(names) => self.get_idxs(names)?,
+            None => HashSet::new(),
+        };
+        let mut out = String::from("digraph {\n    node [shape=circle];\n");
+        for (i, state) in self.get_states().iter().enumerate() {
+            let mut vars: Vec<&str> = state.vars().iter().map(String::as_str).collect();
+            vars.sort();
+            let name = escape(&state.name());
+            let vars = escape(&vars.join(", "));
+            let _ = write!(out, "    n{i} [label=\"{name}\\n{{{vars}}}\"");
+            if highlight.contains(&i) {
+                out.push_str(", style=filled, fillcolor=lightblue");
+            }
+            out.push_str("];\n");
+        }
+        for &i in self.get_initial_idx() {
+            let _ = writeln!(out, "    i{i} [shape=none, label=\"\"];");
+            let _ = writeln!(out, "    i{i} -> n{i};");
+        }
+        for (i, state) in self.get_states().iter().enumerate() {
+            let transitions = self.get_transitions(&state.name()).unwrap_or_default();
+            for (&j, (action, _)) in self.get_post_idx()[i].iter().zip(transitions.iter()) {
+                match action {
+                    Some(action) => {
+                        let action = escape(action);
+                        let _ = writeln!(out, "    n{i} -> n{j} [label=\"{action}\"];");
+                    }
+                    None => {
+                        let _ = writeln!(out, "    n{i} -> n{j};");
+                    }
+                }
+            }
+        }
+        out.push_str("}\n");
+        Some(out)
+    }
+}
@@ -0,0 +1,255 @@
+// Parallel composition of models into a single product model.
+//
+// The states of the product are tuples of states of the components, of which we only build the
+// ones reachable from the tuples of initial states. A tuple is named by joining the names of
+// its states with commas, like "s0,t1", and satisfies the variables of its states, prefixed with
+// the name of their component, like "p1.x". That keeps the variables of different components
+// apart, even when they use the same names, and they can be used in formulas as they are.
+//
+// There are two ways for the components to step:
+//     - synchronously, where every component takes a step at the same time
+//     - interleaved, where one component takes a step, while the others stay where they are
+// Either can be synchronised on actions. Synchronously, this means all components have to take
+// a transition with the same action. Interleaved, this means that actions shared between
+// components can only be taken by all components that know them at once, like in CSP,
+// while all other transitions are still taken by one component at a time.
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use thiserror::Error;
+
+use super::{Model, ModelCreationError, State};
+
+#[derive(Debug, PartialEq, Error)]
+pub enum ProductError {
+    #[error("A product needs at least one component")]
+    NoComponents,
+    #[error("Two components have the same name: {0}")]
+    DuplicateComponent(String),
+    #[error("Two product states have the same name: {0}")]
+    DuplicateState(String),
+    #[error(transparent)]
+    Model(#[from] ModelCreationError),
+}
+
+// The outgoing transitions of every state of every component, by action name and target index.
+type Transitions = Vec<Vec<Vec<(Option<String>, usize)>>>;
+
+fn transitions(components: &[(&str, &Model)]) -> Transitions {
+    components
+        .iter()
+        .map(|(_, model)| {
+            model
+                .get_states()
+                .iter()
+                .map(|state| {
+                    model
+                        .get_transitions(&state.name())
+                        .expect("States are in their model")
+                        .into_iter()
+                        .map(|(action, target)| {
+                            let target = model.get_idx(&target).expect("Targets are in the model");
+                            (action, target)
+                        })
+                        .collect()
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// All ways to pick one item out of every list.
+pub(super) fn combinations<T: Clone>(lists: &[Vec<T>]) -> Vec<Vec<T>> {
+    lists.iter().fold(vec![Vec::new()], |acc, list| {
+        acc.iter()
+            .flat_map(|prefix| {
+                list.iter().map(move |item| {
+                    let mut ret = prefix.clone();
+                    ret.push(item.clone());
+                    ret
+                })
+            })
+            .collect()
+    })
+}
+
+/// Build the reachable part of the product, where `step` gives the transitions of a tuple.
+fn product(
+    components: &[(&str, &Model)],
+    step: impl Fn(&[usize]) -> Vec<(Option<String>, Vec<usize>)>,
+) -> Result<Model, ProductError> {
+    if components.is_empty() {
+        return Err(ProductError::NoComponents);
+    }
+    let mut seen_names = HashSet::new();
+    if let Some((name, _)) = components.iter().find(|(n, _)| !seen_names.insert(*n)) {
+        return Err(ProductError::DuplicateComponent(name.to_string()));
+    }
+    let name_of = |tuple: &[usize]| {
+        tuple
+            .iter()
+            .zip(components.iter())
+            .map(|(&i, (_, model))| model.get_state_by_idx(i).name())
+            .collect::<Vec<String>>()
+            .join(",")
+    };
+
+    let initial_idx: Vec<Vec<usize>> = components
+        .iter()
+        .map(|(_, model)| model.get_initial_idx().to_vec())
+        .collect();
+    let initial = combinations(&initial_idx);
+    let mut index: HashMap<Vec<usize>, usize> = HashMap::new();
+    let mut queue: VecDeque<Vec<usize>> = VecDeque::new();
+    for tuple in &initial {
+        if !index.contains_key(tuple) {
+            index.insert(tuple.clone(), index.len());
+            queue.push_back(tuple.clone());
+        }
+    }
+
+    let mut states = Vec::new();
+    let mut edges = HashMap::new();
+    let mut names = HashSet::new();
+    while let Some(tuple) = queue.pop_front() {
+        let name = name_of(&tuple);
+        if !names.insert(name.clone()) {
+            return Err(ProductError::DuplicateState(name));
+        }
+        let vars = tuple
+            .iter()
+            .zip(components.iter())
+            .flat_map(|(&i, (prefix, model))| {
+                let state = model.get_state_by_idx(i);
+                state
+                    .vars()
+                    .iter()
+                    .map(move |var| format!("{prefix}.{var}"))
+            })
+            .collect();
+        states.push(State::new(name.clone(), vars));
+
+        let mut out: Vec<(Option<String>, String)> = Vec::new();
+        for (action, target) in step(&tuple) {
+            if !index.contains_key(&target) {
+                index.insert(target.clone(), index.len());
+                queue.push_back(target.clone());
+            }
+            let transition = (action, name_of(&target));
+            if !out.contains(&transition) {
+                out.push(transition);
+            }
+        }
+        edges.insert(name, out);
+    }
+    let mut initial_states: Vec<String> = Vec::new();
+    for tuple in &initial {
+        let name = name_of(tuple);
+        if !initial_states.contains(&name) {
+            initial_states.push(name);
+        }
+    }
+    Ok(Model::new_labelled(states, edges, initial_states)?)
+}
+
+impl Model {
+    /// The synchronous product of the components, where all of them take a step at the same time.
+    /// Every component is given with a name, which prefixes its variables in the product,
+    /// so the variable `x` of the component named `p1` becomes `p1.x`.
+    /// The states of the product are named after the states of the components, joined by commas.
+    /// Only the states reachable from the initial states are part of the product.
+    ///
+    /// If `synchronise` is set, the components can only step together on transitions with the
+    /// same action, which is then the action of the product transition. Otherwise, any
+    /// combination of transitions can be taken, and it only keeps an action all of them share.
+    ///
+    /// As the product can only step if all components can, it can have sinks.
+    pub fn synchronous_product(
+        components: &[(&str, &Model)],
+        synchronise: bool,
+    ) -> Result<Model, ProductError> {
+        let transitions = transitions(components);
+        product(components, |tuple| {
+            let options: Vec<Vec<(Option<String>, usize)>> = tuple
+                .iter()
+                .enumerate()
+                .map(|(c, &i)| transitions[c][i].clone())
+                .collect();
+            combinations(&options)
+                .into_iter()
+                .filter_map(|steps| {
+                    let action = steps[0].0.clone();
+                    let agree = steps.iter().all(|(a, _)| *a == action);
+                    if synchronise && !agree {
+                        return None;
+                    }
+                    let action = if agree { action } else { None };
+                    Some((action, steps.into_iter().map(|(_, t)| t).collect()))
+                })
+                .collect()
+        })
+    }
+    /// The interleaving (or asynchronous) product of the components, where one of them takes a
+    /// step at a time, while the others stay where they are. Every transition keeps its action.
+    /// Variables and states are named as in `synchronous_product`, and again only the states
+    /// reachable from the initial states are part of the product.
+    ///
+    /// If `synchronise` is set, the actions that occur in more than one component are shared:
+    /// they can only be taken by all components that have them at the same time, and not by any
+    /// of them alone. This is how components can wait for each other, or pass messages.
+    /// Unlabelled transitions and actions of only one component are still interleaved.
+    pub fn interleaving_product(
+        components: &[(&str, &Model)],
+        synchronise: bool,
+    ) -> Result<Model, ProductError> {
+        let transitions = transitions(components);
+        let mut occurrences: HashMap<String, Vec<usize>> = HashMap::new();
+        for (c, (_, model)) in components.iter().enumerate() {
+            for action in model.all_actions() {
+                occurrences.entry(action).or_default().push(c);
+            }
+        }
+        let mut shared: Vec<(String, Vec<usize>)> = occurrences
+            .into_iter()
+            .filter(|(_, participants)| synchronise && participants.len() > 1)
+            .collect();
+        shared.sort();
+        let is_shared = |action: &Option<String>| {
+            action
+                .as_ref()
+                .is_some_and(|a| shared.iter().any(|(s, _)| s == a))
+        };
+        product(components, |tuple| {
+            let mut ret = Vec::new();
+            for (c, &i) in tuple.iter().enumerate() {
+                for (action, target) in &transitions[c][i] {
+                    if is_shared(action) {
+                        continue;
+                    }
+                    let mut next = tuple.to_vec();
+                    next[c] = *target;
+                    ret.push((action.clone(), next));
+                }
+            }
+            for (action, participants) in &shared {
+                let options: Vec<Vec<usize>> = participants
+                    .iter()
+                    .map(|&c| {
+                        transitions[c][tuple[c]]
+                            .iter()
+                            .filter(|(a, _)| a.as_ref() == Some(action))
+                            .map(|&(_, t)| t)
+                            .collect()
+                    })
+                    .collect();
+                for targets in combinations(&options) {
+                    let mut next = tuple.to_vec();
+                    for (&c, target) in participants.iter().zip(targets) {
+                        next[c] = target;
+                    }
+                    ret.push((Some(action.clone()), next));
+                }
+            }
+            ret
+        })
+    }
+}
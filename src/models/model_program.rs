@@ -0,0 +1,644 @@
+// A small guarded-command language in the style of SMV, so that protocols can be written as
+// rules for going from one state to the next, instead of as a list of all their states.
+//
+// ```text
+// VAR                                     # finite-domain variables
+//   step : {acquire, access, release, done};
+//   lock : {free, held};
+//   tries : 0..3;                         # a range of numbers
+//   waiting : boolean;                    # short for {false, true}
+// INIT                                    # the initial states
+//   step=acquire and lock=free and tries=0;
+// TRANS                                   # guarded commands, as guard : updates
+//   step=acquire and lock=free : step := access, lock := held;
+//   step=acquire and lock=held : tries := tries + 1;
+//   step=access : step := {access, release};
+//   step=release : step := done, lock := free;
+//   step=done : ;                         # no updates: stay in the same state
+// CTLSPEC AG(step=access -> lock=held);
+// LTLSPEC G(lock=held -> F lock=free);
+// ```
+//
+// Statements end with a ';', and belong to the last section keyword before them.
+// Comments start with a '#' and run until the end of the line.
+//
+// The states are all assignments of values to the variables that are reachable from the ones
+// that satisfy every INIT condition (or from all of them, if there are none). A state is named
+// after its assignment, like "step=acquire,lock=free", and satisfies the propositions `var=value`
+// for every variable, so specs can use them as they are. In every state, all commands whose guard
+// holds can be taken. A command sets the variables it updates, to one of the values in a set for
+// `{a, b}`, and leaves the others as they are. States in which no guard holds are sinks.
+//
+// Guards and INIT conditions are written like CTL formulas without temporal operators.
+// The right-hand side of an update can be a value, a set of values, another variable
+// (which takes precedence over a value with the same name), or a variable plus or minus a number.
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use thiserror::Error;
+
+use super::model_product::combinations;
+use super::{Model, ModelCreationError, State};
+use crate::formulas::{parse_ctl, parse_ltl, CTLFormula, LTLFormula};
+
+#[derive(Debug, PartialEq, Error)]
+pub enum ProgramError {
+    #[error("Line {0}: Expected VAR, INIT, TRANS, CTLSPEC, or LTLSPEC before {1}")]
+    NoSection(usize, String),
+    #[error("Line {0}: Expected a declaration like `x : {{a, b}}`, `x : 0..3`, or `x : boolean`, found {1}")]
+    InvalidDeclaration(usize, String),
+    #[error("Line {0}: Invalid name: {1}")]
+    InvalidName(usize, String),
+    #[error("Line {0}: Variable declared twice: {1}")]
+    DuplicateVariable(usize, String),
+    #[error("Line {0}: Unknown variable: {1}")]
+    UnknownVariable(usize, String),
+    #[error("Line {0}: {1} is not a value of {2}")]
+    UnknownValue(usize, String, String),
+    #[error("Line {0}: Expected a proposition like var=value, found {1}")]
+    InvalidProposition(usize, String),
+    #[error("Line {0}: Invalid formula: {1}")]
+    InvalidFormula(usize, String),
+    #[error("Line {0}: Guards and initial conditions cannot use temporal operators")]
+    TemporalCondition(usize),
+    #[error("Line {0}: Expected ':' between the guard and the updates")]
+    ExpectedColon(usize),
+    #[error("Line {0}: Expected an update like `x := value`, found {1}")]
+    InvalidUpdate(usize, String),
+    #[error("Line {0}: {1} is updated twice")]
+    DuplicateUpdate(usize, String),
+    #[error("Line {0}: {1} is not a number, so it cannot be counted with")]
+    NotANumber(usize, String),
+    #[error("Line {0}: {1} := {2} is out of the range of {1}")]
+    OutOfRange(usize, String, String),
+    #[error("No variables declared")]
+    NoVariables,
+    #[error("No assignment satisfies the initial conditions")]
+    NoInitialStates,
+    #[error(transparent)]
+    Model(#[from] ModelCreationError),
+}
+
+/// A spec of a program, with the text it was parsed from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Specification {
+    Ctl(String, Box<CTLFormula>),
+    Ltl(String, Box<LTLFormula>),
+}
+
+/// A program compiled into a model, together with the specs it should satisfy.
+#[derive(Debug, Clone)]
+pub struct Program {
+    pub model: Model,
+    pub specs: Vec<Specification>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Section {
+    Var,
+    Init,
+    Trans,
+    CtlSpec,
+    LtlSpec,
+}
+
+impl Section {
+    fn from_keyword(word: &str) -> Option<Self> {
+        match word {
+            "VAR" => Some(Self::Var),
+            "INIT" => Some(Self::Init),
+            "TRANS" => Some(Self::Trans),
+            "CTLSPEC" => Some(Self::CtlSpec),
+            "LTLSPEC" => Some(Self::LtlSpec),
+            _ => None,
+        }
+    }
+}
+
+struct Statement {
+    line: usize,
+    section: Section,
+    text: String,
+}
+
+/// Split the input into statements, with the line on which each of them starts.
+fn statements(input: &str) -> Result<Vec<Statement>, ProgramError> {
+    // Comments are removed, but the line breaks are kept, so that we can still count lines.
+    let text = input
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default())
+        .collect::<Vec<&str>>()
+        .join("\n");
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+    let mut ret = Vec::new();
+    let mut section = None;
+    let mut line = 1;
+    for part in text.split(';') {
+        let mut rest = part;
+        loop {
+            let trimmed = rest.trim_start();
+            line += rest[..rest.len() - trimmed.len()].matches('\n').count();
+            rest = trimmed;
+            let word_end = rest.find(|c| !is_word_char(c)).unwrap_or(rest.len());
+            match Section::from_keyword(&rest[..word_end]) {
+                Some(keyword) => {
+                    section = Some(keyword);
+                    rest = &rest[word_end..];
+                }
+                None => break,
+            }
+        }
+        let statement = rest.trim_end();
+        if !statement.is_empty() {
+            let Some(section) = section else {
+                let word = statement.split_whitespace().next().unwrap_or_default();
+                return Err(ProgramError::NoSection(line, word.to_string()));
+            };
+            ret.push(Statement {
+                line,
+                section,
+                text: statement.to_string(),
+            });
+        }
+        line += rest.matches('\n').count();
+    }
+    Ok(ret)
+}
+
+/// Whether this can be the name of a variable or a value, and so be part of a proposition.
+fn is_name(name: &str, first: impl Fn(char) -> bool) -> bool {
+    name.starts_with(first)
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        && !matches!(name, "and" | "or")
+}
+
+#[derive(Debug)]
+pub(crate) struct Variable {
+    pub(crate) name: String,
+    pub(crate) values: Vec<String>,
+}
+
+fn declaration(line: usize, text: &str) -> Result<Variable, ProgramError> {
+    let invalid = || ProgramError::InvalidDeclaration(line, text.to_string());
+    let (name, domain) = text.split_once(':').ok_or_else(invalid)?;
+    let (name, domain) = (name.trim(), domain.trim());
+    if !is_name(name, |c| c.is_ascii_lowercase()) {
+        return Err(ProgramError::InvalidName(line, name.to_string()));
+    }
+    let values: Vec<String> = if domain == "boolean" {
+        vec!["false".to_string(), "true".to_string()]
+    } else if let Some(values) = domain.strip_prefix('{').and_then(|d| d.strip_suffix('}')) {
+        values.split(',').map(|v| v.trim().to_string()).collect()
+    } else if let Some((lower, upper)) = domain.split_once("..") {
+        let lower: u64 = lower.trim().parse().map_err(|_| invalid())?;
+        let upper: u64 = upper.trim().parse().map_err(|_| invalid())?;
+        (lower..=upper).map(|i| i.to_string()).collect()
+    } else {
+        return Err(invalid());
+    };
+    if values.is_empty() {
+        return Err(invalid());
+    }
+    let mut seen = HashSet::new();
+    for value in &values {
+        if !is_name(value, |c| c.is_ascii_lowercase() || c.is_ascii_digit()) || !seen.insert(value)
+        {
+            return Err(ProgramError::InvalidName(line, value.clone()));
+        }
+    }
+    Ok(Variable {
+        name: name.to_string(),
+        values,
+    })
+}
+
+pub(crate) struct Variables(pub(crate) Vec<Variable>);
+
+impl Variables {
+    fn get(&self, line: usize, name: &str) -> Result<usize, ProgramError> {
+        self.0
+            .iter()
+            .position(|v| v.name == name)
+            .ok_or_else(|| ProgramError::UnknownVariable(line, name.to_string()))
+    }
+    fn value(&self, line: usize, var: usize, value: &str) -> Result<usize, ProgramError> {
+        let variable = &self.0[var];
+        variable
+            .values
+            .iter()
+            .position(|v| v == value)
+            .ok_or_else(|| {
+                ProgramError::UnknownValue(line, value.to_string(), variable.name.clone())
+            })
+    }
+    /// The variable and value of a proposition like `var=value`.
+    fn proposition(&self, line: usize, atom: &str) -> Result<(usize, usize), ProgramError> {
+        let (var, value) = atom
+            .split_once('=')
+            .ok_or_else(|| ProgramError::InvalidProposition(line, atom.to_string()))?;
+        let var = self.get(line, var)?;
+        Ok((var, self.value(line, var, value)?))
+    }
+}
+
+/// A propositional formula over the variables, with implications rewritten away.
+#[derive(Debug)]
+pub(crate) enum Condition {
+    Top,
+    Bot,
+    Is(usize, usize),
+    Not(Box<Condition>),
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
+}
+
+impl Condition {
+    fn compile(
+        formula: &CTLFormula,
+        vars: &Variables,
+        line: usize,
+    ) -> Result<Box<Condition>, ProgramError> {
+        use CTLFormula as F;
+        use Condition as C;
+        let compile = |formula| Condition::compile(formula, vars, line);
+        let ret = match formula {
+            F::Top => C::Top,
+            F::Bot => C::Bot,
+            F::Atomic(atom) => {
+                let (var, value) = vars.proposition(line, &atom.inner)?;
+                C::Is(var, value)
+            }
+            F::Neg(inner) => C::Not(compile(inner)?),
+            F::And(lhs, rhs) => C::And(compile(lhs)?, compile(rhs)?),
+            F::Or(lhs, rhs) => C::Or(compile(lhs)?, compile(rhs)?),
+            F::ImpliesR(lhs, rhs) => C::Or(Box::new(C::Not(compile(lhs)?)), compile(rhs)?),
+            F::ImpliesL(lhs, rhs) => C::Or(compile(lhs)?, Box::new(C::Not(compile(rhs)?))),
+            F::BiImplies(lhs, rhs) => C::Or(
+                Box::new(C::And(compile(lhs)?, compile(rhs)?)),
+                Box::new(C::And(
+                    Box::new(C::Not(compile(lhs)?)),
+                    Box::new(C::Not(compile(rhs)?)),
+                )),
+            ),
+            _ => return Err(ProgramError::TemporalCondition(line)),
+        };
+        Ok(Box::new(ret))
+    }
+    fn parse(text: &str, vars: &Variables, line: usize) -> Result<Box<Condition>, ProgramError> {
+        let formula =
+            parse_ctl(text).map_err(|e| ProgramError::InvalidFormula(line, e.to_string()))?;
+        Self::compile(&formula, vars, line)
+    }
+    fn holds(&self, state: &[usize]) -> bool {
+        match self {
+            Self::Top => true,
+            Self::Bot => false,
+            Self::Is(var, value) => state[*var] == *value,
+            Self::Not(inner) => !inner.holds(state),
+            Self::And(lhs, rhs) => lhs.holds(state) && rhs.holds(state),
+            Self::Or(lhs, rhs) => lhs.holds(state) || rhs.holds(state),
+        }
+    }
+    /// Whether the condition holds in all states that start with the values in `prefix`,
+    /// in none of them, or `None` if that depends on the variables that are not set yet.
+    fn holds_partial(&self, prefix: &[usize]) -> Option<bool> {
+        match self {
+            Self::Top => Some(true),
+            Self::Bot => Some(false),
+            Self::Is(var, value) => prefix.get(*var).map(|v| v == value),
+            Self::Not(inner) => inner.holds_partial(prefix).map(|b| !b),
+            Self::And(lhs, rhs) => match (lhs.holds_partial(prefix), rhs.holds_partial(prefix)) {
+                (Some(false), _) | (_, Some(false)) => Some(false),
+                (Some(true), Some(true)) => Some(true),
+                _ => None,
+            },
+            Self::Or(lhs, rhs) => match (lhs.holds_partial(prefix), rhs.holds_partial(prefix)) {
+                (Some(true), _) | (_, Some(true)) => Some(true),
+                (Some(false), Some(false)) => Some(false),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// All states satisfying the INIT conditions, in order.
+/// The variables are set one at a time, and any prefix that already falsifies a condition
+/// is dropped right away, so we don't go through every combination of values of all variables.
+fn initial_states(init: &[Condition], vars: &Variables) -> Vec<Vec<usize>> {
+    fn extend(
+        prefix: &mut Vec<usize>,
+        init: &[Condition],
+        vars: &Variables,
+        ret: &mut Vec<Vec<usize>>,
+    ) {
+        if prefix.len() == vars.0.len() {
+            ret.push(prefix.clone());
+            return;
+        }
+        for value in 0..vars.0[prefix.len()].values.len() {
+            prefix.push(value);
+            if init.iter().all(|c| c.holds_partial(prefix) != Some(false)) {
+                extend(prefix, init, vars, ret);
+            }
+            prefix.pop();
+        }
+    }
+    let mut ret = Vec::new();
+    extend(&mut Vec::new(), init, vars, &mut ret);
+    ret
+}
+
+/// The right-hand side of an update.
+#[derive(Debug)]
+pub(crate) enum Expression {
+    // Indexes into the values of the updated variable.
+    Values(Vec<usize>),
+    Copy(usize),
+    Add(usize, i64),
+}
+
+impl Expression {
+    /// The value a copy or a count gives `target`, when the variable it reads has value `source`.
+    /// Fails with the text of that value if it is out of the range of `target`.
+    pub(crate) fn apply(
+        &self,
+        target: usize,
+        source: usize,
+        vars: &Variables,
+    ) -> Result<usize, String> {
+        let value = match self {
+            Self::Values(_) => unreachable!("Only copies and counts read a variable"),
+            Self::Copy(var) => vars.0[*var].values[source].clone(),
+            Self::Add(var, amount) => {
+                let current: i64 = vars.0[*var].values[source]
+                    .parse()
+                    .expect("Only numeric variables can be added to");
+                (current + amount).to_string()
+            }
+        };
+        vars.0[target]
+            .values
+            .iter()
+            .position(|v| *v == value)
+            .ok_or(value)
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct Command {
+    pub(crate) line: usize,
+    pub(crate) guard: Box<Condition>,
+    pub(crate) updates: Vec<(usize, Expression)>,
+}
+
+/// Split on the commas that are not between braces.
+fn split_top_level(text: &str) -> Vec<&str> {
+    let mut ret = Vec::new();
+    let (mut depth, mut start) = (0, 0);
+    for (i, c) in text.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            ',' if depth == 0 => {
+                ret.push(&text[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    ret.push(&text[start..]);
+    ret
+}
+
+fn expression(
+    line: usize,
+    target: usize,
+    text: &str,
+    vars: &Variables,
+) -> Result<Expression, ProgramError> {
+    if let Some(values) = text.strip_prefix('{').and_then(|t| t.strip_suffix('}')) {
+        let values = values
+            .split(',')
+            .map(|value| vars.value(line, target, value.trim()))
+            .collect::<Result<Vec<usize>, ProgramError>>()?;
+        return Ok(Expression::Values(values));
+    }
+    if let Some(op) = text.find(['+', '-']) {
+        let var = vars.get(line, text[..op].trim())?;
+        let amount: i64 = text[op + 1..]
+            .trim()
+            .parse()
+            .map_err(|_| ProgramError::InvalidUpdate(line, text.to_string()))?;
+        let variable = &vars.0[var];
+        if variable.values.iter().any(|v| v.parse::<i64>().is_err()) {
+            return Err(ProgramError::NotANumber(line, variable.name.clone()));
+        }
+        let amount = if text[op..].starts_with('-') {
+            -amount
+        } else {
+            amount
+        };
+        return Ok(Expression::Add(var, amount));
+    }
+    match vars.get(line, text) {
+        Ok(var) => Ok(Expression::Copy(var)),
+        Err(_) => Ok(Expression::Values(vec![vars.value(line, target, text)?])),
+    }
+}
+
+fn command(line: usize, text: &str, vars: &Variables) -> Result<Command, ProgramError> {
+    let colon = text
+        .char_indices()
+        .find(|&(i, c)| c == ':' && !text[i + 1..].starts_with('='))
+        .map(|(i, _)| i)
+        .ok_or(ProgramError::ExpectedColon(line))?;
+    let guard = Condition::parse(&text[..colon], vars, line)?;
+    let mut updates: Vec<(usize, Expression)> = Vec::new();
+    let rest = text[colon + 1..].trim();
+    if !rest.is_empty() {
+        for update in split_top_level(rest) {
+            let (target, value) = update
+                .split_once(":=")
+                .ok_or_else(|| ProgramError::InvalidUpdate(line, update.trim().to_string()))?;
+            let target = vars.get(line, target.trim())?;
+            if updates.iter().any(|&(var, _)| var == target) {
+                return Err(ProgramError::DuplicateUpdate(
+                    line,
+                    vars.0[target].name.clone(),
+                ));
+            }
+            updates.push((target, expression(line, target, value.trim(), vars)?));
+        }
+    }
+    Ok(Command {
+        line,
+        guard,
+        updates,
+    })
+}
+
+fn ctl_atoms(formula: &CTLFormula, atoms: &mut Vec<String>) {
+    if let CTLFormula::Atomic(atom) = formula {
+        atoms.push(atom.inner.clone());
+    }
+    formula.for_each_child(|child| ctl_atoms(child, atoms));
+}
+
+fn ltl_atoms(formula: &LTLFormula, atoms: &mut Vec<String>) {
+    if let LTLFormula::Atomic(atom) = formula {
+        atoms.push(atom.inner.clone());
+    }
+    formula.for_each_child(|child| ltl_atoms(child, atoms));
+}
+
+fn specification(statement: &Statement, vars: &Variables) -> Result<Specification, ProgramError> {
+    let (line, text) = (statement.line, statement.text.clone());
+    let invalid = |e: &dyn ToString| ProgramError::InvalidFormula(line, e.to_string());
+    let mut atoms = Vec::new();
+    let ret = if statement.section == Section::CtlSpec {
+        let formula = parse_ctl(&text).map_err(|e| invalid(&e))?;
+        ctl_atoms(&formula, &mut atoms);
+        Specification::Ctl(text, formula)
+    } else {
+        let formula = parse_ltl(&text).map_err(|e| invalid(&e))?;
+        ltl_atoms(&formula, &mut atoms);
+        Specification::Ltl(text, formula)
+    };
+    // Catch typos in the specs, instead of silently checking them against a variable
+    // that is false everywhere.
+    for atom in atoms {
+        vars.proposition(line, &atom)?;
+    }
+    Ok(ret)
+}
+
+/// All values the command can give every variable, from the given state.
+fn choices(
+    command: &Command,
+    state: &[usize],
+    vars: &Variables,
+) -> Result<Vec<Vec<usize>>, ProgramError> {
+    let mut ret: Vec<Vec<usize>> = state.iter().map(|&value| vec![value]).collect();
+    for (target, expression) in &command.updates {
+        ret[*target] = match expression {
+            Expression::Values(values) => values.clone(),
+            Expression::Copy(var) | Expression::Add(var, _) => {
+                let index = expression
+                    .apply(*target, state[*var], vars)
+                    .map_err(|value| {
+                        ProgramError::OutOfRange(command.line, vars.0[*target].name.clone(), value)
+                    })?;
+                vec![index]
+            }
+        };
+    }
+    Ok(ret)
+}
+
+/// A program as it was written, before its states are worked out.
+/// The symbolic checker compiles this on its own, without listing the states.
+pub(crate) struct ParsedProgram {
+    pub(crate) vars: Variables,
+    pub(crate) init: Vec<Condition>,
+    pub(crate) commands: Vec<Command>,
+    pub(crate) specs: Vec<Specification>,
+}
+
+/// Parse a program in the guarded-command language, without compiling it.
+/// All errors mention the (1-based) line number of the statement they occurred in.
+pub(crate) fn parse_program_text(input: &str) -> Result<ParsedProgram, ProgramError> {
+    let statements = statements(input)?;
+    let mut vars = Variables(Vec::new());
+    for statement in statements.iter().filter(|s| s.section == Section::Var) {
+        let variable = declaration(statement.line, &statement.text)?;
+        if vars.0.iter().any(|v| v.name == variable.name) {
+            return Err(ProgramError::DuplicateVariable(
+                statement.line,
+                variable.name,
+            ));
+        }
+        vars.0.push(variable);
+    }
+    if vars.0.is_empty() {
+        return Err(ProgramError::NoVariables);
+    }
+    let mut init = Vec::new();
+    let mut commands = Vec::new();
+    let mut specs = Vec::new();
+    for statement in &statements {
+        match statement.section {
+            Section::Var => {}
+            Section::Init => init.push(*Condition::parse(&statement.text, &vars, statement.line)?),
+            Section::Trans => commands.push(command(statement.line, &statement.text, &vars)?),
+            Section::CtlSpec | Section::LtlSpec => specs.push(specification(statement, &vars)?),
+        }
+    }
+    Ok(ParsedProgram {
+        vars,
+        init,
+        commands,
+        specs,
+    })
+}
+
+/// Parse a program in the guarded-command language, and compile it into a model.
+/// The specs are returned alongside it, in the order in which they were written.
+///
+/// All errors in the text mention the (1-based) line number of the statement they occurred in.
+pub fn parse_program(input: &str) -> Result<Program, ProgramError> {
+    let ParsedProgram {
+        vars,
+        init,
+        commands,
+        specs,
+    } = parse_program_text(input)?;
+    let initial = initial_states(&init, &vars);
+    if initial.is_empty() {
+        return Err(ProgramError::NoInitialStates);
+    }
+
+    let name_of = |state: &[usize]| {
+        state
+            .iter()
+            .zip(vars.0.iter())
+            .map(|(&value, var)| format!("{}={}", var.name, var.values[value]))
+            .collect::<Vec<String>>()
+    };
+    let mut seen: HashSet<Vec<usize>> = initial.iter().cloned().collect();
+    let mut queue: VecDeque<Vec<usize>> = initial.iter().cloned().collect();
+    let mut states = Vec::new();
+    let mut edges: HashMap<String, Vec<String>> = HashMap::new();
+    while let Some(state) = queue.pop_front() {
+        // The successors in the order the commands give them, without duplicates.
+        let mut next: Vec<Vec<usize>> = Vec::new();
+        let mut next_seen: HashSet<Vec<usize>> = HashSet::new();
+        for command in commands.iter().filter(|c| c.guard.holds(&state)) {
+            for target in combinations(&choices(command, &state, &vars)?) {
+                if next_seen.insert(target.clone()) {
+                    next.push(target);
+                }
+            }
+        }
+        for target in &next {
+            if seen.insert(target.clone()) {
+                queue.push_back(target.clone());
+            }
+        }
+        let props = name_of(&state);
+        let name = props.join(",");
+        edges.insert(
+            name.clone(),
+            next.iter()
+                .map(|target| name_of(target).join(","))
+                .collect(),
+        );
+        states.push(State::new(name, props.into_iter().collect()));
+    }
+    let initial = initial
+        .iter()
+        .map(|state| name_of(state).join(","))
+        .collect();
+    let model = Model::new(states, edges, initial)?;
+    Ok(Program { model, specs })
+}
@@ -0,0 +1,109 @@
+// (De)serialisation of states and models, behind the `serde` feature.
+//
+// We don't derive these on the types themselves, as a model has a lot of internal
+// index bookkeeping that should not end up in the output, and deserialised models
+// still need to go through `Model::new` to be checked.
+// The schema mirrors the python `Model(states, edges, initial_states)` constructor:
+//
+// ```json
+// {
+//   "states": [{"name": "s1", "variables": ["p", "q"]}, {"name": "s2", "variables": []}],
+//   "edges": {"s1": ["s1", "s2"], "s2": ["s1"]},
+//   "initial_states": ["s1"]
+// }
+// ```
+//
+// Variables and edge keys are sorted, so serialising the same model always gives the same output.
+// Transitions with an action are written as an `[action, target]` pair instead of just the target,
+// again like in the python constructor, so models without actions look exactly as above.
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{Model, State};
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StateData {
+    name: String,
+    variables: BTreeSet<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum EdgeData {
+    Plain(String),
+    Labelled(String, String),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ModelData {
+    states: Vec<State>,
+    edges: BTreeMap<String, Vec<EdgeData>>,
+    initial_states: Vec<String>,
+}
+
+impl Serialize for State {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        StateData {
+            name: self.name(),
+            variables: self.vars().iter().cloned().collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for State {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = StateData::deserialize(deserializer)?;
+        Ok(State::new(data.name, data.variables.into_iter().collect()))
+    }
+}
+
+impl Serialize for Model {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ModelData {
+            states: self.get_states().to_vec(),
+            edges: self
+                .get_states()
+                .iter()
+                .map(|s| {
+                    let transitions = self.get_transitions(&s.name()).unwrap_or_default();
+                    let edges = transitions
+                        .into_iter()
+                        .map(|(action, target)| match action {
+                            Some(action) => EdgeData::Labelled(action, target),
+                            None => EdgeData::Plain(target),
+                        })
+                        .collect();
+                    (s.name(), edges)
+                })
+                .collect(),
+            initial_states: self.get_initial().to_vec(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Model {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = ModelData::deserialize(deserializer)?;
+        let edges = data
+            .edges
+            .into_iter()
+            .map(|(source, edges)| {
+                let edges = edges
+                    .into_iter()
+                    .map(|edge| match edge {
+                        EdgeData::Plain(target) => (None, target),
+                        EdgeData::Labelled(action, target) => (Some(action), target),
+                    })
+                    .collect();
+                (source, edges)
+            })
+            .collect();
+        Model::new_labelled(data.states, edges, data.initial_states).map_err(D::Error::custom)
+    }
+}
@@ -0,0 +1,284 @@
+// A small plain-text format for Kripke models, so they don't have to be built in code.
+//
+// ```text
+// # Comments start with a '#' and run until the end of the line
+// state s1 : p q      # a state, with the propositions that are true in it
+// state s2            # a state in which nothing is true
+// s1 -> s1 s2         # transitions, which can be spread over multiple lines
+// s2 -> s1 : a        # transitions labelled with the action a
+// initial s1          # the initial states. If this is left out, the first state is initial
+// ```
+//
+// Names are anything without whitespace, '#', ':', '"', or '->' in them.
+// Other names can be written in double quotes, with '\"' and '\\' as escapes.
+// The order of the lines does not matter, transitions may mention states declared later.
+// States without transitions are sinks.
+use std::collections::{HashMap, HashSet};
+use thiserror::Error;
+
+use super::{Model, State};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Name(String),
+    Colon,
+    Arrow,
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::Name(name) => format!("name {name}"),
+            Token::Colon => "':'".to_string(),
+            Token::Arrow => "'->'".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Error)]
+pub enum ModelParseError {
+    #[error("Line {0}: Unterminated string")]
+    UnterminatedString(usize),
+    #[error("Line {0}: Unexpected {1}")]
+    UnexpectedToken(usize, String),
+    #[error("Line {0}: Expected state name after {1}")]
+    ExpectedName(usize, String),
+    #[error("Line {0}: State declared twice: {1}")]
+    DuplicateState(usize, String),
+    #[error("Line {0}: Transition from unknown state: {1}")]
+    UnknownSource(usize, String),
+    #[error("Line {0}: Transition to unknown state: {1}")]
+    UnknownTarget(usize, String),
+    #[error("Line {0}: Expected one action after ':'")]
+    ExpectedAction(usize),
+    #[error("Line {0}: Unknown initial state: {1}")]
+    UnknownInitial(usize, String),
+}
+
+fn is_name_char(c: char) -> bool {
+    !c.is_whitespace() && !matches!(c, '#' | ':' | '"')
+}
+
+fn tokenize(line: &str, nr: usize) -> Result<Vec<Token>, ModelParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = line.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            '#' => break,
+            ':' => {
+                chars.next();
+                tokens.push(Token::Colon);
+            }
+            '"' => {
+                chars.next();
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(escaped) => name.push(escaped),
+                            None => return Err(ModelParseError::UnterminatedString(nr)),
+                        },
+                        Some(other) => name.push(other),
+                        None => return Err(ModelParseError::UnterminatedString(nr)),
+                    }
+                }
+                tokens.push(Token::Name(name));
+            }
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            _ => {
+                let mut name = String::new();
+                let mut arrow = false;
+                while let Some(&c) = chars.peek() {
+                    if !is_name_char(c) {
+                        break;
+                    }
+                    chars.next();
+                    if c == '-' && chars.peek() == Some(&'>') {
+                        chars.next();
+                        arrow = true;
+                        break;
+                    }
+                    name.push(c);
+                }
+                if !name.is_empty() {
+                    tokens.push(Token::Name(name));
+                }
+                if arrow {
+                    tokens.push(Token::Arrow);
+                }
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+fn expect_names(
+    tokens: impl Iterator<Item = Token>,
+    nr: usize,
+) -> Result<Vec<String>, ModelParseError> {
+    tokens
+        .map(|token| match token {
+            Token::Name(name) => Ok(name),
+            other => Err(ModelParseError::UnexpectedToken(nr, other.describe())),
+        })
+        .collect()
+}
+
+/// Parse a model from the plain-text model format.
+///
+/// All errors mention the (1-based) line number at which they occurred.
+pub fn parse_model(input: &str) -> Result<Model, ModelParseError> {
+    let mut states: Vec<State> = Vec::new();
+    let mut declared: HashMap<String, usize> = HashMap::new();
+    // Transitions and initial states are only checked once all states are known,
+    // so we keep the line numbers around for the error messages.
+    let mut transitions: Vec<(usize, String, Option<String>, Vec<String>)> = Vec::new();
+    let mut initial: Option<Vec<(usize, String)>> = None;
+
+    for (i, line) in input.lines().enumerate() {
+        let nr = i + 1;
+        let tokens = tokenize(line, nr)?;
+        let mut tokens = tokens.into_iter();
+        match (tokens.next(), tokens.next()) {
+            (None, _) => continue,
+            (Some(Token::Name(from)), Some(Token::Arrow)) => {
+                let rest: Vec<Token> = tokens.collect();
+                let (targets, action) = match rest.iter().position(|t| *t == Token::Colon) {
+                    None => (rest, None),
+                    Some(colon) => match &rest[colon + 1..] {
+                        [Token::Name(action)] => (rest[..colon].to_vec(), Some(action.clone())),
+                        _ => return Err(ModelParseError::ExpectedAction(nr)),
+                    },
+                };
+                let targets = expect_names(targets.into_iter(), nr)?;
+                transitions.push((nr, from, action, targets));
+            }
+            (Some(Token::Name(keyword)), rest) if keyword == "state" => {
+                let name = match rest {
+                    Some(Token::Name(name)) => name,
+                    Some(other) => {
+                        return Err(ModelParseError::UnexpectedToken(nr, other.describe()))
+                    }
+                    None => return Err(ModelParseError::ExpectedName(nr, keyword)),
+                };
+                let vars: HashSet<String> = match tokens.next() {
+                    None => HashSet::new(),
+                    Some(Token::Colon) => expect_names(tokens, nr)?.into_iter().collect(),
+                    Some(other) => {
+                        return Err(ModelParseError::UnexpectedToken(nr, other.describe()))
+                    }
+                };
+                if declared.insert(name.clone(), nr).is_some() {
+                    return Err(ModelParseError::DuplicateState(nr, name));
+                }
+                states.push(State::new(name, vars));
+            }
+            (Some(Token::Name(keyword)), rest) if keyword == "initial" => {
+                let names = expect_names(rest.into_iter().chain(tokens), nr)?;
+                initial
+                    .get_or_insert_with(Vec::new)
+                    .extend(names.into_iter().map(|name| (nr, name)));
+            }
+            (Some(Token::Name(name)), _) => {
+                return Err(ModelParseError::UnexpectedToken(nr, format!("name {name}")))
+            }
+            (Some(other), _) => return Err(ModelParseError::UnexpectedToken(nr, other.describe())),
+        }
+    }
+
+    let mut edges: HashMap<String, Vec<(Option<String>, String)>> = declared
+        .keys()
+        .map(|name| (name.clone(), Vec::new()))
+        .collect();
+    for (nr, from, action, targets) in transitions {
+        if let Some(target) = targets.iter().find(|t| !declared.contains_key(*t)) {
+            return Err(ModelParseError::UnknownTarget(nr, target.clone()));
+        }
+        edges
+            .get_mut(&from)
+            .ok_or(ModelParseError::UnknownSource(nr, from.clone()))?
+            .extend(targets.into_iter().map(|target| (action.clone(), target)));
+    }
+
+    let initial = match initial {
+        Some(initial) => initial
+            .into_iter()
+            .map(|(nr, name)| match declared.contains_key(&name) {
+                true => Ok(name),
+                false => Err(ModelParseError::UnknownInitial(nr, name)),
+            })
+            .collect::<Result<Vec<String>, ModelParseError>>()?,
+        None => states.first().map(State::name).into_iter().collect(),
+    };
+
+    // Every state has an edge list, and all names were checked above, with the line
+    // they occurred on, so creating the model itself cannot fail anymore.
+    Ok(Model::new_labelled(states, edges, initial).expect("All states were checked while parsing"))
+}
+
+fn write_name(out: &mut String, name: &str) {
+    let bare = !name.is_empty() && name.chars().all(is_name_char) && !name.contains("->");
+    if bare {
+        out.push_str(name);
+    } else {
+        out.push('"');
+        for c in name.chars() {
+            if c == '"' || c == '\\' {
+                out.push('\\');
+            }
+            out.push(c);
+        }
+        out.push('"');
+    }
+}
+
+impl Model {
+    /// Print this model in the plain-text model format.
+    /// Parsing the result with `parse_model` gives back the same model.
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        for state in self.get_states() {
+            out.push_str("state ");
+            write_name(&mut out, &state.name());
+            let mut vars: Vec<&String> = state.vars().iter().collect();
+            vars.sort();
+            if !vars.is_empty() {
+                out.push_str(" :");
+            }
+            for var in vars {
+                out.push(' ');
+                write_name(&mut out, var);
+            }
+            out.push('\n');
+        }
+        for state in self.get_states() {
+            // One line per run of transitions with the same action, so that parsing
+            // gives back the successors of every state in the same order.
+            let transitions = self.get_transitions(&state.name()).unwrap_or_default();
+            for run in transitions.chunk_by(|(a, _), (b, _)| a == b) {
+                let action = &run[0].0;
+                write_name(&mut out, &state.name());
+                out.push_str(" ->");
+                for (_, target) in run {
+                    out.push(' ');
+                    write_name(&mut out, target);
+                }
+                if let Some(action) = action {
+                    out.push_str(" : ");
+                    write_name(&mut out, action);
+                }
+                out.push('\n');
+            }
+        }
+        out.push_str("initial");
+        for name in self.get_initial() {
+            out.push(' ');
+            write_name(&mut out, name);
+        }
+        out.push('\n');
+        out
+    }
+}
@@ -4,7 +4,14 @@ use std::convert::From;
 use pyo3::exceptions::{PyKeyError, PyValueError};
 use pyo3::prelude::*;
 
-use super::{Model, ModelCreationError, State};
+use super::{
+    parse_model, parse_program, Model, ModelCreationError, ModelParseError, ProductError,
+    ProgramError, Specification, State,
+};
+use crate::formulas::ctl_python::PyCTLFormula;
+use crate::formulas::ltl_python::PyLTLFormula;
+use crate::formulas::CTLFormula;
+use crate::modelcheckers::{bisimulation, simulation};
 
 impl From<ModelCreationError> for PyErr {
     fn from(value: ModelCreationError) -> Self {
@@ -12,6 +19,24 @@ impl From<ModelCreationError> for PyErr {
     }
 }
 
+impl From<ProductError> for PyErr {
+    fn from(value: ProductError) -> Self {
+        PyValueError::new_err(value.to_string())
+    }
+}
+
+impl From<ModelParseError> for PyErr {
+    fn from(value: ModelParseError) -> Self {
+        PyValueError::new_err(value.to_string())
+    }
+}
+
+impl From<ProgramError> for PyErr {
+    fn from(value: ProgramError) -> Self {
+        PyValueError::new_err(value.to_string())
+    }
+}
+
 /// The Python view into the State
 /// This class is frozen. Objects, once created, cannot be modified.
 ///
@@ -27,6 +52,9 @@ impl PyState {
     fn to_rust(&self) -> State {
         State::new(self.name.clone(), self.variables.clone())
     }
+    fn from_rust(state: &State) -> Self {
+        Self::new(state.name(), state.vars().clone())
+    }
 }
 
 #[pymethods]
@@ -40,6 +68,22 @@ impl PyState {
         self.variables.contains(var)
     }
 }
+/// A transition in the edge map passed from python, either just the name of the target state,
+/// or a tuple of an action and the target state.
+#[derive(Debug, Clone, FromPyObject)]
+enum PyEdge {
+    Plain(String),
+    Labelled(String, String),
+}
+impl PyEdge {
+    fn to_rust(&self) -> (Option<String>, String) {
+        match self {
+            PyEdge::Plain(target) => (None, target.clone()),
+            PyEdge::Labelled(action, target) => (Some(action.clone()), target.clone()),
+        }
+    }
+}
+
 /// The python view into the Model
 /// This class is frozen. Objects, once created, cannot be modified.
 /// This class does not expose any public fields. It can only be inspected through methods.
@@ -49,6 +93,9 @@ impl PyState {
 /// This constructor throws a value error when the arguments do not lead to a valid frame,
 /// e.g. if edges point to unknown states.
 ///
+/// Transitions can also be labelled with an action, by giving them as a tuple of
+/// the action and the target state, like {"s1": [("a", "s2"), "s1"]}.
+///
 /// As a third optional argument, you can pass a list of initial states (e.g. ["s1"]),
 /// if this is not passed, the first state in the list is marked as initial.
 ///
@@ -65,12 +112,12 @@ pub struct PyModel {
 impl PyModel {
     fn new_bare(
         states: Vec<PyState>,
-        edges: HashMap<String, Vec<String>>,
+        edges: HashMap<String, Vec<(Option<String>, String)>>,
         initial_states: Vec<String>,
     ) -> Result<Self, ModelCreationError> {
         let innerstates: Vec<State> = states.iter().map(PyState::to_rust).collect();
         let names = edges.keys().cloned().collect();
-        let model = Model::new(innerstates, edges, initial_states)?;
+        let model = Model::new_labelled(innerstates, edges, initial_states)?;
         Ok(Self {
             states,
             names,
@@ -82,9 +129,48 @@ impl PyModel {
             "{which} cannot be found in the model"
         )))
     }
+    fn get_idxs(&self, names: &HashSet<String>) -> PyResult<HashSet<usize>> {
+        names.iter().map(|n| self.get_idx(n)).collect()
+    }
     pub fn to_rust(&self) -> Model {
         self.model.clone()
     }
+    pub fn from_rust(model: Model) -> Self {
+        Self {
+            states: model.get_states().iter().map(PyState::from_rust).collect(),
+            names: model.all(),
+            model,
+        }
+    }
+}
+
+// The specs of a program are CTL or LTL formulas, which python gets as the formula itself.
+#[derive(IntoPyObject)]
+pub(crate) enum PySpecification {
+    Ctl(PyCTLFormula),
+    Ltl(PyLTLFormula),
+}
+
+impl PySpecification {
+    pub(crate) fn from_rust(spec: &Specification) -> Self {
+        match spec {
+            Specification::Ctl(_, formula) => Self::Ctl(PyCTLFormula::from_rust(formula)),
+            Specification::Ltl(_, formula) => Self::Ltl(PyLTLFormula::from_rust(formula)),
+        }
+    }
+}
+
+type EquivalenceResult = (Option<HashSet<(String, String)>>, Option<PyCTLFormula>);
+
+// In python, the relation and the distinguishing formula are returned as a pair,
+// with exactly one of them set, so they can be unpacked directly.
+fn equivalence_to_python(
+    result: Result<HashSet<(String, String)>, Box<CTLFormula>>,
+) -> EquivalenceResult {
+    match result {
+        Ok(relation) => (Some(relation), None),
+        Err(formula) => (None, Some(PyCTLFormula::from_rust(&formula))),
+    }
 }
 
 #[pymethods]
@@ -93,7 +179,7 @@ impl PyModel {
     #[pyo3(signature = (states, edges, initial_states=None, *, allow_sink=false))]
     fn new(
         states: Vec<PyState>,
-        edges: HashMap<String, Vec<String>>,
+        edges: HashMap<String, Vec<PyEdge>>,
         initial_states: Option<Vec<String>>,
         allow_sink: bool,
     ) -> PyResult<Self> {
@@ -102,15 +188,15 @@ impl PyModel {
         ))?;
         let initial_states = initial_states.unwrap_or(vec![first_state.name.clone()]);
 
+        let edges = edges
+            .into_iter()
+            .map(|(k, v)| (k, v.iter().map(PyEdge::to_rust).collect()))
+            .collect();
+        let ret = Self::new_bare(states, edges, initial_states)?;
         if !allow_sink {
-            if let Some((key, _v)) = edges.iter().find(|(_k, v)| v.is_empty()) {
-                return Err(PyValueError::new_err(format!(
-                    "{key} has no outgoing edges"
-                )));
-            }
+            ret.model.check_no_sinks()?;
         }
-
-        Self::new_bare(states, edges, initial_states).map_err(Into::into)
+        Ok(ret)
     }
     fn get_state(&self, which: &str) -> PyResult<PyState> {
         Ok(self
@@ -136,22 +222,46 @@ impl PyModel {
         }
         Ok(self.model.all_except(&names))
     }
-    fn pre_e(&self, names: HashSet<String>) -> PyResult<HashSet<String>> {
-        let indexes = names
-            .iter()
-            .map(|n| self.get_idx(n))
-            .collect::<PyResult<HashSet<usize>>>()?;
-        let res_indexes = self.model.pre_e_idx(&indexes);
+    #[pyo3(signature = (names, actions=None))]
+    fn pre_e(
+        &self,
+        names: HashSet<String>,
+        actions: Option<HashSet<String>>,
+    ) -> PyResult<HashSet<String>> {
+        let indexes = self.get_idxs(&names)?;
+        let res_indexes = match actions {
+            None => self.model.pre_e_idx(&indexes),
+            Some(actions) => {
+                let actions = self.model.get_action_idxs(&actions);
+                self.model.pre_e_actions_idx(&indexes, &actions)
+            }
+        };
         Ok(self.model.get_names(&res_indexes))
     }
-    fn pre_a(&self, names: HashSet<String>) -> PyResult<HashSet<String>> {
-        let indexes = names
-            .iter()
-            .map(|n| self.get_idx(n))
-            .collect::<PyResult<HashSet<usize>>>()?;
-        let res_indexes = self.model.pre_a_idx(&indexes);
+    #[pyo3(signature = (names, actions=None))]
+    fn pre_a(
+        &self,
+        names: HashSet<String>,
+        actions: Option<HashSet<String>>,
+    ) -> PyResult<HashSet<String>> {
+        let indexes = self.get_idxs(&names)?;
+        let res_indexes = match actions {
+            None => self.model.pre_a_idx(&indexes),
+            Some(actions) => {
+                let actions = self.model.get_action_idxs(&actions);
+                self.model.pre_a_actions_idx(&indexes, &actions)
+            }
+        };
         Ok(self.model.get_names(&res_indexes))
     }
+    fn get_transitions(&self, name: &str) -> PyResult<Vec<(Option<String>, String)>> {
+        self.model.get_transitions(name).ok_or(PyKeyError::new_err(
+            "Could not find specified state in model states",
+        ))
+    }
+    fn all_actions(&self) -> HashSet<String> {
+        self.model.all_actions()
+    }
     fn get_next(&self, name: &str) -> PyResult<HashSet<String>> {
         self.model
             .get_next(name)
@@ -163,4 +273,80 @@ impl PyModel {
     fn all_initial(&self) -> HashSet<String> {
         self.model.all_initial()
     }
+    #[staticmethod]
+    #[pyo3(signature = (text, *, allow_sink=false))]
+    fn parse(text: &str, allow_sink: bool) -> PyResult<Self> {
+        let model = parse_model(text)?;
+        if !allow_sink {
+            model.check_no_sinks()?;
+        }
+        Ok(Self::from_rust(model))
+    }
+    #[staticmethod]
+    #[pyo3(signature = (text, *, allow_sink=false))]
+    fn parse_program(text: &str, allow_sink: bool) -> PyResult<(Self, Vec<PySpecification>)> {
+        let program = parse_program(text)?;
+        if !allow_sink {
+            program.model.check_no_sinks()?;
+        }
+        let specs = program
+            .specs
+            .iter()
+            .map(PySpecification::from_rust)
+            .collect();
+        Ok((Self::from_rust(program.model), specs))
+    }
+    fn minimise_bisimulation(&self) -> (PyModel, HashMap<String, String>) {
+        let (quotient, mapping) = self.model.minimise_bisimulation();
+        (PyModel::from_rust(quotient), mapping)
+    }
+    #[staticmethod]
+    #[pyo3(signature = (components, *, synchronise=false))]
+    fn synchronous_product(
+        components: Vec<(String, PyModel)>,
+        synchronise: bool,
+    ) -> PyResult<Self> {
+        let components: Vec<(&str, &Model)> = components
+            .iter()
+            .map(|(name, model)| (name.as_str(), &model.model))
+            .collect();
+        Ok(Self::from_rust(Model::synchronous_product(
+            &components,
+            synchronise,
+        )?))
+    }
+    #[staticmethod]
+    #[pyo3(signature = (components, *, synchronise=false))]
+    fn interleaving_product(
+        components: Vec<(String, PyModel)>,
+        synchronise: bool,
+    ) -> PyResult<Self> {
+        let components: Vec<(&str, &Model)> = components
+            .iter()
+            .map(|(name, model)| (name.as_str(), &model.model))
+            .collect();
+        Ok(Self::from_rust(Model::interleaving_product(
+            &components,
+            synchronise,
+        )?))
+    }
+    fn bisimulation(&self, other: PyModel) -> EquivalenceResult {
+        equivalence_to_python(bisimulation(&self.model, &other.model))
+    }
+    fn simulated_by(&self, other: PyModel) -> EquivalenceResult {
+        equivalence_to_python(simulation(&self.model, &other.model))
+    }
+    fn to_text(&self) -> String {
+        self.model.to_text()
+    }
+    #[pyo3(signature = (highlight=None))]
+    fn to_dot(&self, highlight: Option<HashSet<String>>) -> PyResult<String> {
+        if let Some(names) = &highlight {
+            self.get_idxs(names)?;
+        }
+        Ok(self
+            .model
+            .to_dot(highlight.as_ref())
+            .expect("All highlighted states were checked"))
+    }
 }
@@ -49,7 +49,8 @@ from minictl import CTLChecker
 checker = CTLChecker(model)
 # TODO: Use the model checker to check 2c) in the state s in the model created in Ex. 1.
 # (To check if a formula is true in a state, calculate the set of states in which the
-# formula is true, and then check if the target state is in that computed set.)
+# formula is true, and then check if the target state is in that computed set.
+# If s is the initial state of your model, `checker.holds_initially(formula)` does this for you.)
 
 # Exercise 3)
 # Consider the state s from Ex. 2.
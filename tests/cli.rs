@@ -0,0 +1,170 @@
+// Runs the minictl binary, to check its output and the exit status contract:
+// 0 if all formulas hold, 1 if some formula fails, and 2 on errors.
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+const MODEL: &str = "\
+state s1 : p
+state s2 : p q
+s1 -> s1 s2
+s2 -> s1
+";
+
+const SINK_MODEL: &str = "\
+state s1 : p
+state s2 : q
+s1 -> s2
+";
+
+const PROGRAM: &str = "\
+VAR n : 0..2;
+INIT n=0;
+TRANS n=0 : n := 1;
+TRANS n=1 : n := {0, 2};
+TRANS n=2 : n := 2;
+CTLSPEC AG EF n=2;
+";
+
+/// Writes `contents` to a file in the temporary directory, unique to this test.
+fn write_file(name: &str, contents: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("minictl-cli-{}-{name}", std::process::id()));
+    fs::write(&path, contents).expect("Can write to the temporary directory");
+    path
+}
+
+fn minictl(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_minictl"))
+        .args(args)
+        .output()
+        .expect("Can run the minictl binary")
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+#[test]
+fn holds_exits_0() {
+    let model = write_file("holds.txt", MODEL);
+    let output = minictl(&[model.to_str().unwrap(), "AG p", "E[p U q]"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        stdout(&output),
+        "CTL AG p: holds\n  satisfied in: {s1, s2}\n\
+         CTL E[p U q]: holds\n  satisfied in: {s1, s2}\n"
+    );
+}
+
+#[test]
+fn fails_exits_1() {
+    let model = write_file("fails.txt", MODEL);
+    let output = minictl(&[model.to_str().unwrap(), "AG p", "q"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        stdout(&output),
+        "CTL AG p: holds\n  satisfied in: {s1, s2}\n\
+         CTL q: fails\n  satisfied in: {s2}\n  failing initial states: {s1}\n"
+    );
+}
+
+#[test]
+fn ltl_formulas() {
+    let model = write_file("ltl.txt", MODEL);
+    let output = minictl(&["--ltl", model.to_str().unwrap(), "G p"]);
+    assert_eq!(output.status.code(), Some(0));
+    let output = minictl(&["-l", model.to_str().unwrap(), "F q"]);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn spec_file() {
+    let model = write_file("spec-model.txt", MODEL);
+    let spec = write_file("spec.txt", "# comment\n\nCTL: AG p\nLTL: G F p\nEF q\n");
+    let output = minictl(&["--spec", spec.to_str().unwrap(), model.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(0));
+    let out = stdout(&output);
+    assert!(out.contains("CTL AG p: holds"));
+    assert!(out.contains("LTL G F p: holds"));
+    assert!(out.contains("CTL EF q: holds"));
+}
+
+#[test]
+fn program_specs() {
+    let program = write_file("program.smv", PROGRAM);
+    let output = minictl(&[program.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout(&output).starts_with("CTL AG EF n=2: holds\n"));
+    let output = minictl(&[program.to_str().unwrap(), "AG n=0"]);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn help_exits_0() {
+    let output = minictl(&["--help"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout(&output).starts_with("Usage: minictl"));
+}
+
+#[test]
+fn errors_exit_2() {
+    let model = write_file("errors.txt", MODEL);
+    let model = model.to_str().unwrap();
+    let missing = std::env::temp_dir().join("minictl-cli-does-not-exist.txt");
+    let cases: [&[&str]; 6] = [
+        &[],
+        &[model],
+        &[model, "AG (p"],
+        &["--bogus", model, "p"],
+        &[missing.to_str().unwrap(), "p"],
+        &["--spec", missing.to_str().unwrap(), model],
+    ];
+    for args in cases {
+        let output = minictl(args);
+        assert_eq!(output.status.code(), Some(2), "{args:?}");
+        assert!(stderr(&output).starts_with("minictl: "), "{args:?}");
+        assert!(stdout(&output).is_empty(), "{args:?}");
+    }
+}
+
+#[test]
+fn invalid_model_exits_2() {
+    let model = write_file("invalid.txt", "state s1\ns1 -> s2\n");
+    let output = minictl(&[model.to_str().unwrap(), "p"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("Invalid model"));
+}
+
+#[test]
+fn sinks_rejected_by_default() {
+    let model = write_file("sink.txt", SINK_MODEL);
+    let output = minictl(&[model.to_str().unwrap(), "p"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("State has empty edge list: s2"));
+    assert!(stderr(&output).contains("--allow-sink"));
+}
+
+#[test]
+fn allow_sink() {
+    let model = write_file("allow-sink.txt", SINK_MODEL);
+    let output = minictl(&["--allow-sink", model.to_str().unwrap(), "EX q", "AX AX q"]);
+    assert_eq!(output.status.code(), Some(0));
+    let output = minictl(&["--allow-sink", model.to_str().unwrap(), "EX EX q"]);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn sinks_in_programs() {
+    let program = write_file(
+        "sink.smv",
+        "VAR x : {a, b};\nINIT x=a;\nTRANS x=a : x := b;\n",
+    );
+    let output = minictl(&[program.to_str().unwrap(), "EX x=b"]);
+    assert_eq!(output.status.code(), Some(2));
+    let output = minictl(&["--allow-sink", program.to_str().unwrap(), "EX x=b"]);
+    assert_eq!(output.status.code(), Some(0));
+}
@@ -30,7 +30,7 @@ class TestCreationErrors:
 
     @pytest.mark.parametrize(
         "formula_type",
-        ["And", "Or", "ImpliesR", "ImpliesL", "BiImplies", "EU", "AU"],
+        ["And", "Or", "ImpliesR", "ImpliesL", "BiImplies", "EU", "AU", "ER", "AR"],
     )
     def test_two_args(self, formula_type):
         for i in range(3, 100):
@@ -95,6 +95,16 @@ class TestParse:
     def test_au(self):
         assert CF("AU", CF("p"), CF("q")) == CF.parse("A[pUq]")
 
+    def test_er(self):
+        assert CF("ER", CF("p"), CF("q")) == CF.parse("E[p R q]")
+
+    def test_ar(self):
+        assert CF("AR", CF("p"), CF("q")) == CF.parse("A[pRq]")
+
+    def test_release_needs_brackets(self):
+        with pytest.raises(ValueError, match="Expected U or R"):
+            CF.parse("E[p q]")
+
 
 class TestSymbols:
     def test_top(self):
@@ -145,6 +155,12 @@ class TestSymbols:
     def test_au(self):
         assert str(CF.parse("A[pUq]")) == "A[(p)U(q)]"
 
+    def test_er(self):
+        assert str(CF.parse("E[pRq]")) == "E[(p)R(q)]"
+
+    def test_ar(self):
+        assert str(CF.parse("A[pRq]")) == "A[(p)R(q)]"
+
     def test_complex_1(self):
         assert (
             str(CF.parse("E[pU(q and p)] -> AXz or EG(A[rUw])"))
@@ -0,0 +1,91 @@
+import string
+import pytest
+
+from minictl import CTLStarFormula as SF
+
+
+class TestCreationErrors:
+    @pytest.mark.parametrize("variable_name", string.ascii_uppercase)
+    def test_variable_lowercase(self, variable_name):
+        with pytest.raises(ValueError):
+            SF(variable_name)
+
+    @pytest.mark.parametrize("formula_type", ["p", "q", "TOP", "BOT"])
+    def test_no_args(self, formula_type):
+        for i in range(1, 100):
+            with pytest.raises(ValueError):
+                SF(formula_type, *[SF("q") for _ in range(i)])
+
+    @pytest.mark.parametrize("formula_type", ["Neg", "E", "A", "X", "F", "G"])
+    def test_one_args(self, formula_type):
+        for i in range(2, 100):
+            with pytest.raises(ValueError):
+                SF(formula_type, *[SF("q") for _ in range(i)])
+        with pytest.raises(ValueError):
+            SF(formula_type)
+        with pytest.raises(TypeError):
+            SF(formula_type, "Something that is not a CTLStarFormula")
+
+    @pytest.mark.parametrize(
+        "formula_type",
+        ["And", "Or", "ImpliesR", "ImpliesL", "BiImplies", "U", "W", "R"],
+    )
+    def test_two_args(self, formula_type):
+        for i in range(3, 100):
+            with pytest.raises(ValueError):
+                SF(formula_type, *[SF("q") for _ in range(i)])
+        for i in range(2):
+            with pytest.raises(ValueError):
+                SF(formula_type, *[SF("q") for _ in range(i)])
+        with pytest.raises(TypeError):
+            SF(formula_type, "Something that is", "not a CTLStarFormula")
+
+
+class TestParse:
+    def test_var(self):
+        assert SF("p") == SF.parse("p")
+
+    def test_e(self):
+        assert SF("E", SF("p")) == SF.parse("Ep")
+
+    def test_a(self):
+        assert SF("A", SF("p")) == SF.parse("Ap")
+
+    def test_u(self):
+        assert SF("U", SF("p"), SF("q")) == SF.parse("pUq")
+
+    def test_stacked(self):
+        assert SF("E", SF("G", SF("F", SF("p")))) == SF.parse("EGFp")
+        assert SF.parse("EGFp") == SF.parse("E G F p") == SF.parse("E(G(F(p)))")
+
+    def test_ctl_syntax(self):
+        assert SF.parse("AG EF p") == SF("A", SF("G", SF("E", SF("F", SF("p")))))
+        assert SF.parse("E[p U q]") == SF("E", SF("U", SF("p"), SF("q")))
+        assert SF.parse("A[p U q]") == SF.parse("A(p U q)")
+
+    def test_nested_path(self):
+        assert SF.parse("E(GF p and FG q)") == SF(
+            "E",
+            SF("And", SF("G", SF("F", SF("p"))), SF("F", SF("G", SF("q")))),
+        )
+
+    def test_keywords(self):
+        assert SF.parse("TOP") == SF("TOP")
+        assert SF.parse("BOT") == SF("BOT")
+        assert SF.parse("E G TOP") == SF("E", SF("G", SF("TOP")))
+
+    @pytest.mark.parametrize("formula", ["EXTRA p", "E[p U q)", "E(p U q]", "p and"])
+    def test_errors(self, formula):
+        with pytest.raises(ValueError):
+            SF.parse(formula)
+
+
+class TestSymbols:
+    def test_e(self):
+        assert str(SF.parse("Ep")) == "E(p)"
+
+    def test_a(self):
+        assert str(SF.parse("Ap")) == "A(p)"
+
+    def test_complex(self):
+        assert str(SF.parse("E(GF p and FG q)")) == "E((G(F(p)))∧(F(G(q))))"
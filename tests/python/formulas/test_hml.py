@@ -0,0 +1,81 @@
+import pytest
+
+from minictl import HMLFormula as HF
+
+
+class TestCreationErrors:
+    @pytest.mark.parametrize("name", ["Xy", "x-y", "X", "p q"])
+    def test_invalid_name(self, name):
+        with pytest.raises(ValueError):
+            HF(name)
+
+    @pytest.mark.parametrize("formula_type", ["Diamond", "Box"])
+    def test_modality(self, formula_type):
+        formula = HF(formula_type, HF("p"), action="a")
+        assert formula.arguments == [HF("p")]
+        assert formula.action == "a"
+        with pytest.raises(ValueError):
+            HF(formula_type, HF("p"))
+        with pytest.raises(ValueError):
+            HF(formula_type, HF("p"), HF("q"), action="a")
+        with pytest.raises(ValueError):
+            HF(formula_type, HF("p"), action="")
+        with pytest.raises(ValueError):
+            HF(formula_type, HF("p"), action="a b")
+        with pytest.raises(TypeError):
+            HF(formula_type, "Something that is not an HMLFormula", action="a")
+
+    @pytest.mark.parametrize("formula_type", ["TOP", "Neg", "And", "p"])
+    def test_action_only_on_modalities(self, formula_type):
+        with pytest.raises(ValueError):
+            HF(formula_type, action="a")
+        assert HF("TOP").action is None
+
+    def test_action_in_equality(self):
+        assert HF("Diamond", HF("p"), action="a") != HF("Diamond", HF("p"), action="b")
+        assert HF("Diamond", HF("p"), action="a") != HF("Box", HF("p"), action="a")
+        assert hash(HF("Box", HF("p"), action="a")) == hash(HF("Box", HF("p"), action="a"))
+
+
+class TestParse:
+    def test_var(self):
+        assert HF("p") == HF.parse("p")
+
+    def test_modalities(self):
+        assert HF("Diamond", HF("p"), action="a") == HF.parse("<a>p")
+        assert HF("Box", HF("p"), action="a") == HF.parse("[a]p")
+        assert HF.parse("<coin>[coffee]BOT") == HF(
+            "Diamond", HF("Box", HF("BOT"), action="coffee"), action="coin"
+        )
+
+    def test_action_names(self):
+        assert HF.parse("<Send_1>TOP").action == "Send_1"
+        assert HF.parse("[a.b!]TOP").action == "a.b!"
+
+    def test_modalities_bind_tightly(self):
+        assert HF.parse("<a>p and q") == HF(
+            "And", HF("Diamond", HF("p"), action="a"), HF("q")
+        )
+        assert HF.parse("!<a>p") == HF("Neg", HF("Diamond", HF("p"), action="a"))
+
+    def test_implications(self):
+        assert HF("ImpliesR", HF("p"), HF("q")) == HF.parse("p -> q")
+        assert HF("ImpliesL", HF("p"), HF("q")) == HF.parse("p <- q")
+        assert HF("BiImplies", HF("p"), HF("q")) == HF.parse("p <-> q")
+        assert HF.parse("<a>p <-> [b]q") == HF(
+            "BiImplies",
+            HF("Diamond", HF("p"), action="a"),
+            HF("Box", HF("q"), action="b"),
+        )
+
+    @pytest.mark.parametrize("formula", ["<>p", "[]p", "<a p", "[a p", "<a>", "X", "p and"])
+    def test_errors(self, formula):
+        with pytest.raises(ValueError):
+            HF.parse(formula)
+
+
+class TestSymbols:
+    def test_modalities(self):
+        assert str(HF.parse("<a>p")) == "⟨a⟩(p)"
+        assert str(HF.parse("[a]p")) == "[a](p)"
+        assert str(HF.parse("<a>TOP and [b]BOT")) == "(⟨a⟩(⊤))∧([b](⊥))"
@@ -0,0 +1,99 @@
+import string
+import pytest
+
+from minictl import CTLFormula, MuFormula as MF
+
+
+class TestCreationErrors:
+    @pytest.mark.parametrize("name", ["Xy", "x-y", "X.", "p q"])
+    def test_invalid_name(self, name):
+        with pytest.raises(ValueError):
+            MF(name)
+
+    @pytest.mark.parametrize("variable_name", string.ascii_uppercase)
+    def test_fixpoint_variable(self, variable_name):
+        assert MF(variable_name).arguments == []
+
+    @pytest.mark.parametrize("formula_type", ["Neg", "Diamond", "Box"])
+    def test_one_args(self, formula_type):
+        for i in range(2, 10):
+            with pytest.raises(ValueError):
+                MF(formula_type, *[MF("q") for _ in range(i)])
+        with pytest.raises(ValueError):
+            MF(formula_type)
+        with pytest.raises(TypeError):
+            MF(formula_type, "Something that is not a MuFormula")
+
+    @pytest.mark.parametrize("formula_type", ["Mu", "Nu"])
+    def test_binder(self, formula_type):
+        assert MF(formula_type, MF("X"), MF("X")).arguments == [MF("X"), MF("X")]
+        with pytest.raises(ValueError):
+            MF(formula_type, MF("x"), MF("X"))
+        with pytest.raises(ValueError):
+            MF(formula_type, MF("Neg", MF("X")), MF("X"))
+        with pytest.raises(ValueError):
+            MF(formula_type, MF("X"))
+
+
+class TestParse:
+    def test_var(self):
+        assert MF("p") == MF.parse("p")
+        assert MF("X") == MF.parse("X")
+
+    def test_modalities(self):
+        assert MF("Diamond", MF("p")) == MF.parse("<>p")
+        assert MF("Box", MF("p")) == MF.parse("[]p")
+        assert MF("Box", MF("Diamond", MF("p"))) == MF.parse("[]<>p")
+
+    def test_implications(self):
+        assert MF("ImpliesL", MF("p"), MF("q")) == MF.parse("p <- q")
+        assert MF("BiImplies", MF("p"), MF("q")) == MF.parse("p <-> q")
+
+    def test_fixpoints(self):
+        assert MF.parse("mu X. p or <>X") == MF(
+            "Mu", MF("X"), MF("Or", MF("p"), MF("Diamond", MF("X")))
+        )
+        assert MF.parse("nu Y1. p and []Y1") == MF(
+            "Nu", MF("Y1"), MF("And", MF("p"), MF("Box", MF("Y1")))
+        )
+
+    def test_fixpoint_extends_right(self):
+        assert MF.parse("p and mu X. q or <>X") == MF.parse("p and (mu X. (q or <>X))")
+        assert MF.parse("(mu X. q) or p") == MF("Or", MF("Mu", MF("X"), MF("q")), MF("p"))
+
+    @pytest.mark.parametrize("formula", ["mu x. p", "mu X p", "[p", "<p", "mu X."])
+    def test_errors(self, formula):
+        with pytest.raises(ValueError):
+            MF.parse(formula)
+
+
+class TestSymbols:
+    def test_modalities(self):
+        assert str(MF.parse("<>p")) == "⟨⟩(p)"
+        assert str(MF.parse("[]p")) == "[](p)"
+
+    def test_fixpoints(self):
+        assert str(MF.parse("mu X. p or <>X")) == "μX.((p)∨(⟨⟩(X)))"
+        assert str(MF.parse("nu X. p and []X")) == "νX.((p)∧([](X)))"
+
+
+class TestFromCTL:
+    def test_ex(self):
+        assert MF.from_ctl(CTLFormula.parse("EX p")) == MF.parse("<>p")
+        assert MF.from_ctl(CTLFormula.parse("AX p")) == MF.parse("[]p")
+
+    def test_ef(self):
+        assert MF.from_ctl(CTLFormula.parse("EF p")) == MF.parse("mu Z0. p or <>Z0")
+
+    def test_ag(self):
+        assert MF.from_ctl(CTLFormula.parse("AG p")) == MF.parse("nu Z0. p and []Z0")
+
+    def test_eu(self):
+        assert MF.from_ctl(CTLFormula.parse("E[p U q]")) == MF.parse(
+            "mu Z0. q or (p and <>Z0)"
+        )
+
+    def test_nested(self):
+        assert MF.from_ctl(CTLFormula.parse("AG EF p")) == MF.parse(
+            "nu Z0. (mu Z1. p or <>Z1) and []Z0"
+        )
@@ -0,0 +1,176 @@
+import pytest
+
+from minictl import (
+    CTLChecker,
+    CTLFormula,
+    LTLChecker,
+    LTLFormula,
+    Model,
+    MuChecker,
+    MuFormula,
+    State,
+    SymbolicCTLChecker,
+)
+
+
+def operators(formula):
+    yield formula.name
+    for argument in formula.arguments:
+        yield from operators(argument)
+
+
+def negated(formula):
+    # The formulas directly below a negation.
+    for argument in formula.arguments:
+        if formula.name == "Neg":
+            yield argument
+        yield from negated(argument)
+
+
+def make_model(allow_sink=False):
+    states = [
+        State("s1", {"p"}),
+        State("s2", {"p", "q"}),
+        State("s3", {"q"}),
+        State("s4", set()),
+    ]
+    edges = {"s1": ["s2", "s4"], "s2": ["s3", "s1"], "s3": ["s3"], "s4": ["s1"]}
+    if allow_sink:
+        edges["s3"] = []
+    return Model(states, edges, allow_sink=allow_sink)
+
+
+ctl_formulas = [
+    "AX p",
+    "!EX !p",
+    "AF q",
+    "AG (p -> EF q)",
+    "A[p U q]",
+    "!A[p U !q]",
+    "E[p R q]",
+    "A[p R q]",
+    "!E[!p R AX q]",
+    "(p <-> q) or BOT",
+    "p <- EG !q",
+    "!(AF p and !A[q R p])",
+]
+
+ltl_formulas = [
+    "F p",
+    "G (p -> F q)",
+    "p U q",
+    "!(p U !q)",
+    "p R q",
+    "p W q",
+    "!(p W q)",
+    "!X (p or G q)",
+    "(p <-> X q) and BOT",
+    "!(F G p -> q R (p W !q))",
+]
+
+
+class TestCTLEnf:
+    @pytest.mark.parametrize("text", ctl_formulas)
+    def test_operators(self, text):
+        enf = CTLFormula.parse(text).to_enf()
+        assert set(operators(enf)) <= {"TOP", "p", "q", "Neg", "And", "EX", "EU", "EG"}
+
+    @pytest.mark.parametrize("text", ctl_formulas)
+    def test_equivalent(self, text):
+        formula = CTLFormula.parse(text)
+        for model in [make_model(), make_model(allow_sink=True)]:
+            checker = CTLChecker(model)
+            assert checker.check(formula.to_enf()) == checker.check(formula)
+
+    def test_no_double_negation(self):
+        assert CTLFormula.parse("AX !p").to_enf() == CTLFormula.parse("!EX p")
+        assert CTLFormula.parse("!!p").to_enf() == CTLFormula.parse("p")
+
+    def test_rewrites(self):
+        assert CTLFormula.parse("EF p").to_enf() == CTLFormula.parse("E[TOP U p]")
+        assert CTLFormula.parse("AG p").to_enf() == CTLFormula.parse("!E[TOP U !p]")
+        assert CTLFormula.parse("p or q").to_enf() == CTLFormula.parse("!(!p and !q)")
+        assert CTLFormula.parse("A[p R q]").to_enf() == CTLFormula.parse("!E[!p U !q]")
+
+    def test_already_enf(self):
+        formula = CTLFormula.parse("E[p U EG !EX q] and !p")
+        assert formula.to_enf() == formula
+
+
+class TestCTLNnf:
+    @pytest.mark.parametrize("text", ctl_formulas)
+    def test_negations(self, text):
+        nnf = CTLFormula.parse(text).to_nnf()
+        assert all(inner.name in {"p", "q"} for inner in negated(nnf))
+        assert not {"ImpliesR", "ImpliesL", "BiImplies"} & set(operators(nnf))
+
+    @pytest.mark.parametrize("text", ctl_formulas)
+    def test_equivalent(self, text):
+        formula = CTLFormula.parse(text)
+        for model in [make_model(), make_model(allow_sink=True)]:
+            checker = CTLChecker(model)
+            assert checker.check(formula.to_nnf()) == checker.check(formula)
+
+    def test_duals(self):
+        assert CTLFormula.parse("!EX p").to_nnf() == CTLFormula.parse("AX !p")
+        assert CTLFormula.parse("!EF p").to_nnf() == CTLFormula.parse("AG !p")
+        assert CTLFormula.parse("!AF p").to_nnf() == CTLFormula.parse("EG !p")
+        assert CTLFormula.parse("!E[p U q]").to_nnf() == CTLFormula.parse("A[!p R !q]")
+        assert CTLFormula.parse("!A[p U q]").to_nnf() == CTLFormula.parse("E[!p R !q]")
+        assert CTLFormula.parse("!!A[p R q]").to_nnf() == CTLFormula.parse("A[p R q]")
+        assert CTLFormula.parse("!TOP").to_nnf() == CTLFormula.parse("BOT")
+
+
+class TestCTLRelease:
+    # The release operators are checked through different rewrites in every checker.
+    @pytest.mark.parametrize("text", ["E[p R q]", "A[p R q]", "E[!q R p]", "A[BOT R q]", "E[BOT R q]"])
+    def test_checkers_agree(self, text):
+        formula = CTLFormula.parse(text)
+        for model in [make_model(), make_model(allow_sink=True)]:
+            expected = CTLChecker(model).check(formula)
+            assert SymbolicCTLChecker(model).check(formula) == expected
+            assert MuChecker(model).check(MuFormula.from_ctl(formula)) == expected
+
+    def test_release_is_globally(self):
+        model = make_model()
+        checker = CTLChecker(model)
+        assert checker.check(CTLFormula.parse("A[BOT R q]")) == checker.check(CTLFormula.parse("AG q"))
+        assert checker.check(CTLFormula.parse("E[BOT R p]")) == checker.check(CTLFormula.parse("EG p"))
+
+
+class TestLTLUntilForm:
+    @pytest.mark.parametrize("text", ltl_formulas)
+    def test_operators(self, text):
+        until = LTLFormula.parse(text).to_until_form()
+        assert set(operators(until)) <= {"TOP", "p", "q", "Neg", "And", "X", "U"}
+
+    @pytest.mark.parametrize("text", ltl_formulas)
+    def test_equivalent(self, text):
+        formula = LTLFormula.parse(text)
+        checker = LTLChecker(make_model())
+        assert checker.check(formula.to_until_form()) == checker.check(formula)
+
+    def test_rewrites(self):
+        assert LTLFormula.parse("F p").to_until_form() == LTLFormula.parse("TOP U p")
+        assert LTLFormula.parse("G p").to_until_form() == LTLFormula.parse("!(TOP U !p)")
+        assert LTLFormula.parse("p W q").to_until_form() == LTLFormula.parse("!(!q U (!p and !q))")
+
+
+class TestLTLNnf:
+    @pytest.mark.parametrize("text", ltl_formulas)
+    def test_negations(self, text):
+        nnf = LTLFormula.parse(text).to_nnf()
+        assert all(inner.name in {"p", "q"} for inner in negated(nnf))
+        assert not {"ImpliesR", "ImpliesL", "BiImplies"} & set(operators(nnf))
+
+    @pytest.mark.parametrize("text", ltl_formulas)
+    def test_equivalent(self, text):
+        formula = LTLFormula.parse(text)
+        checker = LTLChecker(make_model())
+        assert checker.check(formula.to_nnf()) == checker.check(formula)
+
+    def test_duals(self):
+        assert LTLFormula.parse("!F p").to_nnf() == LTLFormula.parse("G !p")
+        assert LTLFormula.parse("!X p").to_nnf() == LTLFormula.parse("X !p")
+        assert LTLFormula.parse("!(p U q)").to_nnf() == LTLFormula.parse("!p R !q")
+        assert LTLFormula.parse("!(p W q)").to_nnf() == LTLFormula.parse("!q U (!p and !q)")
@@ -0,0 +1,80 @@
+import pytest
+
+from minictl import CTLFormula as CF
+from minictl import LTLFormula as LF
+
+
+class TestCTLPretty:
+    @pytest.mark.parametrize(
+        "text,expected",
+        [
+            ("((p) and (q))", "p and q"),
+            ("(p and q) and r", "p and q and r"),
+            ("p and (q and r)", "p and (q and r)"),
+            ("(p or q) and r", "(p or q) and r"),
+            ("p or q and r", "p or q and r"),
+            ("p -> q -> r", "p -> q -> r"),
+            ("p -> (q <-> r)", "p -> (q <-> r)"),
+            ("!(p and q)", "!(p and q)"),
+            ("!!EX p", "!!EX p"),
+            ("AG(p -> EF !q)", "AG (p -> EF !q)"),
+            ("AG AF p", "AG AF p"),
+            ("E[(p and q) U !r]", "E[(p and q) U !r]"),
+            ("A[EX p R A[p U q]]", "A[EX p R A[p U q]]"),
+            ("TOP <- BOT", "TOP <- BOT"),
+            ("x_1=a or y.b", "x_1=a or y.b"),
+        ],
+    )
+    def test_ascii(self, text, expected):
+        formula = CF.parse(text)
+        assert formula.pretty("ascii") == expected
+        assert CF.parse(formula.pretty("ascii")) == formula
+
+    def test_unicode(self):
+        formula = CF.parse("AG (p -> EF !q) <-> (TOP or BOT)")
+        assert formula.pretty() == "AG (p → EF ¬q) ↔ ⊤ ∨ ⊥"
+        assert formula.pretty("unicode") == formula.pretty()
+        assert CF.parse("E[p U q] and A[p R q]").pretty() == "E[p U q] ∧ A[p R q]"
+
+    def test_latex(self):
+        formula = CF.parse("AG (p -> EF !q) and E[x_1 U TOP]")
+        assert formula.pretty("latex") == (
+            "\\mathrm{AG}\\, (p \\rightarrow \\mathrm{EF}\\, \\neg q)"
+            " \\land \\mathrm{E}[\\mathit{x\\_1} \\mathbin{\\mathrm{U}} \\top]"
+        )
+
+    def test_unknown_style(self):
+        with pytest.raises(ValueError, match="Unknown print style: html"):
+            CF.parse("p").pretty("html")
+
+    def test_roundtrip_constructed(self):
+        formula = CF("ImpliesR", CF("ImpliesR", CF("p"), CF("q")), CF("Neg", CF("And", CF("p"), CF("TOP"))))
+        assert formula.pretty("ascii") == "p -> q -> !(p and TOP)"
+        assert CF.parse(formula.pretty("ascii")) == formula
+
+
+class TestLTLPretty:
+    @pytest.mark.parametrize(
+        "text,expected",
+        [
+            ("(p U q) U r", "p U q U r"),
+            ("p U (q U r)", "p U (q U r)"),
+            ("p U q and r", "p U q and r"),
+            ("p U (q and r)", "p U (q and r)"),
+            ("(p or q) W r", "(p or q) W r"),
+            ("G (p -> F q)", "G (p -> F q)"),
+            ("X X !p", "X X !p"),
+            ("!(p R q)", "!(p R q)"),
+            ("G F p -> (q <- r)", "G F p -> (q <- r)"),
+        ],
+    )
+    def test_ascii(self, text, expected):
+        formula = LF.parse(text)
+        assert formula.pretty("ascii") == expected
+        assert LF.parse(formula.pretty("ascii")) == formula
+
+    def test_unicode(self):
+        assert LF.parse("G (p -> F !q) or BOT").pretty() == "G (p → F ¬q) ∨ ⊥"
+
+    def test_latex(self):
+        assert LF.parse("p U X q").pretty("latex") == "p \\mathbin{\\mathrm{U}} \\mathrm{X}\\, q"
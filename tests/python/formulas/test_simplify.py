@@ -0,0 +1,121 @@
+import pytest
+
+from minictl import CTLChecker, CTLFormula, LTLChecker, LTLFormula, Model, State
+
+
+class TestCTLRules:
+    s1 = State("s1", {"p", "q"})
+    s2 = State("s2", {"p"})
+    s3 = State("s3", set())
+    model = Model([s1, s2, s3], {"s1": ["s2"], "s2": ["s1", "s3"], "s3": ["s3", "s2"]})
+    # The same states, but s3 is a sink.
+    sink_model = Model(
+        [s1, s2, s3], {"s1": ["s2"], "s2": ["s1", "s3"], "s3": []}, allow_sink=True
+    )
+
+    @pytest.mark.parametrize(
+        "text,expected,rules",
+        [
+            ("!!p", "p", ["¬¬φ ≡ φ"]),
+            ("p and TOP", "p", ["⊤/⊥ absorption"]),
+            ("BOT and EX p", "BOT", ["⊤/⊥ absorption"]),
+            ("p or TOP", "TOP", ["⊤/⊥ absorption"]),
+            ("!TOP or q", "q", ["⊤/⊥ absorption", "⊤/⊥ absorption"]),
+            ("TOP -> p", "p", ["⊤/⊥ absorption"]),
+            ("p -> BOT", "!p", ["⊤/⊥ absorption"]),
+            ("BOT <- p", "!p", ["⊤/⊥ absorption"]),
+            ("p <-> BOT", "!p", ["⊤/⊥ absorption"]),
+            ("EX p and EX p", "EX p", ["idempotence"]),
+            ("q or q", "q", ["idempotence"]),
+            ("AG AG p", "AG p", ["AG AG φ ≡ AG φ"]),
+            ("EF EF p", "EF p", ["EF EF φ ≡ EF φ"]),
+            ("AF AF p", "AF p", ["AF AF φ ≡ AF φ"]),
+            ("EG EG p", "EG p", ["EG EG φ ≡ EG φ"]),
+            ("AG p and AG q", "AG (p and q)", ["AG φ ∧ AG ψ ≡ AG (φ ∧ ψ)"]),
+            ("EF p or EF q", "EF (p or q)", ["EF φ ∨ EF ψ ≡ EF (φ ∨ ψ)"]),
+        ],
+    )
+    def test_rule(self, text, expected, rules):
+        simplified, applied = CTLFormula.parse(text).simplify()
+        assert simplified == CTLFormula.parse(expected)
+        assert applied == rules
+
+    def test_nothing_to_do(self):
+        formula = CTLFormula.parse("A[p U EX q] -> EG !p")
+        assert formula.simplify() == (formula, [])
+
+    def test_rules_in_order(self):
+        simplified, applied = CTLFormula.parse("AG AG AG (p and TOP)").simplify()
+        assert simplified == CTLFormula.parse("AG p")
+        assert applied == ["⊤/⊥ absorption", "AG AG φ ≡ AG φ", "AG AG φ ≡ AG φ"]
+
+    def test_rules_enable_each_other(self):
+        # Merging creates p and p, which is then simplified further.
+        simplified, applied = CTLFormula.parse("AG p and AG !!p").simplify()
+        assert simplified == CTLFormula.parse("AG p")
+        assert applied == ["¬¬φ ≡ φ", "idempotence"]
+        simplified, _ = CTLFormula.parse("AG AG p and AG (q or BOT)").simplify()
+        assert simplified == CTLFormula.parse("AG (p and q)")
+
+    def test_equivalent(self):
+        formulas = [
+            "AG AG (p -> BOT) and AG q",
+            "!!(EF EF q or EF !TOP)",
+            "(AF AF p <-> TOP) or (EG EG q and EG EG q)",
+            "A[!!p U q] <- (BOT -> p)",
+        ]
+        for model in [self.model, self.sink_model]:
+            for fairness in [None, [CTLFormula.parse("q")]]:
+                checker = CTLChecker(model, fairness=fairness)
+                for text in formulas:
+                    formula = CTLFormula.parse(text)
+                    simplified, applied = formula.simplify()
+                    assert applied
+                    assert checker.check(simplified) == checker.check(formula)
+
+
+class TestLTLRules:
+    s1 = State("s1", {"p"})
+    s2 = State("s2", {"q"})
+    s3 = State("s3", {"p", "q"})
+    model = Model([s1, s2, s3], {"s1": ["s1", "s2"], "s2": ["s3"], "s3": ["s1", "s2"]})
+
+    @pytest.mark.parametrize(
+        "text,expected,rules",
+        [
+            ("!!p", "p", ["¬¬φ ≡ φ"]),
+            ("X p and TOP", "X p", ["⊤/⊥ absorption"]),
+            ("p or BOT", "p", ["⊤/⊥ absorption"]),
+            ("BOT -> p", "TOP", ["⊤/⊥ absorption"]),
+            ("p and p", "p", ["idempotence"]),
+            ("q U q", "q", ["idempotence"]),
+            ("F F p", "F p", ["F F φ ≡ F φ"]),
+            ("G G p", "G p", ["G G φ ≡ G φ"]),
+            ("F G F p", "G F p", ["F G F φ ≡ G F φ"]),
+            ("G F G p", "F G p", ["G F G φ ≡ F G φ"]),
+            ("G p and G q", "G (p and q)", ["G φ ∧ G ψ ≡ G (φ ∧ ψ)"]),
+            ("F p or F q", "F (p or q)", ["F φ ∨ F ψ ≡ F (φ ∨ ψ)"]),
+        ],
+    )
+    def test_rule(self, text, expected, rules):
+        simplified, applied = LTLFormula.parse(text).simplify()
+        assert simplified == LTLFormula.parse(expected)
+        assert applied == rules
+
+    def test_nothing_to_do(self):
+        formula = LTLFormula.parse("p W X q -> G F p")
+        assert formula.simplify() == (formula, [])
+
+    def test_equivalent(self):
+        formulas = [
+            "G G (p -> BOT) and G q",
+            "!!(F F q or F !TOP)",
+            "(F G F p <-> TOP) or G F G q",
+            "(p U p) R (q and q)",
+        ]
+        checker = LTLChecker(self.model)
+        for text in formulas:
+            formula = LTLFormula.parse(text)
+            simplified, applied = formula.simplify()
+            assert applied
+            assert checker.check(simplified) == checker.check(formula)
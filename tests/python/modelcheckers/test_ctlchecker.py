@@ -94,14 +94,7 @@ class TestCheckerBasics:
     def test_au(self):
         checker = CTLChecker(self.model)
         assert checker.check(CTLFormula.parse("A[qUp]")) == {"s1", "s2", "s3", "s6"}
-        assert checker.check(CTLFormula.parse("A[pUq]")) == {
-            "s1",
-            "s2",
-            "s3",
-            "s4",
-            "s5",
-            "s6",
-        }
+        assert checker.check(CTLFormula.parse("A[pUq]")) == {"s2", "s3", "s5", "s6"}
 
     def test_eu(self):
         checker = CTLChecker(self.model)
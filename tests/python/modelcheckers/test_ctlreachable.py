@@ -0,0 +1,79 @@
+from minictl import CTLChecker, CTLFormula, Model, State
+
+
+def make_model():
+    # s1 and s2 loop between each other, s3 is initial but only goes to s1,
+    # and u1 and u2 can never be reached from either initial state.
+    return Model(
+        [
+            State("s1", {"p"}),
+            State("s2", {"q"}),
+            State("s3", set()),
+            State("u1", {"p", "q"}),
+            State("u2", {"q"}),
+        ],
+        {
+            "s1": ["s2"],
+            "s2": ["s1"],
+            "s3": ["s1"],
+            "u1": ["u2", "s1"],
+            "u2": ["u1"],
+        },
+        ["s1", "s3"],
+    )
+
+
+class TestHoldsInitially:
+    def test_verdicts(self):
+        checker = CTLChecker(make_model())
+        assert checker.holds_initially(CTLFormula.parse("p")) == {"s1": True, "s3": False}
+        assert checker.holds_initially(CTLFormula.parse("AF q")) == {"s1": True, "s3": True}
+
+    def test_matches_check(self):
+        checker = CTLChecker(make_model())
+        formula = CTLFormula.parse("EX p")
+        states = checker.check(formula)
+        verdicts = checker.holds_initially(formula)
+        assert verdicts == {s: s in states for s in checker.get_model().all_initial()}
+
+    def test_custom(self):
+        checker = CTLChecker(make_model())
+        # A deliberately wrong EX, to see that the verdicts come from the custom algorithm.
+        checker.set_custom("EX", lambda states, model: model.all())
+        assert checker.holds_initially(CTLFormula.parse("EX BOT")) == {"s1": True, "s3": True}
+
+
+class TestReachableOnly:
+    def test_pruned(self):
+        checker = CTLChecker(make_model(), reachable_only=True)
+        assert checker.nr_pruned() == 2
+        assert checker.get_model().all() == {"s1", "s2", "s3"}
+        assert checker.get_model().all_initial() == {"s1", "s3"}
+
+    def test_nothing_pruned(self):
+        model = Model([State("s1", set())], {"s1": ["s1"]})
+        checker = CTLChecker(model, reachable_only=True)
+        assert checker.nr_pruned() == 0
+        assert CTLChecker(make_model()).nr_pruned() == 0
+
+    def test_same_results(self):
+        full = CTLChecker(make_model())
+        restricted = CTLChecker(make_model(), reachable_only=True)
+        for text in ["p", "EX q", "AG EF p", "E[!q U p]", "EG !u", "AF (p and q)"]:
+            formula = CTLFormula.parse(text)
+            assert restricted.check(formula) == full.check(formula) & {"s1", "s2", "s3"}
+            assert restricted.holds_initially(formula) == full.holds_initially(formula)
+
+    def test_fairness(self):
+        fairness = [CTLFormula.parse("q")]
+        full = CTLChecker(make_model(), fairness=fairness)
+        restricted = CTLChecker(make_model(), fairness=fairness, reachable_only=True)
+        assert restricted.nr_pruned() == 2
+        for text in ["EG p", "AF p", "EG TOP", "AG AF q"]:
+            formula = CTLFormula.parse(text)
+            assert restricted.check(formula) == full.check(formula) & {"s1", "s2", "s3"}
+
+    def test_custom_gets_restricted_model(self):
+        checker = CTLChecker(make_model(), reachable_only=True)
+        checker.set_custom("EF", lambda states, model: model.all())
+        assert checker.check(CTLFormula.parse("EF BOT")) == {"s1", "s2", "s3"}
@@ -0,0 +1,179 @@
+import pytest
+
+from minictl import (
+    CTLChecker,
+    CTLFormula,
+    CTLStarChecker,
+    CTLStarFormula,
+    LTLChecker,
+    LTLFormula,
+    Model,
+    State,
+)
+
+
+class TestCTLStarChecker:
+    # s0 can stay in p forever, or move through s1 to s2, where it stays in p.
+    s0 = State("s0", {"p"})
+    s1 = State("s1", set())
+    s2 = State("s2", {"p"})
+    model = Model(
+        [s0, s1, s2],
+        {"s0": ["s0", "s1"], "s1": ["s2"], "s2": ["s2"]},
+        ["s0"],
+    )
+
+    def test_fg_is_not_afag(self):
+        # The classic example: every path ends up in p forever, but on the path that stays
+        # in s0 there is never a point after which all paths stay in p.
+        checker = CTLStarChecker(self.model)
+        assert checker.check(CTLStarFormula.parse("A FG p")) == {"s0", "s1", "s2"}
+        assert checker.check(CTLStarFormula.parse("AF AG p")) == {"s1", "s2"}
+        assert checker.holds(CTLStarFormula.parse("A FG p"))
+        assert not checker.holds(CTLStarFormula.parse("AF AG p"))
+
+    def test_path_formula_is_universal(self):
+        checker = CTLStarChecker(self.model)
+        assert checker.check(CTLStarFormula.parse("FG p")) == checker.check(
+            CTLStarFormula.parse("A FG p")
+        )
+        assert checker.check(CTLStarFormula.parse("p and X p")) == {"s2"}
+
+    def test_e_is_dual(self):
+        checker = CTLStarChecker(self.model)
+        assert checker.check(CTLStarFormula.parse("E GF !p")) == set()
+        assert checker.check(CTLStarFormula.parse("E(F !p)")) == {"s0", "s1"}
+        assert checker.check(CTLStarFormula.parse("!A(G p)")) == {"s0", "s1"}
+
+    def test_nested_quantifiers(self):
+        checker = CTLStarChecker(self.model)
+        # There is a path that stays in states from which some path leaves p.
+        assert checker.check(CTLStarFormula.parse("E G(p and EF !p)")) == {"s0"}
+        assert checker.check(CTLStarFormula.parse("A(X EX p)")) == {"s0", "s1", "s2"}
+
+
+class TestFairnessLikeProperties:
+    # s0 starts in s1 or s3. From s1 we can cycle through p and q states,
+    # and s3 is a dead end without either.
+    states = [
+        State("s0", set()),
+        State("s1", {"p", "q"}),
+        State("s2", {"q"}),
+        State("s3", set()),
+    ]
+    model = Model(
+        states,
+        {"s0": ["s1", "s3"], "s1": ["s2"], "s2": ["s1", "s2"], "s3": ["s3"]},
+        ["s0"],
+    )
+
+    def test_egf_and_fg(self):
+        checker = CTLStarChecker(self.model)
+        formula = CTLStarFormula.parse("E(GF p and FG q)")
+        assert checker.check(formula) == {"s0", "s1", "s2"}
+        assert checker.holds(formula)
+
+    def test_not_both_conditions(self):
+        checker = CTLStarChecker(self.model)
+        assert checker.check(CTLStarFormula.parse("E(GF p and FG !q)")) == set()
+        assert checker.check(CTLStarFormula.parse("E(FG q and FG !p)")) == {
+            "s0",
+            "s1",
+            "s2",
+        }
+        assert checker.check(CTLStarFormula.parse("A(GF p -> FG q)")) == {
+            "s0",
+            "s1",
+            "s2",
+            "s3",
+        }
+
+    def test_fresh_atoms_do_not_clash(self):
+        model = Model(
+            [State("s0", {"__ctlstar_0"}), State("s1", set())],
+            {"s0": ["s1"], "s1": ["s1"]},
+        )
+        checker = CTLStarChecker(model)
+        assert checker.check(CTLStarFormula.parse("E X EG !q")) == {"s0", "s1"}
+        assert checker.check(CTLStarFormula.parse("E F(EX p)")) == set()
+
+    def test_fresh_atoms_are_distinct(self):
+        model = Model(
+            [State("s0", {"__ctlstar_0"}), State("s1", {"p"})],
+            {"s0": ["s1"], "s1": ["s1"]},
+        )
+        checker = CTLStarChecker(model)
+        assert checker.check(CTLStarFormula.parse("A((E X p) U (E G q))")) == set()
+
+
+class TestAgreement:
+    states = [
+        State("s1", {"p"}),
+        State("s2", {"p", "q"}),
+        State("s3", {"p", "q"}),
+        State("s4", set()),
+        State("s5", {"q"}),
+        State("s6", {"q"}),
+    ]
+    model = Model(
+        states,
+        {
+            "s1": ["s1", "s2", "s3"],
+            "s2": ["s1", "s2", "s3"],
+            "s3": ["s4", "s5"],
+            "s4": ["s1", "s6"],
+            "s5": ["s4", "s6"],
+            "s6": ["s1", "s2"],
+        },
+    )
+
+    @pytest.mark.parametrize(
+        "formula",
+        [
+            "EX p",
+            "AX q",
+            "EF (p and !q)",
+            "AF q",
+            "EG p",
+            "AG (p or q)",
+            "E[p U !q]",
+            "A[p U q]",
+            "AG EF q",
+            "EG AX p -> AF !q",
+            "E[EX q U AG p]",
+        ],
+    )
+    def test_ctl(self, formula):
+        ctl = CTLChecker(self.model).check(CTLFormula.parse(formula))
+        ctlstar = CTLStarChecker(self.model).check(CTLStarFormula.parse(formula))
+        assert ctl == ctlstar
+
+    @pytest.mark.parametrize(
+        "formula",
+        ["G F p", "F G q", "p U q", "X p -> X X q", "G(p -> F !p)", "q R p", "p W q"],
+    )
+    def test_ltl(self, formula):
+        ltl = LTLChecker(self.model).check(LTLFormula.parse(formula))
+        ctlstar = CTLStarChecker(self.model).check(CTLStarFormula.parse(formula))
+        assert ltl == ctlstar
+
+
+class TestSinks:
+    # Like the LTL checker, path formulas are about infinite paths only.
+    model = Model(
+        [State("s0", {"p"}), State("s1", set())],
+        {"s0": ["s1"], "s1": []},
+        ["s0"],
+        allow_sink=True,
+    )
+
+    def test_sink_is_vacuous(self):
+        checker = CTLStarChecker(self.model)
+        assert checker.check(CTLStarFormula.parse("A G BOT")) == {"s0", "s1"}
+        assert checker.check(CTLStarFormula.parse("E X TOP")) == set()
+
+
+class TestChecker:
+    def test_get_model(self):
+        model = Model([State("s0", set())], {"s0": ["s0"]})
+        assert CTLStarChecker(model).get_model().all() == {"s0"}
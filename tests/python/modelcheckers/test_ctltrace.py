@@ -0,0 +1,118 @@
+from minictl import CTLChecker, CTLFormula, Model, State
+
+
+def chain():
+    # s1 -> s2 -> s3 -> s4, where only s4 satisfies p, and s4 loops.
+    return Model(
+        [
+            State("s1", {"q"}),
+            State("s2", {"q"}),
+            State("s3", set()),
+            State("s4", {"p"}),
+        ],
+        {"s1": ["s2"], "s2": ["s3"], "s3": ["s4"], "s4": ["s4"]},
+    )
+
+
+def iterations(trace, formula):
+    return [step.states for step in trace if step.kind == "iteration" and step.formula == formula]
+
+
+class TestIterations:
+    def test_eu(self):
+        checker = CTLChecker(chain(), trace=True)
+        checker.check(CTLFormula.parse("E[TOP U p]"))
+        trace = checker.take_trace()
+        assert iterations(trace, CTLFormula.parse("E[TOP U p]")) == [
+            {"s4"},
+            {"s3", "s4"},
+            {"s2", "s3", "s4"},
+            {"s1", "s2", "s3", "s4"},
+            {"s1", "s2", "s3", "s4"},
+        ]
+        numbers = [step.iteration for step in trace if step.kind == "iteration"]
+        assert numbers == [0, 1, 2, 3, 4]
+
+    def test_eu_stops_outside_lhs(self):
+        checker = CTLChecker(chain(), trace=True)
+        checker.check(CTLFormula.parse("E[q U p]"))
+        assert iterations(checker.take_trace(), CTLFormula.parse("E[q U p]")) == [{"s4"}, {"s4"}]
+
+    def test_af(self):
+        model = Model(
+            [State("s1", set()), State("s2", set()), State("s3", {"p"})],
+            {"s1": ["s2", "s3"], "s2": ["s3"], "s3": ["s3"]},
+        )
+        checker = CTLChecker(model, trace=True)
+        checker.check(CTLFormula.parse("AF p"))
+        assert iterations(checker.take_trace(), CTLFormula.parse("AF p")) == [
+            {"s3"},
+            {"s2", "s3"},
+            {"s1", "s2", "s3"},
+            {"s1", "s2", "s3"},
+        ]
+
+    def test_last_iteration_is_fixpoint(self):
+        checker = CTLChecker(chain(), trace=True)
+        formula = CTLFormula.parse("E[q U !q]")
+        result = checker.check(formula)
+        sets = iterations(checker.take_trace(), formula)
+        assert sets[-1] == sets[-2] == result
+
+
+class TestRewrites:
+    def test_ag(self):
+        checker = CTLChecker(chain(), trace=True)
+        checker.check(CTLFormula.parse("AG p"))
+        trace = checker.take_trace()
+        rewrites = [(step.formula, step.rewritten) for step in trace if step.kind == "rewrite"]
+        assert rewrites == [
+            (CTLFormula.parse("AG p"), CTLFormula.parse("!EF !p")),
+            (CTLFormula.parse("EF !p"), CTLFormula.parse("E[TOP U !p]")),
+        ]
+        assert all(step.iteration is None and step.states is None for step in trace if step.kind == "rewrite")
+
+    def test_rewrite_before_iterations(self):
+        checker = CTLChecker(chain(), trace=True)
+        checker.check(CTLFormula.parse("EF p"))
+        kinds = [step.kind for step in checker.take_trace()]
+        assert kinds[0] == "rewrite"
+        assert set(kinds[1:]) == {"iteration"}
+
+    def test_str(self):
+        checker = CTLChecker(chain(), trace=True)
+        checker.check(CTLFormula.parse("E[q U p]"))
+        assert str(checker.take_trace()[0]) == f"{CTLFormula.parse('E[q U p]')} [0]: {{s4}}"
+
+
+class TestTaking:
+    def test_disabled(self):
+        checker = CTLChecker(chain())
+        checker.check(CTLFormula.parse("AG EF p"))
+        assert checker.take_trace() == []
+
+    def test_take_clears(self):
+        checker = CTLChecker(chain(), trace=True)
+        checker.check(CTLFormula.parse("EF p"))
+        assert checker.take_trace() != []
+        assert checker.take_trace() == []
+
+    def test_cached(self):
+        checker = CTLChecker(chain(), trace=True)
+        checker.check(CTLFormula.parse("EF p"))
+        checker.take_trace()
+        checker.check(CTLFormula.parse("EF p"))
+        assert checker.take_trace() == []
+        # Only the new parts of a bigger formula are computed.
+        checker.check(CTLFormula.parse("AG EF p"))
+        trace = checker.take_trace()
+        assert iterations(trace, CTLFormula.parse("E[TOP U p]")) == []
+        assert iterations(trace, CTLFormula.parse("E[TOP U !EF p]")) != []
+
+    def test_same_results(self):
+        formulas = ["AG EF p", "A[q U p]", "AF AG p", "EG q", "AX EX p"]
+        plain = CTLChecker(chain())
+        traced = CTLChecker(chain(), trace=True)
+        for text in formulas:
+            formula = CTLFormula.parse(text)
+            assert traced.check(formula) == plain.check(formula)
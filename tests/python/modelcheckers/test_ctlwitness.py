@@ -0,0 +1,124 @@
+import pytest
+from minictl import CTLFormula, CTLChecker, State, Model
+
+
+class TestWitnesses:
+    s1 = State("s1", {"p"})
+    s2 = State("s2", {"p", "q"})
+    s3 = State("s3", {"p", "q"})
+    s4 = State("s4", set())
+    s5 = State("s5", {"q"})
+    s6 = State("s6", {"q"})
+    model = Model(
+        [s1, s2, s3, s4, s5, s6],
+        {
+            "s1": ["s1", "s2", "s3"],
+            "s2": ["s1", "s2", "s3"],
+            "s3": ["s4", "s5"],
+            "s4": ["s1", "s6"],
+            "s5": ["s4", "s6"],
+            "s6": ["s1", "s2"],
+        },
+    )
+
+    def test_ex(self):
+        checker = CTLChecker(self.model)
+        assert checker.witness("s1", CTLFormula.parse("EXq")) == (["s1", "s2"], [])
+
+    def test_ef(self):
+        checker = CTLChecker(self.model)
+        assert checker.witness("s4", CTLFormula.parse("EFq")) == (["s4", "s6"], [])
+        assert checker.witness("s2", CTLFormula.parse("EFq")) == (["s2"], [])
+
+    def test_eu(self):
+        checker = CTLChecker(self.model)
+        assert checker.witness("s5", CTLFormula.parse("E[qUp]")) == (
+            ["s5", "s6", "s1"],
+            [],
+        )
+
+    def test_eg(self):
+        checker = CTLChecker(self.model)
+        assert checker.witness("s1", CTLFormula.parse("EGp")) == ([], ["s1"])
+        prefix, cycle = checker.witness("s3", CTLFormula.parse("EGq"))
+        assert cycle
+        assert all(self.model.get_state(s).contains("q") for s in prefix + cycle)
+
+    def test_no_witness(self):
+        checker = CTLChecker(self.model)
+        assert checker.witness("s4", CTLFormula.parse("EGp")) is None
+        assert checker.witness("s1", CTLFormula.parse("p")) is None
+        assert checker.witness("s1", CTLFormula.parse("AXp")) is None
+
+    def test_unknown_state(self):
+        checker = CTLChecker(self.model)
+        with pytest.raises(KeyError):
+            checker.witness("s7", CTLFormula.parse("EFp"))
+
+
+class TestCounterexamples:
+    s1 = State("s1", {"p"})
+    s2 = State("s2", {"p", "q"})
+    s3 = State("s3", {"p", "q"})
+    s4 = State("s4", set())
+    s5 = State("s5", {"q"})
+    s6 = State("s6", {"q"})
+    model = Model(
+        [s1, s2, s3, s4, s5, s6],
+        {
+            "s1": ["s1", "s2", "s3"],
+            "s2": ["s1", "s2", "s3"],
+            "s3": ["s4", "s5"],
+            "s4": ["s1", "s6"],
+            "s5": ["s4", "s6"],
+            "s6": ["s1", "s2"],
+        },
+    )
+
+    def test_ax(self):
+        checker = CTLChecker(self.model)
+        assert checker.counterexample("s1", CTLFormula.parse("AXq")) == (
+            ["s1", "s1"],
+            [],
+        )
+
+    def test_ag(self):
+        checker = CTLChecker(self.model)
+        assert checker.counterexample("s1", CTLFormula.parse("AGp")) == (
+            ["s1", "s3", "s4"],
+            [],
+        )
+
+    def test_af(self):
+        checker = CTLChecker(self.model)
+        assert checker.counterexample("s1", CTLFormula.parse("AFq")) == ([], ["s1"])
+
+    def test_au(self):
+        checker = CTLChecker(self.model)
+        assert checker.counterexample("s4", CTLFormula.parse("A[qUp]")) == (["s4"], [])
+
+    def test_no_counterexample(self):
+        checker = CTLChecker(self.model)
+        assert checker.counterexample("s2", CTLFormula.parse("AXp")) is None
+        assert checker.counterexample("s1", CTLFormula.parse("EFq")) is None
+
+
+class TestSinkWitnesses:
+    s1 = State("s1", {"p"})
+    s2 = State("s2", {"p"})
+    s3 = State("s3", set())
+    model = Model(
+        [s1, s2, s3], {"s1": ["s2", "s3"], "s2": [], "s3": ["s3"]}, allow_sink=True
+    )
+
+    def test_eg(self):
+        checker = CTLChecker(self.model)
+        assert checker.check(CTLFormula.parse("EGp")) == {"s1", "s2"}
+        assert checker.witness("s1", CTLFormula.parse("EGp")) == (["s1", "s2"], [])
+
+    def test_af(self):
+        checker = CTLChecker(self.model)
+        assert checker.counterexample("s1", CTLFormula.parse("AF!p")) == (
+            ["s1", "s2"],
+            [],
+        )
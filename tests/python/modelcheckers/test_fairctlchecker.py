@@ -0,0 +1,49 @@
+import pytest
+from minictl import CTLFormula, CTLChecker, State, Model
+
+
+class TestFairCTLChecker:
+    # Without fairness, s1 can stay in its self-loop forever, never reaching p.
+    s1 = State("s1", set())
+    s2 = State("s2", {"p"})
+    s3 = State("s3", set())
+    model = Model(
+        [s1, s2, s3],
+        {"s1": ["s1", "s2"], "s2": ["s2", "s3"], "s3": ["s3"]},
+    )
+
+    def test_is_fair(self):
+        assert not CTLChecker(self.model).is_fair()
+        assert CTLChecker(self.model, fairness=[]).is_fair()
+        assert CTLChecker(self.model, fairness=[CTLFormula.parse("p")]).is_fair()
+
+    def test_unfair_loop(self):
+        assert CTLChecker(self.model).check(CTLFormula.parse("AFp")) == {"s2"}
+        checker = CTLChecker(self.model, fairness=[CTLFormula.parse("p")])
+        assert checker.check(CTLFormula.parse("AFp")) == {"s1", "s2", "s3"}
+        assert checker.check(CTLFormula.parse("EG!p")) == set()
+
+    def test_fair_states(self):
+        checker = CTLChecker(self.model, fairness=[CTLFormula.parse("p")])
+        assert checker.check(CTLFormula.parse("EG TOP")) == {"s1", "s2"}
+        assert checker.check(CTLFormula.parse("EX TOP")) == {"s1", "s2"}
+        assert checker.check(CTLFormula.parse("EF TOP")) == {"s1", "s2"}
+        assert checker.check(CTLFormula.parse("AGp")) == {"s2", "s3"}
+
+    def test_multiple_constraints(self):
+        checker = CTLChecker(
+            self.model, fairness=[CTLFormula.parse("p"), CTLFormula.parse("!p")]
+        )
+        assert checker.check(CTLFormula.parse("EG TOP")) == set()
+        assert checker.check(CTLFormula.parse("AG BOT")) == {"s1", "s2", "s3"}
+
+    def test_eg_witness(self):
+        checker = CTLChecker(self.model, fairness=[CTLFormula.parse("p")])
+        prefix, cycle = checker.witness("s1", CTLFormula.parse("EG TOP"))
+        assert (prefix + cycle)[0] == "s1"
+        assert "s2" in cycle
+
+    def test_set_custom(self):
+        checker = CTLChecker(self.model, fairness=[CTLFormula.parse("p")])
+        with pytest.raises(ValueError):
+            checker.set_custom("EX", lambda _, states: states)
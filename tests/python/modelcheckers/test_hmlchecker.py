@@ -0,0 +1,90 @@
+import pytest
+
+from minictl import HMLChecker, HMLFormula, Model, State
+
+
+class TestHMLChecker:
+    # Two vending machines with the same traces that are told apart by HML:
+    # "m" lets you choose after paying, while "n" chooses for you when you pay.
+    # The unlabelled transitions are never taken by a modality.
+    states = [
+        State("m0", {"ready"}),
+        State("m1", set()),
+        State("m2", {"drink"}),
+        State("m3", {"drink"}),
+        State("n0", {"ready"}),
+        State("n1", set()),
+        State("n2", set()),
+        State("n3", {"drink"}),
+        State("n4", {"drink"}),
+    ]
+    edges = {
+        "m0": [("coin", "m1")],
+        "m1": [("tea", "m2"), ("coffee", "m3")],
+        "m2": ["m2"],
+        "m3": ["m3"],
+        "n0": [("coin", "n1"), ("coin", "n2")],
+        "n1": [("tea", "n3")],
+        "n2": [("coffee", "n4")],
+        "n3": ["n3"],
+        "n4": ["n0"],
+    }
+    model = Model(states, edges, ["m0", "n0"])
+
+    def test_propositional(self):
+        checker = HMLChecker(self.model)
+        assert checker.check(HMLFormula.parse("ready or drink")) == {
+            "m0", "m2", "m3", "n0", "n3", "n4"
+        }
+        assert checker.check(HMLFormula.parse("TOP")) == self.model.all()
+        assert checker.check(HMLFormula.parse("BOT")) == set()
+
+    def test_diamond(self):
+        checker = HMLChecker(self.model)
+        assert checker.check(HMLFormula.parse("<coin>TOP")) == {"m0", "n0"}
+        assert checker.check(HMLFormula.parse("<tea>drink")) == {"m1", "n1"}
+        assert checker.check(HMLFormula.parse("<unknown>TOP")) == set()
+
+    def test_box(self):
+        checker = HMLChecker(self.model)
+        # Vacuously true in all states without coin transitions.
+        assert checker.check(HMLFormula.parse("[coin]BOT")) == self.model.all() - {"m0", "n0"}
+        assert checker.check(HMLFormula.parse("[coin]<tea>TOP")) == self.model.all() - {"n0"}
+        assert checker.check(HMLFormula.parse("[unknown]BOT")) == self.model.all()
+
+    def test_distinguishing(self):
+        checker = HMLChecker(self.model)
+        formula = HMLFormula.parse("<coin>(<tea>TOP and <coffee>TOP)")
+        assert checker.check(formula) == {"m0"}
+        assert not checker.holds(formula)
+        assert checker.holds(HMLFormula.parse("<coin><tea>TOP and <coin><coffee>TOP"))
+
+    def test_unlabelled_transitions(self):
+        # The transition from n4 back to n0 has no action, so no modality can take it.
+        checker = HMLChecker(self.model)
+        assert "n4" not in checker.check(HMLFormula.parse("<coin>TOP"))
+        assert "n4" in checker.check(HMLFormula.parse("[coin]BOT and [tea]BOT"))
+
+    def test_implications(self):
+        checker = HMLChecker(self.model)
+        assert checker.check(HMLFormula.parse("ready -> <coin>TOP")) == self.model.all()
+        assert checker.check(HMLFormula.parse("<tea>TOP <-> <coffee>TOP")) == (
+            self.model.all() - {"n1", "n2"}
+        )
+        assert checker.check(HMLFormula.parse("<tea>TOP <- <coffee>TOP")) == (
+            self.model.all() - {"n2"}
+        )
+
+    def test_unlabelled_model(self):
+        model = Model([State("s", {"p"})], {"s": ["s"]})
+        checker = HMLChecker(model)
+        assert checker.check(HMLFormula.parse("<a>TOP")) == set()
+        assert checker.check(HMLFormula.parse("[a]BOT and p")) == {"s"}
+
+    def test_invalid_formula(self):
+        checker = HMLChecker(self.model)
+        with pytest.raises(TypeError):
+            checker.check("<coin>TOP")
+
+    def test_get_model(self):
+        assert HMLChecker(self.model).get_model().all() == self.model.all()
@@ -0,0 +1,191 @@
+from minictl import LTLFormula, LTLChecker, CTLFormula, CTLChecker, State, Model
+
+
+class TestLTLCheckerBasics:
+    s1 = State("s1", {"p"})
+    s2 = State("s2", {"p", "q"})
+    s3 = State("s3", {"p", "q"})
+    s4 = State("s4", set())
+    s5 = State("s5", {"q"})
+    s6 = State("s6", {"q"})
+    model = Model(
+        [s1, s2, s3, s4, s5, s6],
+        {
+            "s1": ["s1", "s2", "s3"],
+            "s2": ["s1", "s2", "s3"],
+            "s3": ["s4", "s5"],
+            "s4": ["s1", "s6"],
+            "s5": ["s4", "s6"],
+            "s6": ["s1", "s2"],
+        },
+    )
+
+    def test_topbot(self):
+        checker = LTLChecker(self.model)
+        assert checker.check(LTLFormula.parse("TOP")) == {
+            "s1",
+            "s2",
+            "s3",
+            "s4",
+            "s5",
+            "s6",
+        }
+        assert checker.check(LTLFormula.parse("BOT")) == set()
+
+    def test_propositional(self):
+        checker = LTLChecker(self.model)
+        assert checker.check(LTLFormula.parse("p")) == {"s1", "s2", "s3"}
+        assert checker.check(LTLFormula.parse("!p")) == {"s4", "s5", "s6"}
+        assert checker.check(LTLFormula.parse("p and q")) == {"s2", "s3"}
+        assert checker.check(LTLFormula.parse("p -> q")) == {
+            "s2",
+            "s3",
+            "s4",
+            "s5",
+            "s6",
+        }
+
+    def test_x(self):
+        checker = LTLChecker(self.model)
+        assert checker.check(LTLFormula.parse("Xp")) == {"s1", "s2", "s6"}
+        assert checker.check(LTLFormula.parse("Xq")) == set()
+        assert checker.check(LTLFormula.parse("X X p")) == {"s4", "s6"}
+
+    def test_f(self):
+        checker = LTLChecker(self.model)
+        assert checker.check(LTLFormula.parse("Fp")) == {
+            "s1",
+            "s2",
+            "s3",
+            "s4",
+            "s5",
+            "s6",
+        }
+        assert checker.check(LTLFormula.parse("Fq")) == {"s2", "s3", "s5", "s6"}
+
+    def test_g(self):
+        checker = LTLChecker(self.model)
+        assert checker.check(LTLFormula.parse("Gp")) == set()
+        assert checker.check(LTLFormula.parse("G(p or q)")) == set()
+        assert checker.check(LTLFormula.parse("G F p")) == {
+            "s1",
+            "s2",
+            "s3",
+            "s4",
+            "s5",
+            "s6",
+        }
+        assert checker.check(LTLFormula.parse("F G p")) == set()
+
+    def test_u(self):
+        checker = LTLChecker(self.model)
+        assert checker.check(LTLFormula.parse("p U q")) == {"s2", "s3", "s5", "s6"}
+        assert checker.check(LTLFormula.parse("q U p")) == {"s1", "s2", "s3", "s6"}
+
+    def test_w(self):
+        checker = LTLChecker(self.model)
+        assert checker.check(LTLFormula.parse("p W q")) == {
+            "s1",
+            "s2",
+            "s3",
+            "s5",
+            "s6",
+        }
+
+    def test_r(self):
+        checker = LTLChecker(self.model)
+        assert checker.check(LTLFormula.parse("q R p")) == {"s1", "s2", "s3"}
+
+    def test_holds(self):
+        checker = LTLChecker(self.model)
+        assert checker.holds(LTLFormula.parse("q U p"))
+        assert checker.holds(LTLFormula.parse("G(q -> F p)"))
+        assert not checker.holds(LTLFormula.parse("p U q"))
+        assert not checker.holds(LTLFormula.parse("F G p"))
+
+
+class TestLTLCheckerMutualExclusion:
+    s0 = State("s0", {"n1", "n2"})
+    s1 = State("s1", {"t1", "n2"})
+    s2 = State("s2", {"c1", "n2"})
+    s3 = State("s3", {"t1", "t2"})
+    s4 = State("s4", {"c1", "t2"})
+    s5 = State("s5", {"n1", "t2"})
+    s6 = State("s6", {"n1", "c2"})
+    s7 = State("s7", {"t1", "c2"})
+    s8 = State("s8", {"t1", "t2"})
+
+    model = Model(
+        [s0, s1, s2, s3, s4, s5, s6, s7, s8],
+        {
+            "s0": ["s1", "s5"],
+            "s1": ["s3", "s2"],
+            "s2": ["s4", "s5"],
+            "s3": ["s4"],
+            "s4": ["s5"],
+            "s5": ["s6", "s8"],
+            "s6": ["s1", "s7"],
+            "s7": ["s1"],
+            "s8": ["s7"],
+        },
+    )
+
+    def test_safety(self):
+        checker = LTLChecker(self.model)
+        assert checker.holds(LTLFormula.parse("G!(c1 and c2)"))
+
+    def test_liveness(self):
+        checker = LTLChecker(self.model)
+        assert checker.holds(LTLFormula.parse("G(t1 -> F c1)"))
+        assert checker.holds(LTLFormula.parse("G(t2 -> F c2)"))
+        assert not checker.holds(LTLFormula.parse("G(n1 -> X t1)"))
+
+
+class TestLTLCheckerExpressivity:
+    # The classic example where LTL and CTL differ:
+    # FGp holds in s1, while AF AGp does not, as s1 can always still go to s2.
+    s1 = State("s1", {"p"})
+    s2 = State("s2", set())
+    s3 = State("s3", {"p"})
+    model = Model([s1, s2, s3], {"s1": ["s1", "s2"], "s2": ["s3"], "s3": ["s3"]})
+
+    def test_fg(self):
+        assert LTLChecker(self.model).holds(LTLFormula.parse("F G p"))
+        assert "s1" not in CTLChecker(self.model).check(CTLFormula.parse("AF AGp"))
+
+
+class TestLTLCheckerSinks:
+    s1 = State("s1", {"p"})
+    s2 = State("s2", set())
+    model = Model([s1, s2], {"s1": ["s2"], "s2": []}, allow_sink=True)
+
+    def test_vacuous(self):
+        checker = LTLChecker(self.model)
+        assert checker.check(LTLFormula.parse("BOT")) == {"s1", "s2"}
+        assert checker.holds(LTLFormula.parse("G p"))
+
+
+class TestLTLCheckerCounterexamples:
+    s1 = State("s1", {"p"})
+    s2 = State("s2", set())
+    s3 = State("s3", {"p"})
+    model = Model([s1, s2, s3], {"s1": ["s1", "s2"], "s2": ["s3"], "s3": ["s3"]})
+
+    def test_none(self):
+        checker = LTLChecker(self.model)
+        assert checker.counterexample(LTLFormula.parse("F G p")) is None
+        assert checker.counterexample(LTLFormula.parse("F !p -> F G p")) is None
+
+    def test_finite_violation(self):
+        checker = LTLChecker(self.model)
+        assert checker.counterexample(LTLFormula.parse("G p")) == (["s1", "s2"], ["s3"])
+        assert checker.counterexample(LTLFormula.parse("X p")) == (["s1", "s2"], ["s3"])
+
+    def test_loop_violation(self):
+        checker = LTLChecker(self.model)
+        prefix, cycle = checker.counterexample(LTLFormula.parse("G F !p"))
+        assert (prefix + cycle)[0] == "s1"
+        assert cycle
+        assert all(self.model.get_state(s).contains("p") for s in cycle)
+        for current, following in zip(prefix + cycle, (prefix + cycle)[1:] + cycle[:1]):
+            assert following in self.model.get_next(current)
@@ -37,6 +37,26 @@ def eu(lhs: set[str], rhs: set[str], model: Model) -> set[str]:
             states = next_states
 
 
+def er(lhs: set[str], rhs: set[str], model: Model) -> set[str]:
+    # The greatest set of states in rhs that are in lhs or have a successor in the set.
+    states = rhs
+    while True:
+        next_states = {s for s in states if s in lhs or model.get_next(s) & states}
+        if next_states == states:
+            return states
+        states = next_states
+
+
+def ar(lhs: set[str], rhs: set[str], model: Model) -> set[str]:
+    # The greatest set of states in rhs that are in lhs or have all successors in the set.
+    states = rhs
+    while True:
+        next_states = {s for s in states if s in lhs or model.get_next(s) <= states}
+        if next_states == states:
+            return states
+        states = next_states
+
+
 def empty(states: set[str], model: Model) -> set[str]:
     return set()
 
@@ -150,6 +170,29 @@ class TestModularChecker:
             "s6",
         }
 
+    @pytest.mark.parametrize("target,func", [("ER", er), ("AR", ar)])
+    def test_release_correct_debug(self, target, func):
+        checker = CTLChecker(self.model)
+        checker.set_custom(target, func)
+        for text in ["E[pRq]", "A[pRq]", "E[qRp]", "A[qRp]", "E[BOT R p]", "A[!p R q]"]:
+            formula = CTLFormula.parse(text)
+            assert checker.check(formula, debug=True) == CTLChecker(self.model).check(formula)
+
+    @pytest.mark.parametrize("target,formula", [("ER", "E[pRq]"), ("AR", "A[pRq]")])
+    def test_release_incorrect(self, target, formula):
+        checker = CTLChecker(self.model)
+        checker.set_custom(target, lambda lhs, rhs, model: set())
+        assert checker.check(CTLFormula.parse(formula)) == set()
+        checker = CTLChecker(self.model)
+        checker.set_custom(target, lambda lhs, rhs, model: set())
+        with pytest.raises(RuntimeError):
+            checker.check(CTLFormula.parse(formula), debug=True)
+
+    def test_unknown_target(self):
+        checker = CTLChecker(self.model)
+        with pytest.raises(ValueError, match="ER2 is not a valid modal operator"):
+            checker.set_custom("ER2", er)
+
     def test_is_modified(self):
         checker = CTLChecker(self.model)
         assert not checker.is_modified()
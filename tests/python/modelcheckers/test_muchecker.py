@@ -0,0 +1,138 @@
+import time
+
+import pytest
+
+from minictl import CTLChecker, CTLFormula, Model, MuChecker, MuFormula, State
+
+
+class TestMuChecker:
+    s1 = State("s1", {"p"})
+    s2 = State("s2", {"p", "q"})
+    s3 = State("s3", {"p", "q"})
+    s4 = State("s4", set())
+    s5 = State("s5", {"q"})
+    s6 = State("s6", {"q"})
+    model = Model(
+        [s1, s2, s3, s4, s5, s6],
+        {
+            "s1": ["s1", "s2", "s3"],
+            "s2": ["s1", "s2", "s3"],
+            "s3": ["s4", "s5"],
+            "s4": ["s1", "s6"],
+            "s5": ["s4", "s6"],
+            "s6": ["s1", "s2"],
+        },
+        ["s1", "s4"],
+    )
+
+    def test_propositional(self):
+        checker = MuChecker(self.model)
+        assert checker.check(MuFormula.parse("p and !q")) == {"s1"}
+        assert checker.check(MuFormula.parse("TOP")) == self.model.all()
+
+    def test_modalities(self):
+        checker = MuChecker(self.model)
+        assert checker.check(MuFormula.parse("<>(!p and !q)")) == {"s3", "s5"}
+        assert checker.check(MuFormula.parse("[]!p")) == {"s3", "s5"}
+
+    def test_fixpoints(self):
+        checker = MuChecker(self.model)
+        # Some path visits q-without-p infinitely often.
+        assert checker.check(MuFormula.parse("nu X. mu Y. (q and !p and <>X) or <>Y")) == (
+            self.model.all()
+        )
+        # All paths eventually reach s4.
+        assert checker.check(MuFormula.parse("mu X. (!p and !q) or []X")) == {"s4"}
+
+    def test_holds(self):
+        checker = MuChecker(self.model)
+        assert checker.holds(MuFormula.parse("nu X. (p or q or <>TOP) and []X"))
+        assert not checker.holds(MuFormula.parse("p"))
+
+    def test_get_model(self):
+        assert MuChecker(self.model).get_model().all() == self.model.all()
+
+
+class TestErrors:
+    model = Model([State("s0", {"p"})], {"s0": ["s0"]})
+
+    def test_free_variable(self):
+        with pytest.raises(ValueError, match="not bound"):
+            MuChecker(self.model).check(MuFormula.parse("mu X. Y"))
+
+    def test_not_positive(self):
+        with pytest.raises(ValueError, match="negations"):
+            MuChecker(self.model).check(MuFormula.parse("mu X. !X"))
+        with pytest.raises(ValueError, match="negations"):
+            MuChecker(self.model).check(MuFormula.parse("mu X. X -> p"))
+        with pytest.raises(ValueError, match="negations"):
+            MuChecker(self.model).check(MuFormula.parse("mu X. X <-> p"))
+
+    def test_negated_fixpoint(self):
+        checker = MuChecker(self.model)
+        assert checker.check(MuFormula.parse("!(mu X. !p and <>X)")) == {"s0"}
+        assert checker.check(MuFormula.parse("nu Y. !(mu X. !Y or <>X)")) == {"s0"}
+
+
+class TestFromCTL:
+    states = [
+        State("s0", {"p"}),
+        State("s1", {"q"}),
+        State("s2", {"p", "q"}),
+        State("s3", set()),
+        State("s4", {"p"}),
+    ]
+    # s3 is a sink, to check that the translation follows the CTL checker there.
+    model = Model(
+        states,
+        {
+            "s0": ["s1", "s2"],
+            "s1": ["s1", "s3"],
+            "s2": ["s0", "s4"],
+            "s3": [],
+            "s4": ["s4", "s3"],
+        },
+        allow_sink=True,
+    )
+
+    @pytest.mark.parametrize(
+        "formula",
+        [
+            "EX p",
+            "AX q",
+            "EF (p and q)",
+            "AF q",
+            "EG p",
+            "AG (p or q)",
+            "E[p U q]",
+            "A[p U q]",
+            "AG EF q",
+            "!EG !AF p",
+            "EG AX p -> AF !q",
+            "E[EX q U AG p] <-> A[p U !EF q]",
+        ],
+    )
+    def test_agrees_with_ctl(self, formula):
+        ctl = CTLFormula.parse(formula)
+        expected = CTLChecker(self.model).check(ctl)
+        assert MuChecker(self.model).check(MuFormula.from_ctl(ctl)) == expected
+
+
+chain_length = 40000
+
+
+class TestLargeModel:
+    # A long chain, where every fixpoint needs one step per state to converge.
+    # Evaluating the whole body again for every step takes minutes here.
+    model = Model(
+        [State(f"s{i}", {"p"} if i < chain_length - 1 else {"q"}) for i in range(chain_length)],
+        {f"s{i}": [f"s{min(i + 1, chain_length - 1)}"] for i in range(chain_length)},
+    )
+
+    @pytest.mark.parametrize("formula", ["E[p U q] and AF q", "AG EF q and !EG p"])
+    def test_linear_in_model(self, formula):
+        ctl = CTLFormula.parse(formula)
+        start = time.perf_counter()
+        result = MuChecker(self.model).check(MuFormula.from_ctl(ctl))
+        assert time.perf_counter() - start < 10
+        assert result == CTLChecker(self.model).check(ctl)
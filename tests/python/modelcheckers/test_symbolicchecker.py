@@ -0,0 +1,287 @@
+import time
+
+import pytest
+from minictl import CTLFormula, CTLChecker, LTLFormula, SymbolicCTLChecker, State, Model
+
+formulas = [
+    "TOP",
+    "BOT",
+    "p",
+    "!p",
+    "p and q",
+    "p or !q",
+    "p -> q",
+    "p <- q",
+    "p <-> q",
+    "EXp",
+    "AXp",
+    "EFq",
+    "AFq",
+    "EGp",
+    "AGp",
+    "E[pUq]",
+    "A[pUq]",
+    "A[qUp]",
+    "A[pU(EXq)]",
+    "AG(p -> AF q)",
+    "EG EF !p",
+    "AG !(c1 and c2)",
+    "AG(t1 -> AF c1)",
+    "EF(n1 and EX t1)",
+]
+
+
+class TestSymbolicLecture:
+    s1 = State("s1", {"p"})
+    s2 = State("s2", {"p", "q"})
+    s3 = State("s3", {"p", "q"})
+    s4 = State("s4", set())
+    s5 = State("s5", {"q"})
+    s6 = State("s6", {"q"})
+    model = Model(
+        [s1, s2, s3, s4, s5, s6],
+        {
+            "s1": ["s1", "s2", "s3"],
+            "s2": ["s1", "s2", "s3"],
+            "s3": ["s4", "s5"],
+            "s4": ["s1", "s6"],
+            "s5": ["s4", "s6"],
+            "s6": ["s1", "s2"],
+        },
+    )
+
+    def test_values(self):
+        checker = SymbolicCTLChecker(self.model)
+        assert checker.check(CTLFormula.parse("EXp")) == {"s1", "s2", "s4", "s6"}
+        assert checker.check(CTLFormula.parse("A[pUq]")) == {"s2", "s3", "s5", "s6"}
+        assert checker.check(CTLFormula.parse("EGp")) == {"s1", "s2"}
+
+    @pytest.mark.parametrize("formula", formulas)
+    def test_same_as_explicit(self, formula):
+        formula = CTLFormula.parse(formula)
+        explicit = CTLChecker(self.model).check(formula)
+        assert SymbolicCTLChecker(self.model).check(formula) == explicit
+
+
+class TestSymbolicMutualExclusion:
+    s0 = State("s0", {"n1", "n2"})
+    s1 = State("s1", {"t1", "n2"})
+    s2 = State("s2", {"c1", "n2"})
+    s3 = State("s3", {"t1", "t2"})
+    s4 = State("s4", {"c1", "t2"})
+    s5 = State("s5", {"n1", "t2"})
+    s6 = State("s6", {"n1", "c2"})
+    s7 = State("s7", {"t1", "c2"})
+    s8 = State("s8", {"t1", "t2"})
+    model = Model(
+        [s0, s1, s2, s3, s4, s5, s6, s7, s8],
+        {
+            "s0": ["s1", "s5"],
+            "s1": ["s3", "s2"],
+            "s2": ["s4", "s5"],
+            "s3": ["s4"],
+            "s4": ["s5"],
+            "s5": ["s6", "s8"],
+            "s6": ["s1", "s7"],
+            "s7": ["s1"],
+            "s8": ["s7"],
+        },
+    )
+
+    @pytest.mark.parametrize("formula", formulas)
+    def test_same_as_explicit(self, formula):
+        formula = CTLFormula.parse(formula)
+        explicit = CTLChecker(self.model).check(formula)
+        assert SymbolicCTLChecker(self.model).check(formula) == explicit
+
+
+class TestSymbolicSinks:
+    s1 = State("s1", {"p"})
+    s2 = State("s2", {"p"})
+    s3 = State("s3", {"q"})
+    model = Model(
+        [s1, s2, s3], {"s1": ["s2", "s3"], "s2": [], "s3": ["s3"]}, allow_sink=True
+    )
+
+    @pytest.mark.parametrize("formula", formulas)
+    def test_same_as_explicit(self, formula):
+        formula = CTLFormula.parse(formula)
+        explicit = CTLChecker(self.model).check(formula)
+        assert SymbolicCTLChecker(self.model).check(formula) == explicit
+
+    def test_get_model(self):
+        assert SymbolicCTLChecker(self.model).get_model().all() == {"s1", "s2", "s3"}
+
+
+programs = [
+    """
+    VAR
+      step : {acquire, access, release, done};
+      lock : {free, held};
+      tries : 0..3;
+    INIT step=acquire and lock=free and tries=0;
+    TRANS
+      step=acquire and lock=free : step := access, lock := held;
+      step=acquire and lock=held and !tries=3 : tries := tries + 1;
+      step=access : step := {access, release};
+      step=release : step := done, lock := free;
+      step=done : step := acquire;
+    """,
+    """
+    VAR n : 0..4; m : 0..4; b : boolean;
+    INIT n=0 and !m=0;
+    TRANS !n=4 : n := n + 1, b := {false, true};
+          n=4 : n := m, m := n;
+          b=true and !m=0 : m := m - 1;
+    """,
+    """
+    VAR x : {a, b, c}; y : {a, b, c};
+    TRANS x=a : x := y;
+          x=b and !y=a : y := {a, b};
+    """,
+]
+
+program_formulas = [
+    "TOP",
+    "step=access",
+    "EX lock=held",
+    "AX step=done",
+    "AG(step=access -> lock=held)",
+    "AF step=done",
+    "EG !step=done",
+    "A[lock=free U step=access]",
+    "E[tries=0 U tries=3]",
+    "AG EF n=4",
+    "EG m=2",
+    "AF(n=4 and b=true)",
+    "E[!m=0 U n=m]",
+    "EX x=c or AX y=b",
+    "AG(x=a -> EF y=a)",
+    "EF EG x=b",
+    "undeclared",
+    "x=unknown",
+]
+
+
+class TestSymbolicPrograms:
+    @pytest.mark.parametrize("program", programs)
+    @pytest.mark.parametrize("formula", program_formulas)
+    def test_same_as_explicit(self, program, formula):
+        formula = CTLFormula.parse(formula)
+        model, _ = Model.parse_program(program, allow_sink=True)
+        explicit = CTLChecker(model)
+        checker, _ = SymbolicCTLChecker.from_program(program, allow_sink=True)
+        assert checker.check(formula) == explicit.check(formula)
+        assert checker.holds(formula) == (model.all_initial() <= explicit.check(formula))
+
+    def test_specs(self):
+        program = """
+            VAR x : boolean;
+            TRANS TOP : x := {false, true};
+            CTLSPEC AG EF x=true;
+            LTLSPEC G F x=false;
+        """
+        checker, specs = SymbolicCTLChecker.from_program(program)
+        assert specs == Model.parse_program(program)[1]
+        assert specs == [
+            CTLFormula.parse("AG EF x=true"),
+            LTLFormula.parse("G F x=false"),
+        ]
+        assert checker.holds(specs[0])
+        assert checker.get_model() is None
+
+    def test_sink_raises(self):
+        program = "VAR x : {a, b};\nINIT x=a;\nTRANS x=a : x := b;\n"
+        with pytest.raises(ValueError, match="empty edge list: x=b"):
+            SymbolicCTLChecker.from_program(program)
+        checker, _ = SymbolicCTLChecker.from_program(program, allow_sink=True)
+        assert checker.check(CTLFormula.parse("AX BOT")) == {"x=b"}
+
+    def test_out_of_range(self):
+        program = """
+            VAR n : 0..2;
+            INIT n=0;
+            TRANS TOP : n := n + 1;
+        """
+        with pytest.raises(ValueError, match="Line 4: n := 3 is out of the range of n"):
+            SymbolicCTLChecker.from_program(program)
+
+    def test_unreachable_out_of_range(self):
+        # n never gets to 2, so the count never goes out of range.
+        checker, _ = SymbolicCTLChecker.from_program("""
+            VAR n : 0..2;
+            INIT n=0;
+            TRANS n=0 : n := 1;
+                  n=1 : n := 0;
+                  n=2 : n := n + 1;
+        """)
+        assert checker.check(CTLFormula.parse("TOP")) == {"n=0", "n=1"}
+
+    @pytest.mark.parametrize(
+        "program, message",
+        [
+            ("VAR x : boolean; INIT x=true and x=false;", "No assignment satisfies"),
+            ("VAR x : boolean; TRANS x=maybe : ;", "maybe is not a value of x"),
+            ("INIT TOP;", "No variables declared"),
+        ],
+    )
+    def test_error(self, program, message):
+        with pytest.raises(ValueError, match=message):
+            SymbolicCTLChecker.from_program(program)
+
+
+def mutex_program(processes):
+    """Processes that take turns in a critical section with a lock.
+    Any of them can be idle or trying at the same time, so there are over 2^processes states."""
+    return (
+        "VAR lock : {free, held}; "
+        + " ".join(f"p{i} : {{idle, trying, critical}};" for i in range(processes))
+        + " INIT lock=free and "
+        + " and ".join(f"p{i}=idle" for i in range(processes))
+        + "; TRANS "
+        + " ".join(
+            f"p{i}=idle : p{i} := trying; "
+            f"p{i}=trying and lock=free : p{i} := critical, lock := held; "
+            f"p{i}=critical : p{i} := idle, lock := free;"
+            for i in range(processes)
+        )
+    )
+
+
+def mutex_formulas(processes):
+    """Formulas about `mutex_program(processes)`, with whether they hold initially."""
+    last = processes - 1
+    return [
+        ("AG !(p0=critical and p1=critical)", True),
+        ("AG(p0=trying -> EF p0=critical)", True),
+        ("AG(p0=trying -> AF p0=critical)", False),
+        (f"EF(p0=critical and p{last}=trying)", True),
+        ("AG EF lock=free", True),
+        ("EG p0=idle", True),
+        ("AF lock=held", True),
+        (f"A[p{last}=idle U lock=held]", False),
+        ("AG(lock=free -> AX lock=held)", False),
+    ]
+
+
+class TestSymbolicLargeProgram:
+    def test_same_as_explicit(self):
+        model, _ = Model.parse_program(mutex_program(6))
+        explicit = CTLChecker(model)
+        checker, _ = SymbolicCTLChecker.from_program(mutex_program(6))
+        for formula, expected in mutex_formulas(6):
+            formula = CTLFormula.parse(formula)
+            assert checker.check(formula) == explicit.check(formula)
+            assert checker.holds(formula) == expected
+
+    def test_without_states(self):
+        # Far too many states for Model.parse_program.
+        start = time.perf_counter()
+        checker, _ = SymbolicCTLChecker.from_program(mutex_program(30))
+        for formula, expected in mutex_formulas(30):
+            assert checker.holds(CTLFormula.parse(formula)) == expected
+        initial = "lock=free and " + " and ".join(f"p{i}=idle" for i in range(30))
+        assert checker.check(CTLFormula.parse(initial)) == {
+            "lock=free," + ",".join(f"p{i}=idle" for i in range(30))
+        }
+        assert time.perf_counter() - start < 10
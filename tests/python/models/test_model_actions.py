@@ -0,0 +1,114 @@
+import pytest
+from minictl import CTLChecker, CTLFormula, Model, State
+
+
+class TestLabelledModel:
+    # A small vending machine: pay, then choose tea or coffee, or cancel and get refunded.
+    states = [
+        State("idle", set()),
+        State("paid", {"paid"}),
+        State("tea", {"drink"}),
+        State("coffee", {"drink"}),
+    ]
+    edges = {
+        "idle": [("pay", "paid")],
+        "paid": [("tea", "tea"), ("coffee", "coffee"), ("cancel", "idle")],
+        "tea": [("take", "idle")],
+        "coffee": [("take", "idle"), "coffee"],
+    }
+    model = Model(states, edges)
+
+    def test_transitions(self):
+        assert self.model.get_transitions("paid") == [
+            ("tea", "tea"),
+            ("coffee", "coffee"),
+            ("cancel", "idle"),
+        ]
+        assert self.model.get_transitions("coffee") == [("take", "idle"), (None, "coffee")]
+        assert self.model.get_next("paid") == {"tea", "coffee", "idle"}
+
+    def test_unknown_state(self):
+        with pytest.raises(KeyError):
+            self.model.get_transitions("s0")
+
+    def test_actions(self):
+        assert self.model.all_actions() == {"pay", "tea", "coffee", "cancel", "take"}
+        assert Model([State("s", set())], {"s": ["s"]}).all_actions() == set()
+
+    def test_pre_e(self):
+        assert self.model.pre_e({"tea", "coffee"}) == {"paid", "coffee"}
+        assert self.model.pre_e({"tea", "coffee"}, {"tea", "coffee"}) == {"paid"}
+        assert self.model.pre_e({"idle"}, {"take"}) == {"tea", "coffee"}
+        assert self.model.pre_e({"idle"}, set()) == set()
+        assert self.model.pre_e({"idle"}, {"unknown"}) == set()
+
+    def test_pre_a(self):
+        # States without any transition with one of the actions are included vacuously.
+        assert self.model.pre_a({"idle"}, {"take"}) == {"idle", "paid", "tea", "coffee"}
+        assert self.model.pre_a({"tea"}, {"tea", "coffee"}) == {"idle", "tea", "coffee"}
+        assert self.model.pre_a({"tea", "coffee"}, {"tea", "coffee"}) == self.model.all()
+        assert self.model.pre_a({"coffee"}) == set()
+
+    def test_ctl_ignores_actions(self):
+        unlabelled = Model(
+            self.states,
+            {
+                "idle": ["paid"],
+                "paid": ["tea", "coffee", "idle"],
+                "tea": ["idle"],
+                "coffee": ["idle", "coffee"],
+            },
+        )
+        for formula in ["AG EF drink", "EG !drink", "A[!drink U paid]", "EX EX drink"]:
+            f = CTLFormula.parse(formula)
+            assert CTLChecker(self.model).check(f) == CTLChecker(unlabelled).check(f)
+
+    def test_invalid_edge(self):
+        with pytest.raises(TypeError):
+            Model([State("s", set())], {"s": [("a", "s", "b")]})
+        with pytest.raises(ValueError):
+            Model([State("s", set())], {"s": [("a", "t")]})
+
+
+class TestLabelledText:
+    text = """
+    state idle
+    state paid : paid
+    state drink : drink
+    idle -> paid : pay
+    paid -> drink : "choose drink"
+    paid -> idle : cancel
+    drink -> idle : take
+    drink -> drink
+    """
+
+    def test_parse(self):
+        model = Model.parse(self.text)
+        assert model.get_transitions("paid") == [("choose drink", "drink"), ("cancel", "idle")]
+        assert model.get_transitions("drink") == [("take", "idle"), (None, "drink")]
+
+    def test_multiple_targets(self):
+        model = Model.parse("state s1\nstate s2\ns1 -> s1 s2 : a\ns2 -> s1")
+        assert model.get_transitions("s1") == [("a", "s1"), ("a", "s2")]
+
+    @pytest.mark.parametrize("line", ["idle -> paid :", "idle -> paid : a b", "idle -> : a :"])
+    def test_expected_action(self, line):
+        with pytest.raises(ValueError, match="Line 10"):
+            Model.parse(self.text + line)
+
+    def test_roundtrip(self):
+        model = Model.parse(self.text)
+        text = model.to_text()
+        assert "paid -> idle : cancel" in text
+        assert 'paid -> drink : "choose drink"' in text
+        again = Model.parse(text)
+        for state in model.all():
+            assert sorted(again.get_transitions(state), key=str) == sorted(
+                model.get_transitions(state), key=str
+            )
+        assert again.to_text() == text
+
+    def test_dot(self):
+        dot = Model.parse(self.text).to_dot()
+        assert 'n0 -> n1 [label="pay"];' in dot
+        assert "n2 -> n2;" in dot
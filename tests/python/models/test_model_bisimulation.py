@@ -0,0 +1,117 @@
+from minictl import CTLChecker, CTLFormula, HMLChecker, HMLFormula, Model, State
+
+
+class TestMinimiseBisimulation:
+    # Two copies of a p-q cycle, one of them unrolled twice, and a state that only loops.
+    states = [
+        State("a0", {"p"}),
+        State("a1", {"q"}),
+        State("b0", {"p"}),
+        State("b1", {"q"}),
+        State("b2", {"p"}),
+        State("b3", {"q"}),
+        State("c", {"p"}),
+    ]
+    edges = {
+        "a0": ["a1"],
+        "a1": ["a0"],
+        "b0": ["b1"],
+        "b1": ["b2"],
+        "b2": ["b3"],
+        "b3": ["b0"],
+        "c": ["c"],
+    }
+    model = Model(states, edges, ["b0", "c"])
+
+    def test_mapping(self):
+        _, mapping = self.model.minimise_bisimulation()
+        assert mapping == {
+            "a0": "a0",
+            "a1": "a1",
+            "b0": "a0",
+            "b1": "a1",
+            "b2": "a0",
+            "b3": "a1",
+            "c": "c",
+        }
+
+    def test_quotient(self):
+        quotient, _ = self.model.minimise_bisimulation()
+        assert quotient.all() == {"a0", "a1", "c"}
+        assert quotient.get_next("a0") == {"a1"}
+        assert quotient.get_next("a1") == {"a0"}
+        assert quotient.get_next("c") == {"c"}
+        assert quotient.get_state("a1").variables == {"q"}
+
+    def test_initial_states(self):
+        quotient, _ = self.model.minimise_bisimulation()
+        assert quotient.all_initial() == {"a0", "c"}
+
+    def test_minimal_is_unchanged(self):
+        quotient, _ = self.model.minimise_bisimulation()
+        again, mapping = quotient.minimise_bisimulation()
+        assert again.all() == quotient.all()
+        assert mapping == {name: name for name in quotient.all()}
+
+    def test_same_variables_not_bisimilar(self):
+        # All states satisfy nothing, but only some can reach a sink.
+        model = Model(
+            [State("s0", set()), State("s1", set()), State("s2", set())],
+            {"s0": ["s1"], "s1": [], "s2": ["s2"]},
+            allow_sink=True,
+        )
+        quotient, mapping = model.minimise_bisimulation()
+        assert len(quotient.all()) == 3
+        assert quotient.get_next("s1") == set()
+
+    def test_formulas_preserved(self):
+        quotient, mapping = self.model.minimise_bisimulation()
+        for text in ["EG p", "AF q", "EX EX p", "E[p U q]", "AG (p -> AX q)"]:
+            formula = CTLFormula.parse(text)
+            original = CTLChecker(self.model).check(formula)
+            minimised = CTLChecker(quotient).check(formula)
+            assert {mapping[s] for s in original} <= minimised
+            assert {s for s in self.model.all() if mapping[s] in minimised} == original
+
+
+class TestMinimiseLabelled:
+    # After a coin, m0 lets you choose, while n0 chooses for you.
+    # They have the same traces, but are not bisimilar.
+    states = [State(name, set()) for name in ["m0", "m1", "m2", "n0", "n1", "n2", "n3"]]
+    edges = {
+        "m0": [("coin", "m1")],
+        "m1": [("tea", "m2"), ("coffee", "m2")],
+        "m2": [("coin", "m1")],
+        "n0": [("coin", "n1"), ("coin", "n2")],
+        "n1": [("tea", "n3")],
+        "n2": [("coffee", "n3")],
+        "n3": [("coin", "n1"), ("coin", "n2")],
+    }
+    model = Model(states, edges)
+
+    def test_actions_matter(self):
+        _, mapping = self.model.minimise_bisimulation()
+        assert mapping["m0"] == mapping["m2"]
+        assert mapping["n0"] == mapping["n3"]
+        assert mapping["m0"] != mapping["n0"]
+        assert len(set(mapping.values())) == 5
+
+    def test_quotient_transitions(self):
+        quotient, _ = self.model.minimise_bisimulation()
+        assert quotient.get_transitions("m0") == [("coin", "m1")]
+        assert quotient.get_transitions("m1") == [("tea", "m0"), ("coffee", "m0")]
+        assert quotient.get_transitions("n0") == [("coin", "n1"), ("coin", "n2")]
+
+    def test_hml_preserved(self):
+        quotient, mapping = self.model.minimise_bisimulation()
+        formula = HMLFormula.parse("<coin>(<tea>TOP and <coffee>TOP)")
+        original = HMLChecker(self.model).check(formula)
+        assert original == {"m0", "m2"}
+        assert HMLChecker(quotient).check(formula) == {mapping[s] for s in original}
+
+    def test_unlabelled_differs_from_labelled(self):
+        # Without the actions or any variables, every state just steps forever.
+        edges = {k: [t for _, t in v] for k, v in self.edges.items()}
+        quotient, mapping = Model(self.states, edges).minimise_bisimulation()
+        assert set(mapping.values()) == {"m0"}
+        assert quotient.get_next("m0") == {"m0"}
@@ -0,0 +1,35 @@
+import pytest
+from minictl import CTLChecker, CTLFormula, Model, State
+
+
+class TestModelDot:
+    s1 = State("s1", {"q", "p"})
+    s2 = State('s"2', set())
+    model = Model([s1, s2], {"s1": ["s1", 's"2'], 's"2': ["s1"]}, ["s1"])
+
+    def test_structure(self):
+        dot = self.model.to_dot()
+        assert dot.startswith("digraph {")
+        assert dot.rstrip().endswith("}")
+        assert 'n0 [label="s1\\n{p, q}"];' in dot
+        assert 'n1 [label="s\\"2\\n{}"];' in dot
+        assert "n0 -> n0;" in dot
+        assert "n0 -> n1;" in dot
+        assert "n1 -> n0;" in dot
+        assert "n1 -> n1;" not in dot
+
+    def test_initial(self):
+        dot = self.model.to_dot()
+        assert "i0 -> n0;" in dot
+        assert "i1 -> n1;" not in dot
+
+    def test_highlight(self):
+        assert "fillcolor" not in self.model.to_dot()
+        result = CTLChecker(self.model).check(CTLFormula.parse("p"))
+        dot = self.model.to_dot(result)
+        assert 'n0 [label="s1\\n{p, q}", style=filled, fillcolor=lightblue];' in dot
+        assert 'n1 [label="s\\"2\\n{}"];' in dot
+
+    def test_highlight_unknown(self):
+        with pytest.raises(KeyError):
+            self.model.to_dot({"s3"})
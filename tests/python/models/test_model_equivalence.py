@@ -0,0 +1,137 @@
+from minictl import CTLChecker, CTLFormula, Model, State
+
+
+def holds(model, formula):
+    return model.all_initial() <= CTLChecker(model).check(formula)
+
+
+class TestBisimulation:
+    # A p-q cycle, and the same cycle unrolled twice.
+    small = Model(
+        [State("a0", {"p"}), State("a1", {"q"})],
+        {"a0": ["a1"], "a1": ["a0"]},
+    )
+    large = Model(
+        [State("b0", {"p"}), State("b1", {"q"}), State("b2", {"p"}), State("b3", {"q"})],
+        {"b0": ["b1"], "b1": ["b2"], "b2": ["b3"], "b3": ["b0"]},
+    )
+
+    def test_bisimilar(self):
+        relation, formula = self.small.bisimulation(self.large)
+        assert formula is None
+        assert relation == {
+            ("a0", "b0"),
+            ("a0", "b2"),
+            ("a1", "b1"),
+            ("a1", "b3"),
+        }
+
+    def test_symmetric(self):
+        relation, _ = self.large.bisimulation(self.small)
+        assert relation == {(t, s) for s, t in self.small.bisimulation(self.large)[0]}
+
+    def test_with_itself(self):
+        relation, formula = self.small.bisimulation(self.small)
+        assert formula is None
+        assert relation == {("a0", "a0"), ("a1", "a1")}
+
+    def test_variables_differ(self):
+        other = Model([State("c", {"p"})], {"c": ["c"]})
+        relation, formula = self.small.bisimulation(other)
+        assert relation is None
+        assert holds(self.small, formula) != holds(other, formula)
+
+    def test_initial_states(self):
+        # The same model, but starting in the q state.
+        other = Model(
+            [State("a0", {"p"}), State("a1", {"q"})],
+            {"a0": ["a1"], "a1": ["a0"]},
+            ["a1"],
+        )
+        relation, formula = self.small.bisimulation(other)
+        assert relation is None
+        assert formula == CTLFormula.parse("!p")
+
+    def test_branching(self):
+        # Both have the paths p q r and p q s, but only the first chooses after q.
+        states = [
+            State("s0", {"p"}),
+            State("s1", {"q"}),
+            State("s2", {"r"}),
+            State("s3", {"s"}),
+        ]
+        lhs = Model(
+            states,
+            {"s0": ["s1"], "s1": ["s2", "s3"], "s2": ["s2"], "s3": ["s3"]},
+        )
+        rhs = Model(
+            [
+                State("t0", {"p"}),
+                State("t1", {"q"}),
+                State("t2", {"q"}),
+                State("t3", {"r"}),
+                State("t4", {"s"}),
+            ],
+            {"t0": ["t1", "t2"], "t1": ["t3"], "t2": ["t4"], "t3": ["t3"], "t4": ["t4"]},
+        )
+        relation, formula = lhs.bisimulation(rhs)
+        assert relation is None
+        # After the first step on the left, you can always still reach r.
+        assert formula == CTLFormula.parse("!(AX EX r)")
+        assert holds(rhs, formula)
+        assert not holds(lhs, formula)
+
+    def test_sink(self):
+        # A sink and a state that loops forever are told apart by EX TOP.
+        sink = Model([State("s", set())], {"s": []}, allow_sink=True)
+        loop = Model([State("t", set())], {"t": ["t"]})
+        relation, formula = loop.bisimulation(sink)
+        assert relation is None
+        assert holds(loop, formula) != holds(sink, formula)
+
+
+class TestSimulation:
+    # The concrete model always alternates p and q, the abstract one can do anything.
+    concrete = Model(
+        [State("c0", {"p"}), State("c1", {"q"})],
+        {"c0": ["c1"], "c1": ["c0"]},
+    )
+    abstract = Model(
+        [State("a0", {"p"}), State("a1", {"q"})],
+        {"a0": ["a0", "a1"], "a1": ["a0", "a1"]},
+    )
+
+    def test_simulated(self):
+        relation, formula = self.concrete.simulated_by(self.abstract)
+        assert formula is None
+        assert relation == {("c0", "a0"), ("c1", "a1")}
+
+    def test_not_simulated(self):
+        relation, formula = self.abstract.simulated_by(self.concrete)
+        assert relation is None
+        assert holds(self.concrete, formula)
+        assert not holds(self.abstract, formula)
+
+    def test_formula_is_actl(self):
+        _, formula = self.abstract.simulated_by(self.concrete)
+
+        def universal(f):
+            if f.name in ("EX", "EF", "EG", "EU"):
+                return False
+            if f.name == "Neg":
+                return f.arguments[0].arguments == []
+            return all(universal(arg) for arg in f.arguments)
+
+        assert universal(formula)
+
+    def test_simulation_is_not_bisimulation(self):
+        relation, formula = self.concrete.bisimulation(self.abstract)
+        assert relation is None
+        assert holds(self.concrete, formula) != holds(self.abstract, formula)
+
+    def test_actl_preserved(self):
+        # ACTL formulas that hold in the abstract model hold in the concrete one.
+        for text in ["AG (p or q)", "AX (p or q)", "AG (p -> !q)"]:
+            formula = CTLFormula.parse(text)
+            assert holds(self.abstract, formula)
+            assert holds(self.concrete, formula)
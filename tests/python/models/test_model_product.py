@@ -0,0 +1,128 @@
+import pytest
+
+from minictl import CTLChecker, CTLFormula, Model, State
+
+
+def process(i):
+    # A process that acquires the lock to go critical, and releases it again after.
+    return Model(
+        [State("idle", set()), State("critical", {"crit"})],
+        {"idle": [(f"acquire{i}", "critical")], "critical": [(f"release{i}", "idle")]},
+    )
+
+
+lock = Model(
+    [State("free", set()), State("held", {"held"})],
+    {
+        "free": [("acquire1", "held"), ("acquire2", "held")],
+        "held": [("release1", "free"), ("release2", "free")],
+    },
+)
+
+
+class TestInterleaving:
+    def test_unsynchronised(self):
+        product = Model.interleaving_product([("p1", process(1)), ("p2", process(2))])
+        assert product.all() == {
+            "idle,idle",
+            "idle,critical",
+            "critical,idle",
+            "critical,critical",
+        }
+        assert product.all_initial() == {"idle,idle"}
+        assert product.get_state("critical,idle").variables == {"p1.crit"}
+        assert product.get_transitions("idle,idle") == [
+            ("acquire1", "critical,idle"),
+            ("acquire2", "idle,critical"),
+        ]
+
+    def test_mutual_exclusion(self):
+        components = [("p1", process(1)), ("p2", process(2)), ("lock", lock)]
+        mutex = CTLFormula.parse("AG !(p1.crit and p2.crit)")
+
+        unsynchronised = Model.interleaving_product(components)
+        assert "idle,idle,free" not in CTLChecker(unsynchronised).check(mutex)
+
+        product = Model.interleaving_product(components, synchronise=True)
+        assert product.all() == {"idle,idle,free", "critical,idle,held", "idle,critical,held"}
+        checker = CTLChecker(product)
+        assert checker.check(mutex) == product.all()
+        assert checker.check(CTLFormula.parse("AG (lock.held <-> (p1.crit or p2.crit))")) == (
+            product.all()
+        )
+
+    def test_unshared_actions_interleave(self):
+        # Only the lock knows about "reset", so it takes it alone.
+        reset_lock = Model(
+            [State("free", set()), State("held", {"held"})],
+            {
+                "free": [("acquire1", "held")],
+                "held": [("release1", "free"), ("reset", "free")],
+            },
+        )
+        product = Model.interleaving_product(
+            [("p1", process(1)), ("lock", reset_lock)], synchronise=True
+        )
+        assert set(product.get_transitions("critical,held")) == {
+            ("release1", "idle,free"),
+            ("reset", "critical,free"),
+        }
+
+    def test_unlabelled(self):
+        toggle = Model([State("off", set()), State("on", {"on"})], {"off": ["on"], "on": ["off"]})
+        product = Model.interleaving_product([("a", toggle), ("b", toggle)], synchronise=True)
+        assert len(product.all()) == 4
+        assert product.get_next("off,off") == {"on,off", "off,on"}
+
+
+class TestSynchronous:
+    two = Model(
+        [State("a0", set()), State("a1", {"x"})],
+        {"a0": [("tick", "a1")], "a1": [("tick", "a0")]},
+    )
+    three = Model(
+        [State("b0", {"y"}), State("b1", set()), State("b2", set())],
+        {"b0": [("tick", "b1")], "b1": [("tick", "b2")], "b2": [("tick", "b0"), ("tock", "b0")]},
+    )
+
+    def test_lockstep(self):
+        product = Model.synchronous_product([("m", self.two), ("n", self.three)])
+        assert len(product.all()) == 6
+        assert product.get_next("a0,b0") == {"a1,b1"}
+        assert product.get_state("a0,b0").variables == {"n.y"}
+        # Both must be back at the start at the same time, which takes six steps.
+        formula = CTLFormula.parse("EX EX EX EX EX EX (!m.x and n.y)")
+        assert "a0,b0" in CTLChecker(product).check(formula)
+
+    def test_actions(self):
+        product = Model.synchronous_product([("m", self.two), ("n", self.three)])
+        # The actions only agree on tick.
+        assert product.get_transitions("a1,b2") == [("tick", "a0,b0"), (None, "a0,b0")]
+        synchronised = Model.synchronous_product(
+            [("m", self.two), ("n", self.three)], synchronise=True
+        )
+        assert synchronised.get_transitions("a1,b2") == [("tick", "a0,b0")]
+
+    def test_sink(self):
+        # A component that cannot step stops the whole product.
+        stop = Model([State("s", set())], {"s": []}, allow_sink=True)
+        product = Model.synchronous_product([("m", self.two), ("s", stop)])
+        assert product.all() == {"a0,s"}
+        assert product.get_next("a0,s") == set()
+
+    def test_initial_states(self):
+        start = Model([State("c0", set()), State("c1", set())], {"c0": ["c0"], "c1": ["c1"]}, ["c0", "c1"])
+        product = Model.synchronous_product([("m", self.two), ("c", start)])
+        assert product.all_initial() == {"a0,c0", "a0,c1"}
+
+
+class TestErrors:
+    def test_no_components(self):
+        with pytest.raises(ValueError):
+            Model.synchronous_product([])
+        with pytest.raises(ValueError):
+            Model.interleaving_product([])
+
+    def test_duplicate_names(self):
+        with pytest.raises(ValueError):
+            Model.interleaving_product([("p", lock), ("p", lock)])
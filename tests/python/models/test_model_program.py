@@ -0,0 +1,162 @@
+import pytest
+
+from minictl import CTLChecker, CTLFormula, LTLChecker, LTLFormula, Model
+
+lock_protocol = """
+# The simple lock protocol from the teaching materials
+VAR
+  step : {acquire_lock, access_resource, release_lock, done};
+  lock : {free, held};
+INIT
+  step=acquire_lock and lock=free;
+TRANS
+  step=acquire_lock : step := access_resource, lock := held;
+  step=access_resource : step := release_lock;
+  step=release_lock : step := done, lock := free;
+  step=done : ;
+CTLSPEC AG(step=access_resource -> lock=held);
+LTLSPEC G(lock=held -> F lock=free);
+"""
+
+
+class TestCompile:
+    def test_states(self):
+        model, _ = Model.parse_program(lock_protocol)
+        assert model.all() == {
+            "step=acquire_lock,lock=free",
+            "step=access_resource,lock=held",
+            "step=release_lock,lock=held",
+            "step=done,lock=free",
+        }
+        assert model.all_initial() == {"step=acquire_lock,lock=free"}
+        assert model.get_state("step=release_lock,lock=held").variables == {
+            "step=release_lock",
+            "lock=held",
+        }
+
+    def test_transitions(self):
+        model, _ = Model.parse_program(lock_protocol)
+        assert model.get_next("step=acquire_lock,lock=free") == {"step=access_resource,lock=held"}
+        assert model.get_next("step=release_lock,lock=held") == {"step=done,lock=free"}
+        assert model.get_next("step=done,lock=free") == {"step=done,lock=free"}
+
+    def test_specs(self):
+        model, specs = Model.parse_program(lock_protocol)
+        assert specs == [
+            CTLFormula.parse("AG(step=access_resource -> lock=held)"),
+            LTLFormula.parse("G(lock=held -> F lock=free)"),
+        ]
+        assert CTLChecker(model).check(specs[0]) == model.all()
+        assert LTLChecker(model).check(specs[1]) == model.all()
+
+    def test_nondeterminism(self):
+        model, _ = Model.parse_program("""
+            VAR x : {a, b, c}; y : boolean;
+            INIT x=a and y=false;
+            TRANS x=a : x := {b, c}, y := {false, true};
+                  TOP : ;
+        """)
+        assert model.get_next("x=a,y=false") == {
+            "x=a,y=false",
+            "x=b,y=false",
+            "x=b,y=true",
+            "x=c,y=false",
+            "x=c,y=true",
+        }
+
+    def test_counting(self):
+        model, specs = Model.parse_program("""
+            VAR n : 0..3;
+            INIT n=0;
+            TRANS !n=3 : n := n + 1;
+                  n=3 : n := 0;
+            CTLSPEC AG AF n=3;
+        """)
+        assert model.get_next("n=2") == {"n=3"}
+        assert model.get_next("n=3") == {"n=0"}
+        assert CTLChecker(model).check(specs[0]) == model.all()
+
+    def test_copy(self):
+        model, _ = Model.parse_program("""
+            VAR x : {a, b}; y : {a, b};
+            INIT x=a and y=b;
+            TRANS TOP : x := y, y := x;
+        """)
+        assert model.get_next("x=a,y=b") == {"x=b,y=a"}
+        assert model.get_next("x=b,y=a") == {"x=a,y=b"}
+
+    def test_only_reachable(self):
+        model, _ = Model.parse_program("""
+            VAR n : 0..9;
+            INIT n=4;
+            TRANS TOP : ;
+        """)
+        assert model.all() == {"n=4"}
+
+    def test_without_init(self):
+        model, _ = Model.parse_program("VAR b : boolean; TRANS TOP : ;")
+        assert model.all_initial() == {"b=false", "b=true"}
+
+    def test_many_variables(self):
+        # 2^60 combinations of values, but INIT rules out all but two of them,
+        # which should be found without going through the rest.
+        names = [f"b{i}" for i in range(60)]
+        program = "VAR " + " ".join(f"{n} : boolean;" for n in names)
+        program += " INIT " + " and ".join(f"{n}=false" for n in names[1:]) + ";"
+        program += " TRANS b0=false : b0 := true; TRANS b0=true : b1 := true;"
+        model, _ = Model.parse_program(program)
+        assert len(model.all_initial()) == 2
+        assert len(model.all()) == 3
+
+    def test_init_order(self):
+        model, _ = Model.parse_program("""
+            VAR x : {a, b, c}; n : 0..2;
+            INIT !(x=b) and (n=1 or x=c);
+            TRANS TOP : ;
+        """)
+        assert model.to_text().splitlines()[-1] == "initial x=a,n=1 x=c,n=0 x=c,n=1 x=c,n=2"
+
+
+class TestSinks:
+    program = """
+        VAR x : {a, b};
+        INIT x=a;
+        TRANS x=a : x := b;
+    """
+
+    def test_sink_raises(self):
+        with pytest.raises(ValueError, match="empty edge list: x=b"):
+            Model.parse_program(self.program)
+
+    def test_allow_sink(self):
+        model, _ = Model.parse_program(self.program, allow_sink=True)
+        assert model.get_next("x=b") == set()
+
+
+class TestErrors:
+    @pytest.mark.parametrize(
+        "program,message",
+        [
+            ("x : boolean;", "Line 1: Expected VAR"),
+            ("VAR x : boolean;\nVAR x : {a};", "Line 2: Variable declared twice: x"),
+            ("VAR X : boolean;", "Invalid name: X"),
+            ("VAR x : {a, a};", "Invalid name: a"),
+            ("VAR x : 3..a;", "Expected a declaration"),
+            ("INIT TOP;", "No variables declared"),
+            ("VAR x : boolean;\nINIT x=maybe;", "Line 2: maybe is not a value of x"),
+            ("VAR x : boolean;\nINIT y=true;", "Line 2: Unknown variable: y"),
+            ("VAR x : boolean;\nINIT x;", "Expected a proposition like var=value"),
+            ("VAR x : boolean;\nINIT EF x=true;", "cannot use temporal operators"),
+            ("VAR x : boolean;\nINIT x=true and x=false;", "No assignment satisfies"),
+            ("VAR x : boolean;\nTRANS x=true x := false;", "Expected ':'"),
+            ("VAR x : boolean;\nTRANS TOP : x = false;", "Expected an update"),
+            ("VAR x : boolean;\nTRANS TOP : x := true, x := false;", "x is updated twice"),
+            ("VAR x : boolean;\nTRANS TOP : x := x + 1;", "x is not a number"),
+            ("VAR n : 0..2;\n\nTRANS\n  TOP : n := n + 1;", "Line 4: n := 3 is out of the range of n"),
+            ("VAR x : boolean;\n# a comment\nCTLSPEC AG x=yes;", "Line 3: yes is not a value of x"),
+            ("VAR x : boolean;\nLTLSPEC G (x=true;", "Line 2: Invalid formula"),
+        ],
+    )
+    def test_error(self, program, message):
+        with pytest.raises(ValueError, match=message):
+            Model.parse_program(program, allow_sink=True)
@@ -0,0 +1,120 @@
+import pytest
+from minictl import Model, State
+
+
+class TestModelParse:
+    text = """
+    # The model from the lecture notes
+    state s1 : p
+    state s2 : p q
+    state s3 : p q
+    state s4
+    state s5 : q
+    state s6 : q     # trailing comment
+    s1 -> s1 s2 s3
+    s2 -> s1 s2 s3
+    s3 -> s4 s5
+    s4 -> s1 s6
+    s5 -> s4 s6
+    s6 -> s1
+    s6 -> s2
+    """
+
+    def test_parse(self):
+        model = Model.parse(self.text)
+        assert model.all() == {"s1", "s2", "s3", "s4", "s5", "s6"}
+        assert model.all_containing("p") == {"s1", "s2", "s3"}
+        assert model.all_containing("q") == {"s2", "s3", "s5", "s6"}
+        assert model.get_next("s6") == {"s1", "s2"}
+        assert model.get_state("s4").variables == set()
+
+    def test_default_initial(self):
+        assert Model.parse(self.text).all_initial() == {"s1"}
+
+    def test_initial(self):
+        model = Model.parse(self.text + "initial s2 s3\ninitial s4")
+        assert model.all_initial() == {"s2", "s3", "s4"}
+        model = Model.parse(self.text + "initial")
+        assert model.all_initial() == set()
+
+    def test_order(self):
+        model = Model.parse("a -> b\nstate a\nstate b\nb -> a\ninitial b")
+        assert model.get_next("a") == {"b"}
+        assert model.all_initial() == {"b"}
+
+    def test_quoted(self):
+        model = Model.parse('state "s 1" : "x:y" "a\\"b"\n"s 1" -> "s 1"')
+        assert model.all() == {"s 1"}
+        assert model.get_state("s 1").variables == {"x:y", 'a"b'}
+
+    def test_sinks(self):
+        with pytest.raises(ValueError):
+            Model.parse("state s1\nstate s2\ns1 -> s2")
+        model = Model.parse("state s1\nstate s2\ns1 -> s2", allow_sink=True)
+        assert model.get_next("s2") == set()
+
+
+class TestModelParseErrors:
+    @pytest.mark.parametrize(
+        "text,message",
+        [
+            ("state s1\nstate s1", "Line 2: State declared twice: s1"),
+            ("state s1\ns1 -> s2", "Line 2: Transition to unknown state: s2"),
+            ("state s1\n\ns2 -> s1", "Line 3: Transition from unknown state: s2"),
+            ("state s1\ns1 -> s1\ninitial s3", "Line 3: Unknown initial state: s3"),
+            ('state s1\nstate "s2', "Line 2: Unterminated string"),
+            ("state s1\nstate", "Line 2: Expected state name after state"),
+            ("state s1 p", "Line 1: Unexpected name p"),
+            ("state s1 : p -> q", "Line 1: Unexpected '->'"),
+            ("state s1\ns1 s1", "Line 2: Unexpected name s1"),
+        ],
+    )
+    def test_errors(self, text, message):
+        with pytest.raises(ValueError) as excinfo:
+            Model.parse(text, allow_sink=True)
+        assert message in str(excinfo.value)
+
+
+class TestModelPrint:
+    s1 = State("s1", {"p", "q"})
+    s2 = State("s 2", {"r#1"})
+    s3 = State("state", set())
+    s4 = State("->", {""})
+
+    def assert_same(self, lhs: Model, rhs: Model):
+        assert lhs.all() == rhs.all()
+        assert lhs.all_initial() == rhs.all_initial()
+        for name in lhs.all():
+            assert lhs.get_state(name).variables == rhs.get_state(name).variables
+            assert lhs.get_next(name) == rhs.get_next(name)
+            assert lhs.get_transitions(name) == rhs.get_transitions(name)
+
+    def test_print(self):
+        model = Model([self.s1], {"s1": ["s1"]})
+        assert model.to_text() == "state s1 : p q\ns1 -> s1\ninitial s1\n"
+
+    def test_roundtrip(self):
+        model = Model(
+            [self.s1, self.s2, self.s3, self.s4],
+            {"s1": ["s 2", "state"], "s 2": ["->"], "state": ["s1"], "->": []},
+            ["s 2", "state"],
+            allow_sink=True,
+        )
+        self.assert_same(model, Model.parse(model.to_text(), allow_sink=True))
+
+    def test_transition_order(self):
+        model = Model(
+            [self.s1, self.s3],
+            {"s1": [("a", "state"), "s1", ("a", "s1"), ("b", "s1"), "state"], "state": ["s1"]},
+        )
+        assert model.to_text() == (
+            "state s1 : p q\nstate state\n"
+            "s1 -> state : a\ns1 -> s1\ns1 -> s1 : a\ns1 -> s1 : b\ns1 -> state\n"
+            "state -> s1\ninitial s1\n"
+        )
+        self.assert_same(model, Model.parse(model.to_text()))
+
+    def test_roundtrip_parsed(self):
+        model = Model.parse(TestModelParse.text)
+        self.assert_same(model, Model.parse(model.to_text()))
+        assert Model.parse(model.to_text()).to_text() == model.to_text()
@@ -0,0 +1,131 @@
+// The JSON format of models and formulas, which is only there with the `serde` feature.
+#![cfg(feature = "serde")]
+use std::collections::{HashMap, HashSet};
+
+use minictl::formulas::{parse_ctl, parse_ltl, CTLFormula, LTLFormula};
+use minictl::models::{Model, State};
+
+fn state(name: &str, vars: &[&str]) -> State {
+    State::new(
+        name.to_string(),
+        vars.iter().map(|v| v.to_string()).collect(),
+    )
+}
+
+fn small_model() -> Model {
+    let states = vec![state("s1", &["q", "p"]), state("s2", &[])];
+    let edges = HashMap::from([
+        ("s1".to_string(), vec!["s1".to_string(), "s2".to_string()]),
+        ("s2".to_string(), vec!["s1".to_string()]),
+    ]);
+    Model::new(states, edges, vec!["s1".to_string()]).unwrap()
+}
+
+fn labelled_model() -> Model {
+    let states = vec![
+        state("idle", &[]),
+        state("busy", &["working"]),
+        state("done", &["ready"]),
+    ];
+    let edges = HashMap::from([
+        (
+            "idle".to_string(),
+            vec![(Some("start".to_string()), "busy".to_string())],
+        ),
+        (
+            "busy".to_string(),
+            vec![
+                (None, "busy".to_string()),
+                (Some("finish".to_string()), "done".to_string()),
+                (Some("abort".to_string()), "idle".to_string()),
+            ],
+        ),
+        ("done".to_string(), vec![(None, "idle".to_string())]),
+    ]);
+    Model::new_labelled(states, edges, vec!["idle".to_string(), "done".to_string()]).unwrap()
+}
+
+fn assert_same(lhs: &Model, rhs: &Model) {
+    let names = |m: &Model| m.get_states().iter().map(State::name).collect::<Vec<_>>();
+    assert_eq!(names(lhs), names(rhs));
+    assert_eq!(lhs.all_initial(), rhs.all_initial());
+    for state in lhs.get_states() {
+        let other = rhs.get_state(&state.name()).unwrap();
+        assert_eq!(state.vars(), other.vars());
+        assert_eq!(
+            lhs.get_transitions(&state.name()),
+            rhs.get_transitions(&state.name())
+        );
+    }
+}
+
+#[test]
+fn model_roundtrip() {
+    for model in [small_model(), labelled_model()] {
+        let json = serde_json::to_string(&model).unwrap();
+        let back: Model = serde_json::from_str(&json).unwrap();
+        assert_same(&model, &back);
+        assert_eq!(serde_json::to_string(&back).unwrap(), json);
+    }
+}
+
+#[test]
+fn model_json() {
+    assert_eq!(
+        serde_json::to_string(&small_model()).unwrap(),
+        concat!(
+            r#"{"states":[{"name":"s1","variables":["p","q"]},{"name":"s2","variables":[]}],"#,
+            r#""edges":{"s1":["s1","s2"],"s2":["s1"]},"#,
+            r#""initial_states":["s1"]}"#,
+        )
+    );
+}
+
+#[test]
+fn labelled_model_json() {
+    assert_eq!(
+        serde_json::to_string(&labelled_model()).unwrap(),
+        concat!(
+            r#"{"states":[{"name":"idle","variables":[]},{"name":"busy","variables":["working"]},"#,
+            r#"{"name":"done","variables":["ready"]}],"#,
+            r#""edges":{"busy":["busy",["finish","done"],["abort","idle"]],"#,
+            r#""done":["idle"],"idle":[["start","busy"]]},"#,
+            r#""initial_states":["idle","done"]}"#,
+        )
+    );
+}
+
+#[test]
+fn model_is_checked() {
+    let dangling = r#"{"states":[{"name":"s1","variables":[]}],"edges":{"s1":["s2"]},"initial_states":["s1"]}"#;
+    let err = serde_json::from_str::<Model>(dangling).unwrap_err();
+    assert!(err.to_string().contains("Edge points to state s2"));
+    let unknown = r#"{"states":[],"edges":{},"initial_states":[],"extra":1}"#;
+    assert!(serde_json::from_str::<Model>(unknown).is_err());
+}
+
+#[test]
+fn state_json() {
+    let json = serde_json::to_string(&state("s", &["b", "a"])).unwrap();
+    assert_eq!(json, r#"{"name":"s","variables":["a","b"]}"#);
+    let back: State = serde_json::from_str(&json).unwrap();
+    assert_eq!(
+        back.vars(),
+        &HashSet::from(["a".to_string(), "b".to_string()])
+    );
+}
+
+#[test]
+fn formula_json() {
+    let formula = parse_ctl("E[p U AX !q] or TOP").unwrap();
+    let json = serde_json::to_string(&formula).unwrap();
+    assert_eq!(
+        json,
+        r#"{"Or":[{"EU":[{"Atomic":"p"},{"AX":{"Neg":{"Atomic":"q"}}}]},"Top"]}"#
+    );
+    assert_eq!(serde_json::from_str::<CTLFormula>(&json).unwrap(), *formula);
+
+    let formula = parse_ltl("G (p -> F q) W X r").unwrap();
+    let json = serde_json::to_string(&formula).unwrap();
+    assert_eq!(serde_json::from_str::<LTLFormula>(&json).unwrap(), *formula);
+}